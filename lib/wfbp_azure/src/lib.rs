@@ -1 +1,2 @@
 pub mod functions;
+pub mod logging;
@@ -0,0 +1,59 @@
+use anyhow::{anyhow, Context};
+use serde::Deserialize;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+/// How log messages are formatted.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Short single-line messages.
+    Compact,
+    /// Newline-delimited JSON.
+    Json,
+    /// Multi-line, human-readable messages.
+    Pretty,
+}
+
+/// Logging settings, read before the rest of the config so startup errors are
+/// logged. Debug builds default to compact logs at the debug level, and
+/// release builds default to JSON logs at the info level.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LoggingConfig {
+    #[serde(default)]
+    pub log_format: Option<LogFormat>,
+    /// One of `trace`, `debug`, `info`, `warn` or `error`.
+    #[serde(default)]
+    pub log_level: Option<String>,
+}
+
+impl LoggingConfig {
+    /// Registers the global tracing subscriber.
+    pub fn init(&self) -> anyhow::Result<()> {
+        let level = match self.log_level.as_deref() {
+            Some(level) => level.parse().map_err(|_| {
+                anyhow!(
+                    "invalid log level '{level}', expected one of trace, \
+                     debug, info, warn or error"
+                )
+            })?,
+            None if cfg!(debug_assertions) => Level::DEBUG,
+            None => Level::INFO,
+        };
+        let format = self.log_format.unwrap_or(if cfg!(debug_assertions) {
+            LogFormat::Compact
+        } else {
+            LogFormat::Json
+        });
+
+        let builder = FmtSubscriber::builder().with_max_level(level);
+        let result = match format {
+            LogFormat::Compact => builder.compact().try_init(),
+            LogFormat::Json => builder.json().try_init(),
+            LogFormat::Pretty => builder.pretty().try_init(),
+        };
+        result
+            .map_err(|error| anyhow!(error))
+            .context("error registering tracing subscriber")
+    }
+}
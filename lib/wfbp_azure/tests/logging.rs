@@ -0,0 +1,47 @@
+use serde_json::json;
+use wfbp_azure::logging::{LogFormat, LoggingConfig};
+
+#[test]
+fn log_format_is_parsed() {
+    let config: LoggingConfig = serde_json::from_value(json!({
+        "log_format": "json",
+        "log_level": "warn",
+    }))
+    .unwrap();
+    assert_eq!(config.log_format, Some(LogFormat::Json));
+    assert_eq!(config.log_level.as_deref(), Some("warn"));
+
+    let formats: Vec<LogFormat> =
+        serde_json::from_value(json!(["compact", "json", "pretty"])).unwrap();
+    assert_eq!(
+        formats,
+        [LogFormat::Compact, LogFormat::Json, LogFormat::Pretty]
+    );
+
+    // Both settings are optional
+    let config: LoggingConfig = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(config.log_format, None);
+    assert_eq!(config.log_level, None);
+}
+
+#[test]
+fn invalid_log_format_is_rejected() {
+    let result = serde_json::from_value::<LoggingConfig>(json!({
+        "log_format": "verbose",
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn invalid_log_level_is_rejected() {
+    let config = LoggingConfig {
+        log_format: None,
+        log_level: Some("loud".to_owned()),
+    };
+    let error = config.init().unwrap_err();
+    assert!(
+        error.to_string().contains("invalid log level 'loud'"),
+        "unexpected error: {}",
+        error
+    );
+}
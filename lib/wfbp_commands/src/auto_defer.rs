@@ -0,0 +1,172 @@
+use crate::{
+    CommandCallback, CommandOptionRegistry, HandleInteractionError,
+    InteractionData, InteractionResponder, ResponseState, SlashCommandData,
+};
+use async_trait::async_trait;
+use std::{future::Future, sync::Arc, time::Duration};
+use wfbp_discord::{models::CreateWebhookMessageBuilder, DiscordRestClient};
+
+/// How an interaction was responded to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResponseKind {
+    /// The handler's message was sent as the initial response.
+    Initial,
+    /// The interaction was deferred, either before it reached the handler or
+    /// because the handler took too long, and the handler's message replaced
+    /// the loading message.
+    Deferred,
+}
+
+/// Sends a handler's response to an interaction, deferring the response if
+/// the handler takes too long so the interaction doesn't time out.
+#[derive(Clone, Copy, Debug)]
+pub struct ResponseBudget {
+    budget: Duration,
+}
+
+impl ResponseBudget {
+    /// How long a handler has to respond before the response is deferred.
+    /// Discord requires an initial response within 3 seconds.
+    pub const DEFAULT_BUDGET: Duration = Duration::from_millis(2500);
+
+    pub fn new() -> Self {
+        Self::with_budget(Self::DEFAULT_BUDGET)
+    }
+
+    pub fn with_budget(budget: Duration) -> Self {
+        ResponseBudget { budget }
+    }
+
+    /// How much of the budget is left for an interaction, measured from when
+    /// Discord created it.
+    pub fn remaining(&self, interaction_data: &InteractionData) -> Duration {
+        self.budget
+            .checked_sub(interaction_data.age())
+            .unwrap_or_default()
+    }
+
+    /// Waits for the handler to produce a message. If nothing has been sent
+    /// for the interaction and the handler finishes within the budget, the
+    /// message is sent as the initial response. Otherwise, the interaction is
+    /// deferred if it hasn't been already, and the message replaces the
+    /// loading message once it's ready.
+    pub async fn respond<F>(
+        &self,
+        responder: &InteractionResponder,
+        handler: F,
+    ) -> Result<ResponseKind, HandleInteractionError>
+    where
+        F: Future<
+            Output = Result<
+                CreateWebhookMessageBuilder,
+                HandleInteractionError,
+            >,
+        >,
+    {
+        tokio::pin!(handler);
+        if responder.state() == ResponseState::Pending {
+            // A timeout still polls the handler once, so an exhausted budget
+            // is checked separately
+            let remaining = self.remaining(responder.interaction_data());
+            if !remaining.is_zero() {
+                let result =
+                    tokio::time::timeout(remaining, &mut handler).await;
+                if let Ok(message) = result {
+                    responder.respond(message?).await?;
+                    return Ok(ResponseKind::Initial);
+                }
+            }
+
+            responder.defer().await?;
+        }
+
+        let message = handler.await?;
+        responder.respond(message).await?;
+        Ok(ResponseKind::Deferred)
+    }
+}
+
+impl Default for ResponseBudget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A command callback which produces a message to respond with instead of
+/// sending the response itself.
+#[async_trait]
+pub trait ResponseCallback: Send + Sync + 'static {
+    async fn respond<'a>(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        invoke_data: &'a SlashCommandData,
+        options: CommandOptionRegistry<'a>,
+    ) -> Result<CreateWebhookMessageBuilder, HandleInteractionError>;
+}
+
+/// Wraps a [`ResponseCallback`] so its message is sent within the
+/// interaction's [`ResponseBudget`].
+pub struct AutoDeferCallback<C> {
+    callback: C,
+    discord_client: DiscordRestClient,
+    budget: ResponseBudget,
+    deferred: bool,
+}
+
+impl<C> AutoDeferCallback<C> {
+    pub fn new(
+        callback: C,
+        discord_client: DiscordRestClient,
+        budget: ResponseBudget,
+    ) -> Self {
+        AutoDeferCallback {
+            callback,
+            discord_client,
+            budget,
+            deferred: false,
+        }
+    }
+
+    /// Marks interactions as already deferred when they reach the callback,
+    /// so the message always replaces the loading message.
+    pub fn already_deferred(mut self) -> Self {
+        self.deferred = true;
+        self
+    }
+}
+
+#[async_trait]
+impl<C> CommandCallback for AutoDeferCallback<C>
+where
+    C: ResponseCallback,
+{
+    async fn invoke<'a>(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        invoke_data: &'a SlashCommandData,
+        options: CommandOptionRegistry<'a>,
+    ) -> Result<(), HandleInteractionError> {
+        let discord_client = self.discord_client.clone();
+        let responder = if self.deferred {
+            InteractionResponder::deferred(
+                discord_client,
+                interaction_data.clone(),
+            )
+        } else {
+            InteractionResponder::new(discord_client, interaction_data.clone())
+        };
+
+        let handler =
+            self.callback
+                .respond(interaction_data, invoke_data, options);
+        let kind = self.budget.respond(&responder, handler).await?;
+        if kind == ResponseKind::Deferred && !self.deferred {
+            tracing::debug!(
+                command = %invoke_data.name,
+                "response was deferred after exceeding the time budget"
+            );
+        }
+
+        Ok(())
+    }
+}
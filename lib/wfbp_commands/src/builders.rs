@@ -1,10 +1,14 @@
 use crate::{
-    Choice, CommandCallback, CommandOption, CommandOptionType, SlashCommand,
+    Choice, CommandCallback, CommandOption, CommandOptionType,
+    InvalidCommandError, SlashCommand,
 };
 use std::{
     borrow::Cow,
     fmt::{Debug, Formatter},
 };
+use wfbp_discord::models::{
+    ApplicationIntegrationType, InteractionContextType,
+};
 
 macro_rules! builder {
     (@default_ty $_:ty) => {
@@ -224,6 +228,44 @@ macro_rules! option_builder {
     };
 }
 
+macro_rules! string_option_shorthands {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl<Name, Desc> $name<Name, Desc> {
+                /// Adds a required string option.
+                #[inline]
+                pub fn required_string(
+                    self,
+                    name: impl Into<Cow<'static, str>>,
+                    description: impl Into<Cow<'static, str>>,
+                ) -> Self {
+                    self.string_option(|builder| {
+                        builder
+                            .name(name)
+                            .description(description)
+                            .required(true)
+                    })
+                }
+
+                /// Adds an optional string option.
+                #[inline]
+                pub fn optional_string(
+                    self,
+                    name: impl Into<Cow<'static, str>>,
+                    description: impl Into<Cow<'static, str>>,
+                ) -> Self {
+                    self.string_option(|builder| {
+                        builder
+                            .name(name)
+                            .description(description)
+                            .required(false)
+                    })
+                }
+            }
+        )*
+    };
+}
+
 #[macro_export]
 macro_rules! create_callback {
     {
@@ -261,6 +303,20 @@ macro_rules! create_callback {
             $($field_name: $field_init,)*
         }
     }};
+    {
+        context: $ctx_name:ident : $ctx_ty:ty = $ctx_init:expr,
+        handler: async
+            |$interaction_data:pat_param, $command_data:pat_param, $options:pat_param|
+            $handler:expr
+        $(,)?
+    } => {
+        $crate::create_callback! {
+            capture: {
+                $ctx_name: $ctx_ty = $ctx_init,
+            },
+            handler: async |$interaction_data, $command_data, $options| $handler,
+        }
+    };
 }
 
 builder! {
@@ -271,10 +327,14 @@ builder! {
     },
     optional = {
         default_permission: bool,
+        contexts: Vec<InteractionContextType> as impl IntoIterator<Item = InteractionContextType> = contexts.into_iter().collect(),
+        integration_types: Vec<ApplicationIntegrationType> as impl IntoIterator<Item = ApplicationIntegrationType> = integration_types.into_iter().collect(),
+        long_help: Cow<'static, str> as impl Into<Cow<'static, str>> = long_help.into(),
     },
     extra = {
         options: Vec<CommandOption> = Vec::new(),
         [hide] callback: Option<Box<dyn CommandCallback>> = None,
+        examples: Vec<Cow<'static, str>> = Vec::new(),
     },
     ready = ReadyCommandBuilder,
     build = |builder| -> SlashCommand {
@@ -283,7 +343,11 @@ builder! {
             description: builder.description,
             options: builder.options,
             default_permission: builder.default_permission,
+            contexts: builder.contexts,
+            integration_types: builder.integration_types,
             callback: builder.callback,
+            long_help: builder.long_help,
+            examples: builder.examples,
         }
     }
 }
@@ -308,6 +372,17 @@ option_builder! {
     ],
 }
 
+string_option_shorthands!(CommandBuilder, SubCommandOptionBuilder);
+
+impl ReadyCommandBuilder {
+    /// Builds the command, checking that its options are nested correctly.
+    pub fn try_build(self) -> Result<SlashCommand, InvalidCommandError> {
+        let command = self.build();
+        command.validate()?;
+        Ok(command)
+    }
+}
+
 impl<Name, Desc> CommandBuilder<Name, Desc> {
     #[inline]
     pub fn callback<C: CommandCallback>(self, callback: C) -> Self {
@@ -322,6 +397,13 @@ impl<Name, Desc> CommandBuilder<Name, Desc> {
         self.callback = Some(callback);
         self
     }
+
+    /// Adds an example usage, shown by the help command.
+    #[inline]
+    pub fn example(mut self, example: impl Into<Cow<'static, str>>) -> Self {
+        self.examples.push(example.into());
+        self
+    }
 }
 
 builder! {
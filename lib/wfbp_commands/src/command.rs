@@ -1,4 +1,4 @@
-use crate::{FromOption, FromOptionError};
+use crate::{FromOption, FromOptionError, UserHasher};
 use async_recursion::async_recursion;
 use async_trait::async_trait;
 use derive_more::{Display, Error};
@@ -6,14 +6,17 @@ use std::{
     borrow::Cow,
     fmt::{Debug, Formatter},
     sync::Arc,
+    time::{Duration, Instant},
 };
+use tracing::{field::Empty, info, info_span, Instrument};
 use wfbp_discord::{
     models::{
         ApplicationCommand, ApplicationCommandInteractionDataOption,
         ApplicationCommandInteractionDataOptionType,
         ApplicationCommandInteractionDataResolved, ApplicationCommandOption,
         ApplicationCommandOptionChoice, ApplicationCommandOptionType,
-        CreateApplicationCommand, GuildMember, Snowflake, User,
+        ApplicationIntegrationType, CreateApplicationCommand, GuildMember,
+        InteractionContextType, Role, Snowflake, Timestamp, User,
     },
     routes::CreateGlobalApplicationCommand,
     DiscordRestClient,
@@ -25,15 +28,34 @@ pub struct SlashCommand {
     pub description: Cow<'static, str>,
     pub options: Vec<CommandOption>,
     pub default_permission: Option<bool>,
+    /// Where the command can be used, or [`None`] for Discord's default.
+    pub contexts: Option<Vec<InteractionContextType>>,
+    /// Where the command is available when the app is installed, or [`None`]
+    /// for Discord's default.
+    pub integration_types: Option<Vec<ApplicationIntegrationType>>,
     pub callback: Option<Box<dyn CommandCallback>>,
+    /// Detailed help shown by the help command instead of the description.
+    /// This is not sent to Discord.
+    pub long_help: Option<Cow<'static, str>>,
+    /// Example usages shown by the help command. These are not sent to
+    /// Discord.
+    pub examples: Vec<Cow<'static, str>>,
 }
 
 impl SlashCommand {
+    /// Checks that the command's options are nested no more than two levels
+    /// deep (group -> subcommand -> options), as required by Discord.
+    pub fn validate(&self) -> Result<(), InvalidCommandError> {
+        validate_options(&self.name, &self.options, OptionParent::Command)
+    }
+
     pub async fn register(
         &self,
         client: &DiscordRestClient,
         application_id: Snowflake,
     ) -> Result<ApplicationCommand, RequestError> {
+        self.validate()
+            .map_err(|error| RequestError::Custom(error.into()))?;
         CreateGlobalApplicationCommand::execute(
             client,
             application_id,
@@ -42,10 +64,48 @@ impl SlashCommand {
         .await
     }
 
+    /// Handles an invocation of this command inside a span describing the
+    /// invocation, which records the outcome and duration once the command
+    /// completes. The invoking user is only recorded if a [`UserHasher`] is
+    /// given, and only as a hash of their ID.
     pub async fn handle(
         &self,
         interaction_data: Arc<InteractionData>,
         root_data: SlashCommandData,
+        user_hasher: Option<&UserHasher>,
+    ) -> Result<(), HandleInteractionError> {
+        let subcommand = root_data.subcommand_path();
+        let subcommand = (!subcommand.is_empty()).then(|| subcommand.join(" "));
+        let user =
+            user_hasher.and_then(|hasher| interaction_data.user_hash(hasher));
+        let span = info_span!(
+            "command",
+            invocation_id = %interaction_data.id,
+            command = %root_data.name,
+            subcommand = subcommand.as_deref(),
+            guild_id = interaction_data.guild_id.map(Snowflake::to_u64),
+            user = user.as_deref(),
+            outcome = Empty,
+            duration_ms = Empty,
+        );
+
+        let started = Instant::now();
+        let result = self
+            .handle_inner(interaction_data, &root_data)
+            .instrument(span.clone())
+            .await;
+        let outcome = if result.is_ok() { "ok" } else { "err" };
+        span.record("outcome", &outcome);
+        span.record("duration_ms", &(started.elapsed().as_millis() as u64));
+        span.in_scope(|| info!("command finished"));
+
+        result
+    }
+
+    async fn handle_inner(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        root_data: &SlashCommandData,
     ) -> Result<(), HandleInteractionError> {
         if root_data.name != self.name {
             return Err(HandleInteractionError::UnknownCommand(
@@ -57,7 +117,7 @@ impl SlashCommand {
         if let Some(callback) = self.callback.as_ref() {
             execute_callback(
                 interaction_data.clone(),
-                &root_data,
+                root_data,
                 &root_data.options,
                 callback.as_ref(),
             )
@@ -67,7 +127,7 @@ impl SlashCommand {
         // Options
         handle_options(
             interaction_data.clone(),
-            &root_data,
+            root_data,
             &self.options,
             root_data.options.iter(),
         )
@@ -77,13 +137,55 @@ impl SlashCommand {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum OptionParent {
+    Command,
+    SubCommandGroup,
+    SubCommand,
+}
+
+fn validate_options(
+    path: &str,
+    options: &[CommandOption],
+    parent: OptionParent,
+) -> Result<(), InvalidCommandError> {
+    for option in options {
+        let path = format!("{} {}", path, option.name);
+        match (&option.kind, parent) {
+            (
+                CommandOptionType::SubCommandGroup { options },
+                OptionParent::Command,
+            ) => {
+                validate_options(&path, options, OptionParent::SubCommandGroup)?
+            }
+            (CommandOptionType::SubCommandGroup { .. }, _) => {
+                return Err(InvalidCommandError::NestedSubCommandGroup(path))
+            }
+            (
+                CommandOptionType::SubCommand { options, .. },
+                OptionParent::Command | OptionParent::SubCommandGroup,
+            ) => validate_options(&path, options, OptionParent::SubCommand)?,
+            (CommandOptionType::SubCommand { .. }, _) => {
+                return Err(InvalidCommandError::NestedSubCommand(path))
+            }
+            (_, OptionParent::SubCommandGroup) => {
+                return Err(InvalidCommandError::OptionInSubCommandGroup(path))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
 async fn execute_callback<C: ?Sized + CommandCallback>(
     interaction_data: Arc<InteractionData>,
     root_data: &SlashCommandData,
     option_data: &[ApplicationCommandInteractionDataOption],
     callback: &C,
 ) -> Result<(), HandleInteractionError> {
-    let option_registry = CommandOptionRegistry::new(option_data);
+    let option_registry = CommandOptionRegistry::new(option_data)
+        .with_resolved(&root_data.resolved);
     callback
         .invoke(interaction_data, &root_data, option_registry)
         .await?;
@@ -117,6 +219,10 @@ impl Debug for SlashCommand {
             .field("description", &self.description)
             .field("options", &self.options)
             .field("default_permission", &self.default_permission)
+            .field("contexts", &self.contexts)
+            .field("integration_types", &self.integration_types)
+            .field("long_help", &self.long_help)
+            .field("examples", &self.examples)
             .finish_non_exhaustive()
     }
 }
@@ -160,6 +266,51 @@ pub struct InteractionData {
     pub channel_id: Snowflake,
     pub member: Option<GuildMember>,
     pub user: Option<User>,
+    pub locale: Option<String>,
+    pub guild_locale: Option<String>,
+    /// When the interaction was received by the bot.
+    pub received_at: Timestamp,
+}
+
+impl InteractionData {
+    /// How long an interaction token can be used to respond to an
+    /// interaction.
+    pub const TOKEN_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+    /// How long ago the interaction was created.
+    pub fn age(&self) -> Duration {
+        (Timestamp::now().0 - self.id.created_at().0)
+            .to_std()
+            .unwrap_or_default()
+    }
+
+    /// How much longer the interaction token can be used for.
+    pub fn time_remaining(&self) -> Duration {
+        Self::TOKEN_LIFETIME
+            .checked_sub(self.age())
+            .unwrap_or_default()
+    }
+
+    /// Whether the interaction token has expired and can no longer be used to
+    /// respond to the interaction.
+    pub fn is_token_expired(&self) -> bool {
+        self.time_remaining().is_zero()
+    }
+
+    /// The ID of the user who invoked the interaction.
+    pub fn user_id(&self) -> Option<Snowflake> {
+        self.member
+            .as_ref()
+            .and_then(GuildMember::user)
+            .or(self.user.as_ref())
+            .map(User::id)
+    }
+
+    /// A hash of the invoking user's ID, so their commands can be correlated
+    /// in logs without recording the ID itself.
+    pub fn user_hash(&self, hasher: &UserHasher) -> Option<String> {
+        self.user_id().map(|user_id| hasher.hash(user_id))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -170,6 +321,30 @@ pub struct SlashCommandData {
     pub options: Vec<ApplicationCommandInteractionDataOption>,
 }
 
+impl SlashCommandData {
+    /// The names of the invoked subcommand group and subcommand, if any.
+    pub fn subcommand_path(&self) -> Vec<&str> {
+        let mut path = Vec::new();
+        let mut options = self.options.as_slice();
+        while let Some(option) = options.first() {
+            match &option.kind {
+                ApplicationCommandInteractionDataOptionType::SubCommand {
+                    options: inner,
+                }
+                | ApplicationCommandInteractionDataOptionType::SubCommandGroup {
+                    options: inner,
+                } => {
+                    path.push(option.name.as_str());
+                    options = inner.as_deref().unwrap_or_default();
+                }
+                _ => break,
+            }
+        }
+
+        path
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Display, Error)]
 pub enum HandleInteractionError {
@@ -184,15 +359,49 @@ pub enum HandleInteractionError {
     #[display(fmt = "missing input options from interaction data")]
     MissingOptions,
     #[display(fmt = "{}", _0)]
+    Request(RequestError),
+    #[display(fmt = "{}", _0)]
     Custom(#[error(ignore)] anyhow::Error),
 }
 
+impl HandleInteractionError {
+    /// The request error that caused this error, if any. This also finds
+    /// request errors that were given context with [`anyhow::Context`].
+    pub fn request_error(&self) -> Option<&RequestError> {
+        match self {
+            HandleInteractionError::Request(error) => Some(error),
+            HandleInteractionError::Custom(error) => error.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug, Display, Error)]
+pub enum InvalidCommandError {
+    #[display(fmt = "subcommand group '{}' must be a top-level option", _0)]
+    NestedSubCommandGroup(#[error(ignore)] String),
+    #[display(
+        fmt = "subcommand '{}' cannot be nested under another subcommand",
+        _0
+    )]
+    NestedSubCommand(#[error(ignore)] String),
+    #[display(fmt = "subcommand group option '{}' must be a subcommand", _0)]
+    OptionInSubCommandGroup(#[error(ignore)] String),
+}
+
 impl From<anyhow::Error> for HandleInteractionError {
     fn from(error: anyhow::Error) -> Self {
         HandleInteractionError::Custom(error)
     }
 }
 
+impl From<RequestError> for HandleInteractionError {
+    fn from(error: RequestError) -> Self {
+        HandleInteractionError::Request(error)
+    }
+}
+
 impl From<GetOptionError> for HandleInteractionError {
     fn from(error: GetOptionError) -> Self {
         HandleInteractionError::OptionError(error)
@@ -210,6 +419,8 @@ impl From<&SlashCommand> for CreateApplicationCommand {
                 Some(command.options.iter().map(Into::into).collect())
             },
             default_permission: command.default_permission,
+            contexts: command.contexts.clone(),
+            integration_types: command.integration_types.clone(),
         }
     }
 }
@@ -266,24 +477,12 @@ impl CommandOption {
         root_data: &SlashCommandData,
         invoke_data: &ApplicationCommandInteractionDataOption,
     ) -> Result<(), HandleInteractionError> {
-        match &self.kind {
-            CommandOptionType::SubCommand {
-                options,
-                callback: Some(ref callback),
-            } => {
+        match (&self.kind, InvokedOption::from(invoke_data)) {
+            (
+                CommandOptionType::SubCommand { options, callback },
+                InvokedOption::SubCommand(option_data),
+            ) => {
                 // Options
-                let option_data =
-                    match &invoke_data.kind {
-                        ApplicationCommandInteractionDataOptionType::SubCommand {
-                            options,
-                        } => options
-                            .as_ref()
-                            .map(Vec::as_slice)
-                            .unwrap_or(&[]),
-                    _ => return Err(HandleInteractionError::InvalidData(
-                        self.name.to_string(),
-                    ))
-                };
                 handle_options(
                     interaction_data.clone(),
                     &root_data,
@@ -293,41 +492,69 @@ impl CommandOption {
                 .await?;
 
                 // Callback
-                execute_callback(
-                    interaction_data.clone(),
-                    root_data,
-                    option_data,
-                    callback.as_ref(),
-                )
-                .await?;
+                if let Some(callback) = callback {
+                    execute_callback(
+                        interaction_data.clone(),
+                        root_data,
+                        option_data,
+                        callback.as_ref(),
+                    )
+                    .await?;
+                }
 
                 Ok(())
             }
-            CommandOptionType::SubCommandGroup { options } => {
-                let option_data = match &invoke_data.kind {
-                    ApplicationCommandInteractionDataOptionType::SubCommandGroup {
-                        options
-                    } => options
-                        .as_ref()
-                        .map(Vec::as_slice)
-                        .unwrap_or(&[]),
-                    _ => return Err(HandleInteractionError::InvalidData(
-                        self.name.to_string(),
-                    ))
-                };
-
-                // Options
+            (
+                CommandOptionType::SubCommandGroup { options },
+                InvokedOption::SubCommandGroup(option_data),
+            ) => {
                 handle_options(
                     interaction_data.clone(),
                     &root_data,
                     options,
                     option_data,
                 )
-                .await?;
-
-                Ok(())
+                .await
             }
-            _ => Ok(()),
+            (
+                CommandOptionType::SubCommand { .. }
+                | CommandOptionType::SubCommandGroup { .. },
+                _,
+            )
+            | (_, InvokedOption::SubCommand(_))
+            | (_, InvokedOption::SubCommandGroup(_)) => {
+                Err(HandleInteractionError::InvalidData(self.name.to_string()))
+            }
+            (_, InvokedOption::Value) => Ok(()),
+        }
+    }
+}
+
+/// An option from an interaction, classified by how it is dispatched.
+#[derive(Clone, Copy, Debug)]
+pub enum InvokedOption<'a> {
+    /// A subcommand and the options passed to it.
+    SubCommand(&'a [ApplicationCommandInteractionDataOption]),
+    /// A subcommand group and the subcommand invoked within it.
+    SubCommandGroup(&'a [ApplicationCommandInteractionDataOption]),
+    /// An option with a value.
+    Value,
+}
+
+impl<'a> From<&'a ApplicationCommandInteractionDataOption>
+    for InvokedOption<'a>
+{
+    fn from(option: &'a ApplicationCommandInteractionDataOption) -> Self {
+        match &option.kind {
+            ApplicationCommandInteractionDataOptionType::SubCommand {
+                options,
+            } => InvokedOption::SubCommand(options.as_deref().unwrap_or(&[])),
+            ApplicationCommandInteractionDataOptionType::SubCommandGroup {
+                options,
+            } => InvokedOption::SubCommandGroup(
+                options.as_deref().unwrap_or(&[]),
+            ),
+            _ => InvokedOption::Value,
         }
     }
 }
@@ -482,11 +709,24 @@ impl<S: Clone + Into<T>, T> From<&Choice<S>>
 
 pub struct CommandOptionRegistry<'a> {
     options: &'a [ApplicationCommandInteractionDataOption],
+    resolved: Option<&'a ApplicationCommandInteractionDataResolved>,
 }
 
 impl<'a> CommandOptionRegistry<'a> {
     pub fn new(options: &'a [ApplicationCommandInteractionDataOption]) -> Self {
-        CommandOptionRegistry { options }
+        CommandOptionRegistry {
+            options,
+            resolved: None,
+        }
+    }
+
+    /// Sets the users, roles, etc. that were resolved from the options.
+    pub fn with_resolved(
+        mut self,
+        resolved: &'a ApplicationCommandInteractionDataResolved,
+    ) -> Self {
+        self.resolved = Some(resolved);
+        self
     }
 
     pub fn get_raw_option(
@@ -513,11 +753,56 @@ impl<'a> CommandOptionRegistry<'a> {
             .iter()
             .find(|option| option.name == name)
             .map(|option| {
-                T::from_option(option)
-                    .map_err(GetOptionError::InvalidOptionValue)
+                T::from_option(option).map_err(|error| {
+                    GetOptionError::InvalidOptionValue {
+                        name: name.to_string(),
+                        error,
+                    }
+                })
             })
             .transpose()
     }
+
+    /// Gets whether a mentionable option refers to a user or a role, based on
+    /// the resolved data sent with the interaction.
+    pub fn get_mentionable(
+        &self,
+        name: &str,
+    ) -> Result<MentionableTarget<'a>, GetOptionError> {
+        let id: Snowflake = self.get_option(name)?;
+        let resolved = self.resolved.ok_or_else(|| {
+            GetOptionError::UnresolvedMentionable(name.to_string())
+        })?;
+
+        if let Some(role) = resolved.roles.as_ref().and_then(|r| r.get(&id)) {
+            return Ok(MentionableTarget::Role(id, Some(role)));
+        }
+        if let Some(user) = resolved.users.as_ref().and_then(|u| u.get(&id)) {
+            return Ok(MentionableTarget::User(id, Some(user)));
+        }
+        if resolved.members.as_ref().and_then(|m| m.get(&id)).is_some() {
+            return Ok(MentionableTarget::User(id, None));
+        }
+
+        Err(GetOptionError::UnresolvedMentionable(name.to_string()))
+    }
+}
+
+/// What a mentionable option refers to.
+#[derive(Clone, Copy, Debug)]
+pub enum MentionableTarget<'a> {
+    User(Snowflake, Option<&'a User>),
+    Role(Snowflake, Option<&'a Role>),
+}
+
+impl MentionableTarget<'_> {
+    /// The ID of the user or role.
+    pub fn id(&self) -> Snowflake {
+        match *self {
+            MentionableTarget::User(id, _) => id,
+            MentionableTarget::Role(id, _) => id,
+        }
+    }
 }
 
 #[derive(Debug, Display, Error)]
@@ -525,8 +810,17 @@ impl<'a> CommandOptionRegistry<'a> {
 pub enum GetOptionError {
     #[display(fmt = "option not found: '{}'", _0)]
     MissingOption(#[error(ignore)] String),
-    #[display(fmt = "invalid option")]
-    InvalidOptionValue(FromOptionError),
+    #[display(fmt = "invalid value for option '{}': {}", name, error)]
+    InvalidOptionValue {
+        name: String,
+        #[error(source)]
+        error: FromOptionError,
+    },
+    #[display(
+        fmt = "couldn't tell whether option '{}' is a user or a role",
+        _0
+    )]
+    UnresolvedMentionable(#[error(ignore)] String),
     #[display(fmt = "{}", _0)]
     Custom(#[error(ignore)] anyhow::Error),
 }
@@ -1,19 +1,61 @@
-use crate::{InteractionData, SlashCommand, SlashCommandData};
+use crate::{
+    ComponentHandler, ComponentRouter, InteractionData, NoopRegistrationLock,
+    RegistrationLock, SlashCommand, SlashCommandData, UserHasher,
+};
 use anyhow::{bail, Context};
-use std::{borrow::Cow, collections::HashMap, sync::Arc};
+use serde_json::Value;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
 use tokio::sync::RwLock;
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, info, instrument, warn};
 use wfbp_discord::{
     models::{
-        ApplicationCommandInteractionData, Interaction, InteractionType,
-        Snowflake,
+        ApplicationCommand, ApplicationCommandInteractionData, ComponentType,
+        CreateApplicationCommand, CreateWebhookMessage, Embed, Interaction,
+        InteractionType, Snowflake, Timestamp,
+    },
+    routes::{
+        BulkOverwriteGlobalApplicationCommands, CreateFollowupMessage,
+        GetGlobalApplicationCommands,
     },
-    routes::BulkOverwriteGlobalApplicationCommands,
     DiscordRestClient,
 };
 
+const GUILD_NOT_ALLOWED_MESSAGE: &str =
+    "This bot isn't enabled in this server.";
+
+/// The differences between the commands in a registry and the commands
+/// registered with Discord, by command name.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CommandDiff {
+    /// Commands which are not registered with Discord yet.
+    pub added: Vec<String>,
+    /// Commands which are registered with Discord but are out of date.
+    pub changed: Vec<String>,
+    /// Commands which are registered with Discord but not in the registry.
+    pub removed: Vec<String>,
+}
+
+impl CommandDiff {
+    /// Whether the registered commands are already up to date.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.changed.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
 pub struct CommandRegistry {
     slash_commands: RwLock<HashMap<Cow<'static, str>, SlashCommand>>,
+    components: RwLock<ComponentRouter>,
+    registration_lock: RwLock<Arc<dyn RegistrationLock>>,
+    disabled_commands: RwLock<HashSet<String>>,
+    allowed_guilds: RwLock<HashSet<Snowflake>>,
+    discord_client: RwLock<Option<DiscordRestClient>>,
+    user_hasher: RwLock<Option<UserHasher>>,
 }
 
 impl CommandRegistry {
@@ -27,17 +69,126 @@ impl CommandRegistry {
                     .map(|command| (command.name.clone(), command))
                     .collect(),
             ),
+            components: RwLock::new(ComponentRouter::new()),
+            registration_lock: RwLock::new(Arc::new(NoopRegistrationLock)),
+            disabled_commands: RwLock::new(HashSet::new()),
+            allowed_guilds: RwLock::new(HashSet::new()),
+            discord_client: RwLock::new(None),
+            user_hasher: RwLock::new(None),
         };
 
         Arc::new(registry)
     }
 
+    /// Adds a handler for message components, replacing any existing handler
+    /// with the same prefix.
+    pub async fn add_component(&self, handler: ComponentHandler) {
+        self.components.write().await.add(handler);
+    }
+
+    /// Sets the lock used to make sure only one instance syncs commands at a
+    /// time. By default, commands are always synced.
+    pub async fn set_registration_lock(&self, lock: impl RegistrationLock) {
+        *self.registration_lock.write().await = Arc::new(lock);
+    }
+
+    /// Sets the client used to tell users when a command is disabled or a
+    /// guild isn't allowed. Without a client, those interactions are skipped
+    /// silently.
+    pub async fn set_discord_client(&self, client: DiscordRestClient) {
+        *self.discord_client.write().await = Some(client);
+    }
+
+    /// Sets the hasher used to record who invoked each command in its
+    /// tracing span. Without a hasher, users aren't recorded.
+    pub async fn set_user_hasher(&self, hasher: UserHasher) {
+        *self.user_hasher.write().await = Some(hasher);
+    }
+
+    /// Enables or disables a command. Disabled commands stay registered with
+    /// Discord, but users are told the command is disabled instead of it
+    /// being executed. Returns `false` if there is no command with that name.
+    pub async fn set_command_enabled(&self, name: &str, enabled: bool) -> bool {
+        if !self.slash_commands.read().await.contains_key(name) {
+            return false;
+        }
+
+        let mut disabled_commands = self.disabled_commands.write().await;
+        if enabled {
+            disabled_commands.remove(name);
+        } else {
+            disabled_commands.insert(name.to_owned());
+        }
+
+        true
+    }
+
+    /// Whether a command is enabled.
+    pub async fn is_command_enabled(&self, name: &str) -> bool {
+        !self.disabled_commands.read().await.contains(name)
+    }
+
+    /// Sets the guilds the bot can be used in. Interactions from other guilds
+    /// are told the bot isn't enabled instead of being handled. If no guilds
+    /// are given, every guild is allowed.
+    pub async fn set_allowed_guilds(
+        &self,
+        guild_ids: impl IntoIterator<Item = Snowflake>,
+    ) {
+        *self.allowed_guilds.write().await = guild_ids.into_iter().collect();
+    }
+
+    /// Whether the bot can be used in a guild. Interactions outside of guilds
+    /// are always allowed.
+    pub async fn is_guild_allowed(&self, guild_id: Option<Snowflake>) -> bool {
+        let allowed_guilds = self.allowed_guilds.read().await;
+        match guild_id {
+            Some(guild_id) => {
+                allowed_guilds.is_empty() || allowed_guilds.contains(&guild_id)
+            }
+            None => true,
+        }
+    }
+
+    /// Detailed help for a command, or [`None`] if there is no command with
+    /// that name.
+    pub async fn help_embed(&self, name: &str) -> Option<Embed> {
+        self.slash_commands
+            .read()
+            .await
+            .get(name)
+            .map(SlashCommand::help_embed)
+    }
+
+    /// Lists each command with its description.
+    pub async fn help_overview(&self) -> Embed {
+        let slash_commands = self.slash_commands.read().await;
+        let mut commands: Vec<_> = slash_commands.values().collect();
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        let description = commands
+            .into_iter()
+            .map(|command| {
+                format!("`/{}`: {}", command.name, command.description)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Embed {
+            title: Some("Commands".into()),
+            description: Some(description),
+            ..Default::default()
+        }
+    }
+
     pub async fn register_commands(
         &self,
         client: &DiscordRestClient,
         app_id: Snowflake,
     ) -> anyhow::Result<()> {
         let slash_commands = self.slash_commands.read().await;
+        for command in slash_commands.values() {
+            command.validate().context("invalid command")?;
+        }
         let commands = slash_commands.values().map(Into::into).collect();
 
         let result = BulkOverwriteGlobalApplicationCommands::execute(
@@ -52,6 +203,98 @@ impl CommandRegistry {
         Ok(())
     }
 
+    /// Compares the commands in the registry to the commands registered with
+    /// Discord.
+    pub async fn diff(&self, registered: &[ApplicationCommand]) -> CommandDiff {
+        let slash_commands = self.slash_commands.read().await;
+        let local: BTreeMap<_, _> = slash_commands
+            .values()
+            .map(|command| {
+                let command: CreateApplicationCommand = command.into();
+                (command_name(&command).to_owned(), normalize(&command))
+            })
+            .collect();
+        let remote: BTreeMap<_, _> = registered
+            .iter()
+            .map(|command| {
+                let command = to_create_command(command);
+                (command_name(&command).to_owned(), normalize(&command))
+            })
+            .collect();
+
+        let mut diff = CommandDiff::default();
+        for (name, command) in local.iter() {
+            match remote.get(name) {
+                None => diff.added.push(name.clone()),
+                Some(registered) if registered != command => {
+                    diff.changed.push(name.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = remote
+            .keys()
+            .filter(|name| !local.contains_key(*name))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Registers the commands with Discord if they differ from the commands
+    /// already registered, or unconditionally if `force` is set. If another
+    /// instance holds the registration lock, nothing is synced and `None` is
+    /// returned.
+    #[instrument(skip(self, client))]
+    pub async fn sync_commands(
+        &self,
+        client: &DiscordRestClient,
+        app_id: Snowflake,
+        force: bool,
+    ) -> anyhow::Result<Option<CommandDiff>> {
+        let lock = self.registration_lock.read().await.clone();
+        let acquired = lock
+            .try_acquire()
+            .await
+            .context("error acquiring registration lock")?;
+        if !acquired {
+            info!("another instance is syncing application commands");
+            return Ok(None);
+        }
+
+        let result = self.sync_commands_locked(client, app_id, force).await;
+        if let Err(error) = lock.release().await {
+            warn!(?error, "error releasing registration lock");
+        }
+
+        result.map(Some)
+    }
+
+    async fn sync_commands_locked(
+        &self,
+        client: &DiscordRestClient,
+        app_id: Snowflake,
+        force: bool,
+    ) -> anyhow::Result<CommandDiff> {
+        let registered = GetGlobalApplicationCommands::execute(client, app_id)
+            .await
+            .context("error getting application commands")?;
+        let diff = self.diff(&registered).await;
+        if diff.is_empty() && !force {
+            info!("application commands are up to date");
+            return Ok(diff);
+        }
+
+        info!(
+            added = ?diff.added,
+            changed = ?diff.changed,
+            removed = ?diff.removed,
+            force,
+            "overwriting application commands"
+        );
+        self.register_commands(client, app_id).await?;
+        Ok(diff)
+    }
+
     #[instrument(skip(self, interaction))]
     pub async fn handle_interaction(
         &self,
@@ -65,6 +308,8 @@ impl CommandRegistry {
                 channel_id,
                 member,
                 user,
+                locale,
+                guild_locale,
             } => {
                 debug!("handling application command");
                 let interaction_data = Arc::new(InteractionData {
@@ -75,8 +320,27 @@ impl CommandRegistry {
                     channel_id,
                     member,
                     user,
+                    locale,
+                    guild_locale,
+                    received_at: Timestamp::now(),
                 });
 
+                // Responding with an expired token would fail anyway
+                if interaction_data.is_token_expired() {
+                    warn!(
+                        age = ?interaction_data.age(),
+                        "interaction token expired, skipping command"
+                    );
+                    return Ok(());
+                }
+
+                if !self.is_guild_allowed(interaction_data.guild_id).await {
+                    info!(guild_id = ?interaction_data.guild_id, "guild is not allowed");
+                    return self
+                        .notify(&interaction_data, GUILD_NOT_ALLOWED_MESSAGE)
+                        .await;
+                }
+
                 match data {
                     ApplicationCommandInteractionData::ChatInput {
                         id,
@@ -90,6 +354,15 @@ impl CommandRegistry {
                             Some(command) => command,
                             None => bail!("command not found: '{}'", name),
                         };
+                        if !self.is_command_enabled(&name).await {
+                            info!(%name, "command is disabled");
+                            return self
+                                .notify(
+                                    &interaction_data,
+                                    "This command is temporarily disabled.",
+                                )
+                                .await;
+                        }
 
                         let command_data = SlashCommandData {
                             command_id: id,
@@ -98,8 +371,13 @@ impl CommandRegistry {
                             options: options.unwrap_or_default(),
                         };
 
+                        let user_hasher = self.user_hasher.read().await;
                         command
-                            .handle(interaction_data, command_data)
+                            .handle(
+                                interaction_data,
+                                command_data,
+                                user_hasher.as_ref(),
+                            )
                             .await
                             .context("error handling command")
                     }
@@ -111,9 +389,172 @@ impl CommandRegistry {
                     }
                 }
             }
-            InteractionType::MessageComponent { .. } => {
-                bail!("message components not implemented")
+            InteractionType::MessageComponent {
+                guild_id,
+                channel_id,
+                member,
+                user,
+                message,
+                component_type,
+            } => {
+                debug!("handling message component");
+                let (custom_id, values) = match component_type {
+                    ComponentType::Button { custom_id, .. } => {
+                        (custom_id, Vec::new())
+                    }
+                    ComponentType::SelectMenu {
+                        custom_id, values, ..
+                    } => (custom_id, values.unwrap_or_default()),
+                    ComponentType::ActionRow => {
+                        bail!("action rows cannot be interacted with")
+                    }
+                };
+                let interaction_data = Arc::new(InteractionData {
+                    id: interaction.id,
+                    application_id: interaction.application_id,
+                    token: interaction.token,
+                    guild_id,
+                    channel_id,
+                    member,
+                    user,
+                    locale: None,
+                    guild_locale: None,
+                    received_at: Timestamp::now(),
+                });
+
+                // Responding with an expired token would fail anyway
+                if interaction_data.is_token_expired() {
+                    warn!(
+                        age = ?interaction_data.age(),
+                        "interaction token expired, skipping component"
+                    );
+                    return Ok(());
+                }
+
+                if !self.is_guild_allowed(interaction_data.guild_id).await {
+                    info!(guild_id = ?interaction_data.guild_id, "guild is not allowed");
+                    return self
+                        .notify(&interaction_data, GUILD_NOT_ALLOWED_MESSAGE)
+                        .await;
+                }
+
+                self.components
+                    .read()
+                    .await
+                    .handle(interaction_data, &custom_id, message, values)
+                    .await
+            }
+        }
+    }
+
+    /// Tells the user why their interaction wasn't handled.
+    async fn notify(
+        &self,
+        interaction_data: &InteractionData,
+        content: &str,
+    ) -> anyhow::Result<()> {
+        let discord_client = self.discord_client.read().await;
+        let discord_client = match discord_client.as_ref() {
+            Some(discord_client) => discord_client,
+            None => return Ok(()),
+        };
+
+        // The dispatcher has already deferred publicly, so the followup
+        // can't be made ephemeral
+        let message = CreateWebhookMessage::builder().content(content).build();
+        CreateFollowupMessage::execute(
+            discord_client,
+            interaction_data.application_id,
+            interaction_data.token.clone(),
+            message,
+        )
+        .await
+        .context("error creating response")?;
+
+        Ok(())
+    }
+}
+
+fn to_create_command(command: &ApplicationCommand) -> CreateApplicationCommand {
+    match command {
+        ApplicationCommand::ChatInput {
+            name,
+            description,
+            options,
+            default_permission,
+            contexts,
+            integration_types,
+            ..
+        } => CreateApplicationCommand::ChatInput {
+            name: name.clone(),
+            description: description.clone(),
+            options: Some(options.clone()),
+            default_permission: *default_permission,
+            contexts: contexts.clone(),
+            integration_types: integration_types.clone(),
+        },
+        ApplicationCommand::User {
+            name,
+            default_permission,
+            contexts,
+            integration_types,
+            ..
+        } => CreateApplicationCommand::User {
+            name: name.clone(),
+            default_permission: *default_permission,
+            contexts: contexts.clone(),
+            integration_types: integration_types.clone(),
+        },
+        ApplicationCommand::Message {
+            name,
+            default_permission,
+            contexts,
+            integration_types,
+            ..
+        } => CreateApplicationCommand::Message {
+            name: name.clone(),
+            default_permission: *default_permission,
+            contexts: contexts.clone(),
+            integration_types: integration_types.clone(),
+        },
+    }
+}
+
+fn command_name(command: &CreateApplicationCommand) -> &str {
+    match command {
+        CreateApplicationCommand::ChatInput { name, .. }
+        | CreateApplicationCommand::User { name, .. }
+        | CreateApplicationCommand::Message { name, .. } => name,
+    }
+}
+
+/// Serializes a command, removing fields which are set to their defaults so
+/// that equivalent commands compare equal.
+fn normalize(command: &CreateApplicationCommand) -> Value {
+    fn strip_defaults(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                map.retain(|key, value| match (key.as_str(), &*value) {
+                    (_, Value::Null) => false,
+                    ("options" | "choices", Value::Array(values)) => {
+                        !values.is_empty()
+                    }
+                    ("required", Value::Bool(false)) => false,
+                    ("default_permission", Value::Bool(true)) => false,
+                    ("integration_types", Value::Array(values)) => {
+                        values.as_slice() != [Value::from(0)]
+                    }
+                    _ => true,
+                });
+                map.values_mut().for_each(strip_defaults);
             }
+            Value::Array(values) => values.iter_mut().for_each(strip_defaults),
+            _ => {}
         }
     }
+
+    let mut value =
+        serde_json::to_value(command).expect("error serializing command");
+    strip_defaults(&mut value);
+    value
 }
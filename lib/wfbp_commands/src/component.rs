@@ -0,0 +1,170 @@
+use crate::{CustomId, HandleInteractionError, InteractionData};
+use anyhow::Context;
+use async_trait::async_trait;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+use wfbp_discord::{
+    models::{AllowedMentions, Component, EditWebhookMessage, Embed, Message},
+    routes::EditOriginalInteractionResponse,
+    DiscordRestClient,
+};
+
+/// Handles interactions with message components whose `custom_id` starts
+/// with a prefix. See [`CustomId`] for how IDs are formatted.
+pub struct ComponentHandler {
+    pub prefix: Cow<'static, str>,
+    pub client: DiscordRestClient,
+    pub callback: Box<dyn ComponentCallback>,
+}
+
+impl ComponentHandler {
+    pub fn new(
+        prefix: impl Into<Cow<'static, str>>,
+        client: DiscordRestClient,
+        callback: impl ComponentCallback,
+    ) -> Self {
+        ComponentHandler {
+            prefix: prefix.into(),
+            client,
+            callback: Box::new(callback),
+        }
+    }
+
+    /// Invokes the callback and applies its response. The dispatcher has
+    /// already acknowledged the interaction with a deferred update, so
+    /// changes are made by editing the original response, which is the
+    /// message the component is attached to.
+    pub async fn handle(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        component_data: ComponentData,
+    ) -> anyhow::Result<()> {
+        let response = self
+            .callback
+            .invoke(interaction_data.clone(), &component_data)
+            .await
+            .context("error handling component")?;
+        match response {
+            ComponentResponse::UpdateMessage(update) => {
+                EditOriginalInteractionResponse::execute(
+                    &self.client,
+                    interaction_data.application_id,
+                    interaction_data.token.clone(),
+                    update.into(),
+                )
+                .await
+                .context("error updating message")?;
+            }
+            ComponentResponse::DeferredUpdateMessage => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for ComponentHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentHandler")
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+pub trait ComponentCallback: Send + Sync + 'static {
+    async fn invoke(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        component_data: &ComponentData,
+    ) -> Result<ComponentResponse, HandleInteractionError>;
+}
+
+#[async_trait]
+impl<F> ComponentCallback for F
+where
+    F: Send + Sync + 'static,
+    F: Fn(
+        Arc<InteractionData>,
+        &ComponentData,
+    ) -> Result<ComponentResponse, HandleInteractionError>,
+{
+    async fn invoke(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        component_data: &ComponentData,
+    ) -> Result<ComponentResponse, HandleInteractionError> {
+        (self)(interaction_data, component_data)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ComponentData {
+    /// The decoded `custom_id` of the component.
+    pub custom_id: CustomId,
+    /// The message the component is attached to.
+    pub message: Message,
+    /// The values of the options selected in a select menu. This is empty
+    /// for buttons.
+    pub values: Vec<String>,
+}
+
+/// A response to a component interaction.
+#[derive(Clone, Debug)]
+pub enum ComponentResponse {
+    /// Edits the message the component is attached to.
+    UpdateMessage(MessageUpdate),
+    /// Leaves the message unchanged. The dispatcher already acknowledged the
+    /// interaction, so the message can still be edited later.
+    DeferredUpdateMessage,
+}
+
+/// Changes to a message. Fields which are set replace the message's existing
+/// values, and fields which are not set are left unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct MessageUpdate {
+    pub content: Option<String>,
+    pub embeds: Option<Vec<Embed>>,
+    pub components: Option<Vec<Component>>,
+}
+
+impl MessageUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the message's content.
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Replaces the message's embeds.
+    pub fn embeds(mut self, embeds: impl IntoIterator<Item = Embed>) -> Self {
+        self.embeds = Some(embeds.into_iter().collect());
+        self
+    }
+
+    /// Replaces the message's components.
+    pub fn components(
+        mut self,
+        components: impl IntoIterator<Item = Component>,
+    ) -> Self {
+        self.components = Some(components.into_iter().collect());
+        self
+    }
+}
+
+impl From<MessageUpdate> for EditWebhookMessage {
+    fn from(update: MessageUpdate) -> Self {
+        EditWebhookMessage {
+            content: update.content,
+            embeds: update.embeds,
+            components: update.components,
+            allowed_mentions: Some(AllowedMentions::none()),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{ComponentData, ComponentHandler, InteractionData};
+use anyhow::bail;
+use derive_more::{Display, Error};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+use wfbp_discord::models::Message;
+
+/// A structured `custom_id` formatted as `prefix:part:part...`. The prefix
+/// may itself contain `:`, but each part is escaped so it can contain any
+/// text.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CustomId {
+    pub prefix: String,
+    pub parts: Vec<String>,
+}
+
+impl CustomId {
+    /// The maximum length of an encoded `custom_id`.
+    pub const MAX_LEN: usize = 100;
+
+    pub fn new(prefix: impl Into<String>) -> Self {
+        CustomId {
+            prefix: prefix.into(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Adds a part to the end of the ID.
+    pub fn part(mut self, part: impl ToString) -> Self {
+        self.parts.push(part.to_string());
+        self
+    }
+
+    /// Encodes the ID into a `custom_id`.
+    pub fn encode(&self) -> Result<String, CustomIdError> {
+        let encoded =
+            self.parts
+                .iter()
+                .fold(self.prefix.clone(), |mut encoded, part| {
+                    encoded.push(':');
+                    encoded.push_str(&escape(part));
+                    encoded
+                });
+
+        if encoded.len() > Self::MAX_LEN {
+            return Err(CustomIdError::TooLong(encoded));
+        }
+
+        Ok(encoded)
+    }
+
+    /// Decodes a `custom_id` with a known prefix, or [`None`] if it has a
+    /// different prefix.
+    pub fn decode(custom_id: &str, prefix: &str) -> Option<Self> {
+        let rest = custom_id.strip_prefix(prefix)?;
+        let parts = match rest {
+            "" => Vec::new(),
+            rest => rest.strip_prefix(':')?.split(':').map(unescape).collect(),
+        };
+
+        Some(CustomId {
+            prefix: prefix.to_owned(),
+            parts,
+        })
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug, Display, Error)]
+pub enum CustomIdError {
+    #[display(
+        fmt = "custom ID '{}' is longer than {} characters",
+        _0,
+        "CustomId::MAX_LEN"
+    )]
+    TooLong(#[error(ignore)] String),
+}
+
+fn escape(part: &str) -> Cow<'_, str> {
+    if part.contains(['%', ':']) {
+        part.replace('%', "%25").replace(':', "%3A").into()
+    } else {
+        part.into()
+    }
+}
+
+fn unescape(part: &str) -> String {
+    part.replace("%3A", ":").replace("%25", "%")
+}
+
+/// Dispatches component interactions to handlers by the prefix of their
+/// `custom_id`. When several prefixes match, the longest one is used.
+#[derive(Debug, Default)]
+pub struct ComponentRouter {
+    handlers: HashMap<Cow<'static, str>, ComponentHandler>,
+}
+
+impl ComponentRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a handler, replacing any existing handler with the same prefix.
+    pub fn add(&mut self, handler: ComponentHandler) {
+        self.handlers.insert(handler.prefix.clone(), handler);
+    }
+
+    /// Finds the handler for a `custom_id` and decodes the ID.
+    pub fn route(
+        &self,
+        custom_id: &str,
+    ) -> Option<(&ComponentHandler, CustomId)> {
+        // Parts never contain an unescaped ':', so the prefix must end at one
+        // (or at the end of the ID). Check the longest prefixes first.
+        let ends = std::iter::once(custom_id.len())
+            .chain(custom_id.rmatch_indices(':').map(|(index, _)| index));
+
+        ends.map(|end| &custom_id[..end]).find_map(|prefix| {
+            let handler = self.handlers.get(prefix)?;
+            let id = CustomId::decode(custom_id, prefix)?;
+            Some((handler, id))
+        })
+    }
+
+    /// Routes a component interaction to its handler.
+    pub async fn handle(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        custom_id: &str,
+        message: Message,
+        values: Vec<String>,
+    ) -> anyhow::Result<()> {
+        let (handler, custom_id) = match self.route(custom_id) {
+            Some(route) => route,
+            None => bail!("component not found: '{}'", custom_id),
+        };
+
+        handler
+            .handle(
+                interaction_data,
+                ComponentData {
+                    custom_id,
+                    message,
+                    values,
+                },
+            )
+            .await
+    }
+}
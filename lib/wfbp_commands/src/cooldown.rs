@@ -0,0 +1,193 @@
+use crate::{
+    CommandCallback, CommandOptionRegistry, HandleInteractionError,
+    InteractionData, SlashCommandData,
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use wfbp_discord::{
+    models::{CreateWebhookMessage, Snowflake},
+    routes::{CreateFollowupMessage, DeleteOriginalInteractionResponse},
+    DiscordRestClient,
+};
+
+/// Tracks when each user last used each command.
+#[derive(Clone, Debug)]
+pub struct CooldownTracker {
+    window: Duration,
+    state: Arc<Mutex<CooldownState>>,
+}
+
+#[derive(Debug)]
+struct CooldownState {
+    last_used: HashMap<(Snowflake, String), Instant>,
+    last_sweep: Instant,
+}
+
+impl CooldownTracker {
+    /// Creates a tracker which only lets each user use a command once per
+    /// `window`.
+    pub fn new(window: Duration) -> Self {
+        CooldownTracker {
+            window,
+            state: Arc::new(Mutex::new(CooldownState {
+                last_used: HashMap::new(),
+                last_sweep: Instant::now(),
+            })),
+        }
+    }
+
+    /// Records a use of a command. If the user is still on cooldown, the use
+    /// isn't recorded and the remaining cooldown is returned instead.
+    pub fn try_use(
+        &self,
+        user_id: Snowflake,
+        command: &str,
+    ) -> Result<(), Duration> {
+        self.try_use_at(user_id, command, Instant::now())
+    }
+
+    /// Same as [`CooldownTracker::try_use`], but at a specific time.
+    pub fn try_use_at(
+        &self,
+        user_id: Snowflake,
+        command: &str,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let window = self.window;
+        let mut state = self.state.lock().unwrap();
+
+        // Remove expired cooldowns so the map doesn't grow forever
+        if now.saturating_duration_since(state.last_sweep) >= window {
+            state.last_used.retain(|_, &mut last_used| {
+                now.saturating_duration_since(last_used) < window
+            });
+            state.last_sweep = now;
+        }
+
+        let key = (user_id, command.to_owned());
+        if let Some(&last_used) = state.last_used.get(&key) {
+            let elapsed = now.saturating_duration_since(last_used);
+            if elapsed < window {
+                return Err(window - elapsed);
+            }
+        }
+
+        state.last_used.insert(key, now);
+        Ok(())
+    }
+
+    /// The number of users currently on cooldown for any command.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().last_used.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Builds the message telling a user how long they need to wait before using
+/// a command again.
+pub type CooldownNotice =
+    Box<dyn Fn(&InteractionData, Duration) -> String + Send + Sync>;
+
+/// Wraps a callback so users on cooldown are told how long to wait instead
+/// of it being invoked.
+pub struct CooldownCallback<C> {
+    callback: C,
+    tracker: CooldownTracker,
+    discord_client: DiscordRestClient,
+    app_id: Snowflake,
+    notice: CooldownNotice,
+}
+
+impl<C> CooldownCallback<C> {
+    pub fn new(
+        callback: C,
+        tracker: CooldownTracker,
+        discord_client: DiscordRestClient,
+        app_id: Snowflake,
+    ) -> Self {
+        CooldownCallback {
+            callback,
+            tracker,
+            discord_client,
+            app_id,
+            notice: Box::new(|_, remaining| {
+                let seconds = remaining.as_secs_f32().ceil();
+                format!(
+                    "You're using this command too quickly, try again in \
+                    {seconds} seconds."
+                )
+            }),
+        }
+    }
+
+    /// Sets the message shown to users on cooldown, for example to translate
+    /// it into the user's language.
+    pub fn notice<F>(mut self, notice: F) -> Self
+    where
+        F: Fn(&InteractionData, Duration) -> String + Send + Sync + 'static,
+    {
+        self.notice = Box::new(notice);
+        self
+    }
+}
+
+#[async_trait]
+impl<C> CommandCallback for CooldownCallback<C>
+where
+    C: CommandCallback,
+{
+    async fn invoke<'a>(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        invoke_data: &'a SlashCommandData,
+        options: CommandOptionRegistry<'a>,
+    ) -> Result<(), HandleInteractionError> {
+        let cooldown = match interaction_data.user_id() {
+            Some(user_id) => self.tracker.try_use(user_id, &invoke_data.name),
+            None => Ok(()),
+        };
+
+        match cooldown {
+            Ok(()) => {
+                self.callback
+                    .invoke(interaction_data, invoke_data, options)
+                    .await
+            }
+            Err(remaining) => {
+                // The interaction was already deferred publicly, and the first
+                // followup would replace that loading message. Delete it so
+                // the notice is sent as a separate, ephemeral message instead.
+                DeleteOriginalInteractionResponse::execute(
+                    &self.discord_client,
+                    self.app_id,
+                    interaction_data.token.clone(),
+                )
+                .await
+                .context("error deleting deferred response")?;
+
+                let message = CreateWebhookMessage::builder()
+                    .content((self.notice)(&interaction_data, remaining))
+                    .ephemeral()
+                    .build();
+                CreateFollowupMessage::execute(
+                    &self.discord_client,
+                    self.app_id,
+                    interaction_data.token.clone(),
+                    message,
+                )
+                .await
+                .context("error creating response")?;
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,5 +1,14 @@
 use anyhow::Context;
 use derive_more::{Display, Error};
+use std::{
+    fmt::Display,
+    num::{
+        NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU16, NonZeroU32,
+        NonZeroU64, NonZeroU8,
+    },
+    ops::Deref,
+    str::FromStr,
+};
 use wfbp_discord::models::{
     ApplicationCommandInteractionDataOption,
     ApplicationCommandInteractionDataOptionType, Snowflake,
@@ -8,8 +17,24 @@ use wfbp_discord::models::{
 #[non_exhaustive]
 #[derive(Debug, Display, Error)]
 pub enum FromOptionError {
-    #[display(fmt = "the option is the wrong type")]
-    InvalidType,
+    #[display(
+        fmt = "expected {} option, got {}",
+        "expected.join(\" or \")",
+        actual
+    )]
+    InvalidType {
+        expected: &'static [&'static str],
+        actual: &'static str,
+    },
+    #[display(
+        fmt = "expected a value between {} and {}, got {}",
+        min,
+        max,
+        value
+    )]
+    OutOfRange { value: i64, min: i64, max: i64 },
+    #[display(fmt = "expected a non-zero value")]
+    Zero,
     #[display(fmt = "error parsing input: {}", _0)]
     ParseError(#[error(ignore)] String),
     #[display(fmt = "{}", _0)]
@@ -57,7 +82,10 @@ macro_rules! from_option {
                 option: &'a ApplicationCommandInteractionDataOption,
             ) -> Result<Self, FromOptionError> {
                 $(from_option!(@branch option.kind, $variant $(=> |$value| $result)?);)*
-                Err(FromOptionError::InvalidType)
+                Err(FromOptionError::InvalidType {
+                    expected: &[$(stringify!($variant)),*],
+                    actual: option.kind.name(),
+                })
             }
         }
     };
@@ -121,6 +149,73 @@ from_option!(u128 = Integer => |&value| {
 
 from_option!(f32 = Number => |&value| Ok(value as f32));
 
+macro_rules! from_option_non_zero {
+    ($($target:ty = $inner:ty),* $(,)?) => {
+        $(
+            impl<'a> FromOption<'a> for $target {
+                fn from_option(
+                    option: &'a ApplicationCommandInteractionDataOption,
+                ) -> Result<Self, FromOptionError> {
+                    let value: $inner = FromOption::from_option(option)?;
+                    <$target>::new(value).ok_or(FromOptionError::Zero)
+                }
+            }
+        )*
+    };
+}
+
+from_option_non_zero!(
+    NonZeroI8 = i8,
+    NonZeroI16 = i16,
+    NonZeroI32 = i32,
+    NonZeroI64 = i64,
+    NonZeroU8 = u8,
+    NonZeroU16 = u16,
+    NonZeroU32 = u32,
+    NonZeroU64 = u64,
+);
+
+/// An integer option which must be between `MIN` and `MAX` (inclusive).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct BoundedI64<const MIN: i64, const MAX: i64>(i64);
+
+impl<const MIN: i64, const MAX: i64> BoundedI64<MIN, MAX> {
+    /// Creates a bounded integer, or returns [`None`] if the value is out of
+    /// range.
+    pub fn new(value: i64) -> Option<Self> {
+        if (MIN..=MAX).contains(&value) {
+            Some(BoundedI64(value))
+        } else {
+            None
+        }
+    }
+
+    pub fn get(self) -> i64 {
+        self.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> From<BoundedI64<MIN, MAX>> for i64 {
+    fn from(value: BoundedI64<MIN, MAX>) -> Self {
+        value.0
+    }
+}
+
+impl<'a, const MIN: i64, const MAX: i64> FromOption<'a>
+    for BoundedI64<MIN, MAX>
+{
+    fn from_option(
+        option: &'a ApplicationCommandInteractionDataOption,
+    ) -> Result<Self, FromOptionError> {
+        let value: i64 = FromOption::from_option(option)?;
+        BoundedI64::new(value).ok_or(FromOptionError::OutOfRange {
+            value,
+            min: MIN,
+            max: MAX,
+        })
+    }
+}
+
 impl<'a> FromOption<'a> for &'a str {
     fn from_option(
         option: &'a ApplicationCommandInteractionDataOption,
@@ -129,7 +224,52 @@ impl<'a> FromOption<'a> for &'a str {
             ApplicationCommandInteractionDataOptionType::String {
                 ref value,
             } => Ok(value),
-            _ => Err(FromOptionError::InvalidType),
+            ref kind => Err(FromOptionError::InvalidType {
+                expected: &["String"],
+                actual: kind.name(),
+            }),
         }
     }
 }
+
+/// A string option containing a comma-separated list of values. Whitespace
+/// around each value is trimmed and empty values are skipped.
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub struct CommaSeparated<T>(pub Vec<T>);
+
+impl<T> CommaSeparated<T> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for CommaSeparated<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T> FromOption<'a> for CommaSeparated<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn from_option(
+        option: &'a ApplicationCommandInteractionDataOption,
+    ) -> Result<Self, FromOptionError> {
+        let value: &str = FromOption::from_option(option)?;
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value.parse().map_err(|error: T::Err| {
+                    FromOptionError::ParseError(error.to_string())
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(CommaSeparated)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{CommandOption, CommandOptionType, SlashCommand};
+use std::fmt::Write;
+use wfbp_discord::models::{Embed, EmbedField};
+
+impl SlashCommand {
+    /// Detailed help for the command, listing its subcommands, options and
+    /// examples.
+    pub fn help_embed(&self) -> Embed {
+        let mut fields = Vec::new();
+        let path = format!("/{}", self.name);
+        let options = option_lines(&self.options);
+        if !options.is_empty() {
+            fields.push(EmbedField {
+                name: "Options".into(),
+                value: options,
+                inline: None,
+            });
+        }
+        subcommand_fields(&path, &self.options, &mut fields);
+        if !self.examples.is_empty() {
+            let examples = self
+                .examples
+                .iter()
+                .map(|example| format!("`{}`", example))
+                .collect::<Vec<_>>()
+                .join("\n");
+            fields.push(EmbedField {
+                name: "Examples".into(),
+                value: examples,
+                inline: None,
+            });
+        }
+        fields.truncate(Embed::MAX_FIELDS);
+
+        Embed {
+            title: Some(path),
+            description: Some(
+                self.long_help
+                    .as_deref()
+                    .unwrap_or(&self.description)
+                    .to_owned(),
+            ),
+            fields: if fields.is_empty() {
+                None
+            } else {
+                Some(fields)
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Adds a field for each subcommand, including subcommands in groups.
+fn subcommand_fields(
+    path: &str,
+    options: &[CommandOption],
+    fields: &mut Vec<EmbedField>,
+) {
+    for option in options {
+        let path = format!("{} {}", path, option.name);
+        match &option.kind {
+            CommandOptionType::SubCommandGroup { options } => {
+                subcommand_fields(&path, options, fields)
+            }
+            CommandOptionType::SubCommand { options, .. } => {
+                let mut value = option.description.to_string();
+                let options = option_lines(options);
+                if !options.is_empty() {
+                    value.push('\n');
+                    value.push_str(&options);
+                }
+                fields.push(EmbedField {
+                    name: path,
+                    value,
+                    inline: None,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Describes each option which isn't a subcommand or group, one per line.
+fn option_lines(options: &[CommandOption]) -> String {
+    let mut lines = String::new();
+    for option in options {
+        let required = match option.kind {
+            CommandOptionType::SubCommand { .. }
+            | CommandOptionType::SubCommandGroup { .. } => continue,
+            CommandOptionType::String { required, .. }
+            | CommandOptionType::Integer { required, .. }
+            | CommandOptionType::Number { required, .. }
+            | CommandOptionType::Boolean { required }
+            | CommandOptionType::User { required }
+            | CommandOptionType::Channel { required }
+            | CommandOptionType::Role { required }
+            | CommandOptionType::Mentionable { required } => {
+                required.unwrap_or(false)
+            }
+        };
+
+        if !lines.is_empty() {
+            lines.push('\n');
+        }
+        let suffix = if required { " (required)" } else { "" };
+        write!(lines, "`{}`{}: {}", option.name, suffix, option.description)
+            .unwrap();
+    }
+
+    lines
+}
@@ -1,9 +1,29 @@
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+mod auto_defer;
 mod builders;
 mod command;
 mod command_registry;
+mod component;
+mod component_router;
+mod cooldown;
 mod from_option;
+mod help;
+mod permissions;
+mod registration_lock;
+mod responder;
+mod user_hasher;
 
+pub use auto_defer::*;
 pub use builders::*;
 pub use command::*;
 pub use command_registry::*;
+pub use component::*;
+pub use component_router::*;
+pub use cooldown::*;
 pub use from_option::*;
+pub use permissions::*;
+pub use registration_lock::*;
+pub use responder::*;
+pub use user_hasher::*;
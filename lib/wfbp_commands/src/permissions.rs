@@ -0,0 +1,97 @@
+use crate::InteractionData;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::RwLock;
+use wfbp_discord::{
+    models::{Permissions, Role, Snowflake, User},
+    routes::{GetChannel, GetGuildRoles},
+    DiscordRestClient,
+};
+use wfbp_http::RequestError;
+
+/// Caches the roles in each guild so member permissions can be checked
+/// without requesting them for every command.
+#[derive(Clone, Debug)]
+pub struct GuildRoleCache {
+    client: DiscordRestClient,
+    ttl: Duration,
+    roles: Arc<RwLock<HashMap<Snowflake, CachedRoles>>>,
+}
+
+type CachedRoles = (Instant, Arc<[Role]>);
+
+impl GuildRoleCache {
+    pub fn new(client: DiscordRestClient, ttl: Duration) -> Self {
+        GuildRoleCache {
+            client,
+            ttl,
+            roles: Default::default(),
+        }
+    }
+
+    /// Gets the roles in a guild, requesting them if they aren't cached.
+    pub async fn roles(
+        &self,
+        guild_id: Snowflake,
+    ) -> Result<Arc<[Role]>, RequestError> {
+        if let Some((expires, roles)) = self.roles.read().await.get(&guild_id) {
+            if Instant::now() < *expires {
+                return Ok(roles.clone());
+            }
+        }
+
+        let roles: Arc<[Role]> =
+            GetGuildRoles::execute(&self.client, guild_id).await?.into();
+        self.roles
+            .write()
+            .await
+            .insert(guild_id, (Instant::now() + self.ttl, roles.clone()));
+        Ok(roles)
+    }
+
+    /// Checks whether the member who invoked an interaction has a permission
+    /// in the channel it was invoked in.
+    ///
+    /// If the interaction includes the member's permissions, those are used
+    /// since Discord already applied the channel's overwrites to them.
+    /// Otherwise, the permissions are computed from the guild's roles and the
+    /// channel's overwrites. Interactions outside of guilds never have any
+    /// permissions.
+    pub async fn member_has_permission(
+        &self,
+        interaction: &InteractionData,
+        permission: Permissions,
+    ) -> Result<bool, RequestError> {
+        let (guild_id, member) =
+            match (interaction.guild_id, interaction.member.as_ref()) {
+                (Some(guild_id), Some(member)) => (guild_id, member),
+                _ => return Ok(false),
+            };
+
+        // Prefer the permissions computed by Discord
+        if let Some(permissions) = member.permissions() {
+            return Ok(permissions.contains(permission));
+        }
+
+        // Compute the permissions from roles and overwrites
+        let user_id = match member.user().map(User::id) {
+            Some(user_id) => user_id,
+            None => return Ok(false),
+        };
+        let roles = self.roles(guild_id).await?;
+        let channel =
+            GetChannel::execute(&self.client, interaction.channel_id).await?;
+        let permissions = Permissions::compute(
+            guild_id,
+            user_id,
+            member.roles(),
+            &roles,
+            channel.permission_overwrites.as_deref().unwrap_or_default(),
+        );
+
+        Ok(permissions.contains(permission))
+    }
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+/// Makes sure only one instance registers commands at a time. When several
+/// instances start together, each of them would otherwise overwrite the
+/// global commands, and users could briefly see commands disappear.
+///
+/// A distributed implementation can be built on Redis: `try_acquire` runs
+/// `SET <key> <instance id> NX PX <ttl>` and returns whether the key was
+/// set, and `release` deletes the key only if it still holds the instance's
+/// ID. The TTL keeps a crashed instance from holding the lock forever.
+#[async_trait]
+pub trait RegistrationLock: Send + Sync + 'static {
+    /// Tries to acquire the lock without waiting. Returns `false` if another
+    /// instance holds it.
+    async fn try_acquire(&self) -> anyhow::Result<bool>;
+
+    /// Releases the lock after registering commands.
+    async fn release(&self) -> anyhow::Result<()>;
+}
+
+/// A lock which is always acquired, for when only one instance is running.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopRegistrationLock;
+
+#[async_trait]
+impl RegistrationLock for NoopRegistrationLock {
+    async fn try_acquire(&self) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
+    async fn release(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    CommandCallback, CommandOptionRegistry, HandleInteractionError,
+    InteractionData, SlashCommandData,
+};
+use async_trait::async_trait;
+use derive_more::{Display, Error};
+use std::sync::{Arc, Mutex};
+use wfbp_discord::{
+    models::{
+        CreateWebhookMessage, CreateWebhookMessageBuilder, EditWebhookMessage,
+        InteractionApplicationCommandCallbackData, InteractionResponse,
+        Message,
+    },
+    routes::{
+        CreateFollowupMessage, CreateInteractionResponse,
+        EditOriginalInteractionResponse,
+    },
+    DiscordRestClient,
+};
+use wfbp_http::RequestError;
+
+/// How far along an interaction's response is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResponseState {
+    /// Nothing has been sent in response to the interaction yet.
+    Pending,
+    /// The response was deferred, and the user sees a loading state.
+    Deferred,
+    /// A response message was sent.
+    Sent,
+}
+
+/// Responds to an interaction, keeping track of what has already been sent
+/// so the right endpoint is used for each message. Clones share the same
+/// state.
+#[derive(Clone, Debug)]
+pub struct InteractionResponder {
+    discord_client: DiscordRestClient,
+    interaction_data: Arc<InteractionData>,
+    state: Arc<Mutex<ResponseState>>,
+}
+
+impl InteractionResponder {
+    /// Creates a responder for an interaction which hasn't been responded to.
+    pub fn new(
+        discord_client: DiscordRestClient,
+        interaction_data: Arc<InteractionData>,
+    ) -> Self {
+        Self::with_state(
+            discord_client,
+            interaction_data,
+            ResponseState::Pending,
+        )
+    }
+
+    /// Creates a responder for an interaction which was already deferred,
+    /// for example by the dispatcher.
+    pub fn deferred(
+        discord_client: DiscordRestClient,
+        interaction_data: Arc<InteractionData>,
+    ) -> Self {
+        Self::with_state(
+            discord_client,
+            interaction_data,
+            ResponseState::Deferred,
+        )
+    }
+
+    fn with_state(
+        discord_client: DiscordRestClient,
+        interaction_data: Arc<InteractionData>,
+        state: ResponseState,
+    ) -> Self {
+        InteractionResponder {
+            discord_client,
+            interaction_data,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub fn interaction_data(&self) -> &Arc<InteractionData> {
+        &self.interaction_data
+    }
+
+    pub fn state(&self) -> ResponseState {
+        *self.state.lock().unwrap()
+    }
+
+    fn set_state(&self, state: ResponseState) {
+        *self.state.lock().unwrap() = state;
+    }
+
+    /// Defers the response. Does nothing if the interaction was already
+    /// deferred or responded to.
+    pub async fn defer(&self) -> Result<(), ResponderError> {
+        if self.state() != ResponseState::Pending {
+            return Ok(());
+        }
+
+        CreateInteractionResponse::execute(
+            &self.discord_client,
+            self.interaction_data.id,
+            self.interaction_data.token.clone(),
+            InteractionResponse::DeferredChannelMessageWithSource {
+                data: InteractionApplicationCommandCallbackData {
+                    ..Default::default()
+                },
+            },
+        )
+        .await?;
+        self.set_state(ResponseState::Deferred);
+        Ok(())
+    }
+
+    /// Sends a message as the initial response to the interaction. If the
+    /// response was deferred, the loading message is replaced instead.
+    pub async fn respond(
+        &self,
+        message: CreateWebhookMessageBuilder,
+    ) -> Result<(), ResponderError> {
+        match self.state() {
+            ResponseState::Pending => {
+                CreateInteractionResponse::execute(
+                    &self.discord_client,
+                    self.interaction_data.id,
+                    self.interaction_data.token.clone(),
+                    InteractionResponse::channel_message(
+                        message.build_callback_data(),
+                    ),
+                )
+                .await?;
+            }
+            ResponseState::Deferred => {
+                let message = message.build();
+                self.edit(EditWebhookMessage {
+                    content: message.content,
+                    embeds: message.embeds,
+                    allowed_mentions: message.allowed_mentions,
+                    components: message.components,
+                    ..Default::default()
+                })
+                .await?;
+            }
+            ResponseState::Sent => {
+                return Err(ResponderError::AlreadyResponded)
+            }
+        }
+
+        self.set_state(ResponseState::Sent);
+        Ok(())
+    }
+
+    /// Edits the original response. If the response was deferred, this
+    /// replaces the loading message.
+    pub async fn edit(
+        &self,
+        message: EditWebhookMessage,
+    ) -> Result<Message, ResponderError> {
+        if self.state() == ResponseState::Pending {
+            return Err(ResponderError::NotResponded);
+        }
+
+        let message = EditOriginalInteractionResponse::execute(
+            &self.discord_client,
+            self.interaction_data.application_id,
+            self.interaction_data.token.clone(),
+            message,
+        )
+        .await?;
+        self.set_state(ResponseState::Sent);
+        Ok(message)
+    }
+
+    /// Sends a followup message. If the response was deferred, the first
+    /// followup replaces the loading message.
+    pub async fn followup(
+        &self,
+        message: CreateWebhookMessage,
+    ) -> Result<Message, ResponderError> {
+        if self.state() == ResponseState::Pending {
+            return Err(ResponderError::NotResponded);
+        }
+
+        let message = CreateFollowupMessage::execute(
+            &self.discord_client,
+            self.interaction_data.application_id,
+            self.interaction_data.token.clone(),
+            message,
+        )
+        .await?;
+        self.set_state(ResponseState::Sent);
+        Ok(message)
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Display, Error)]
+pub enum ResponderError {
+    #[display(fmt = "the interaction was already responded to")]
+    AlreadyResponded,
+    #[display(fmt = "the interaction hasn't been responded to yet")]
+    NotResponded,
+    #[display(fmt = "{}", _0)]
+    Request(RequestError),
+}
+
+impl From<RequestError> for ResponderError {
+    fn from(error: RequestError) -> Self {
+        ResponderError::Request(error)
+    }
+}
+
+impl From<ResponderError> for HandleInteractionError {
+    fn from(error: ResponderError) -> Self {
+        match error {
+            ResponderError::Request(error) => error.into(),
+            error => HandleInteractionError::Custom(error.into()),
+        }
+    }
+}
+
+/// A command callback which is given an [`InteractionResponder`] to respond
+/// to the interaction with.
+#[async_trait]
+pub trait ResponderCallback: Send + Sync + 'static {
+    async fn invoke<'a>(
+        &self,
+        responder: InteractionResponder,
+        invoke_data: &'a SlashCommandData,
+        options: CommandOptionRegistry<'a>,
+    ) -> Result<(), HandleInteractionError>;
+}
+
+/// Wraps a [`ResponderCallback`] so it can be used as a command callback.
+pub struct WithResponder<C> {
+    callback: C,
+    discord_client: DiscordRestClient,
+    deferred: bool,
+}
+
+impl<C> WithResponder<C> {
+    pub fn new(callback: C, discord_client: DiscordRestClient) -> Self {
+        WithResponder {
+            callback,
+            discord_client,
+            deferred: false,
+        }
+    }
+
+    /// Marks interactions as already deferred when they reach the callback.
+    pub fn already_deferred(mut self) -> Self {
+        self.deferred = true;
+        self
+    }
+}
+
+#[async_trait]
+impl<C> CommandCallback for WithResponder<C>
+where
+    C: ResponderCallback,
+{
+    async fn invoke<'a>(
+        &self,
+        interaction_data: Arc<InteractionData>,
+        invoke_data: &'a SlashCommandData,
+        options: CommandOptionRegistry<'a>,
+    ) -> Result<(), HandleInteractionError> {
+        let discord_client = self.discord_client.clone();
+        let responder = if self.deferred {
+            InteractionResponder::deferred(discord_client, interaction_data)
+        } else {
+            InteractionResponder::new(discord_client, interaction_data)
+        };
+
+        self.callback.invoke(responder, invoke_data, options).await
+    }
+}
@@ -0,0 +1,35 @@
+//! Utilities for testing commands and how they respond to interactions.
+
+use crate::InteractionData;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wfbp_discord::models::{Snowflake, Timestamp};
+
+/// An interaction from application 2 with the token `token`, sent in channel
+/// 3 outside of a guild. Use struct update syntax to change any of it.
+pub fn interaction_data() -> InteractionData {
+    InteractionData {
+        id: Snowflake::new(1),
+        application_id: Snowflake::new(2),
+        token: "token".into(),
+        guild_id: None,
+        channel_id: Snowflake::new(3),
+        member: None,
+        user: None,
+        locale: None,
+        guild_locale: None,
+        received_at: Timestamp::now(),
+    }
+}
+
+/// Same as [`interaction_data`], but for an interaction created `age` ago.
+pub fn interaction_created_ago(age: Duration) -> InteractionData {
+    let created_at = SystemTime::now() - age;
+    let millis = created_at.duration_since(UNIX_EPOCH).unwrap().as_millis()
+        as u64
+        - Snowflake::DISCORD_EPOCH;
+
+    InteractionData {
+        id: Snowflake::new(millis << 22),
+        ..interaction_data()
+    }
+}
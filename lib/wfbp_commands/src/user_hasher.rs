@@ -0,0 +1,48 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{
+    fmt::{Debug, Formatter, Write},
+    sync::Arc,
+};
+use wfbp_discord::models::Snowflake;
+
+/// Hashes user IDs with HMAC-SHA256 so a user's commands can be correlated
+/// in logs without recording their ID. The key should be a secret unique to
+/// the deployment, since anyone with it can check whether a hash belongs to
+/// a particular user.
+#[derive(Clone)]
+pub struct UserHasher {
+    key: Arc<[u8]>,
+}
+
+impl UserHasher {
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        UserHasher {
+            key: key.as_ref().into(),
+        }
+    }
+
+    /// Hashes a user ID. The same ID always has the same hash for a given
+    /// key.
+    pub fn hash(&self, user_id: Snowflake) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(&user_id.to_u64().to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // Half of the digest is plenty to tell users apart in logs
+        let mut hash = String::with_capacity(32);
+        for byte in &digest[..16] {
+            write!(hash, "{byte:02x}").unwrap();
+        }
+        hash
+    }
+}
+
+impl Debug for UserHasher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserHasher")
+            .field("key", &"<secret>")
+            .finish()
+    }
+}
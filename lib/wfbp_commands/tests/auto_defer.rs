@@ -0,0 +1,189 @@
+use reqwest::Method;
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+use wfbp_commands::{
+    test_util::interaction_created_ago, HandleInteractionError,
+    InteractionResponder, ResponseBudget, ResponseKind,
+};
+use wfbp_discord::{
+    models::{CreateWebhookMessage, CreateWebhookMessageBuilder, Snowflake},
+    test_util::{self, message_json},
+    DiscordRestClient,
+};
+use wfbp_http::test_util::MockRestServer;
+use wiremock::{
+    matchers::{method, path, path_regex},
+    Mock, ResponseTemplate,
+};
+
+const BUDGET: Duration = Duration::from_millis(100);
+
+async fn discord_server() -> (MockRestServer, DiscordRestClient) {
+    let (server, client) = test_util::discord_server(Snowflake::new(2)).await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path_regex(r"^/interactions/\d+/token/callback$"))
+                .respond_with(ResponseTemplate::new(204)),
+        )
+        .await;
+    server
+        .mock(
+            Mock::given(method("PATCH"))
+                .and(path("/webhooks/2/token/messages/@original"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(message_json("3", "Done")),
+                ),
+        )
+        .await;
+
+    (server, client)
+}
+
+async fn handler(
+    delay: Duration,
+) -> Result<CreateWebhookMessageBuilder, HandleInteractionError> {
+    tokio::time::sleep(delay).await;
+    Ok(CreateWebhookMessage::builder().content("Done"))
+}
+
+/// The requests sent to Discord, other than the token request.
+async fn sent_requests(server: &MockRestServer) -> Vec<(String, String)> {
+    server
+        .received_requests()
+        .await
+        .into_iter()
+        .filter(|req| req.path() != "/oauth2/token")
+        .map(|req| (req.method(), req.path().to_owned()))
+        .collect()
+}
+
+#[tokio::test]
+async fn fast_handler_sends_initial_response() {
+    let (server, client) = discord_server().await;
+    let interaction_data = Arc::new(interaction_created_ago(Duration::ZERO));
+    let responder = InteractionResponder::new(client, interaction_data.clone());
+    let budget = ResponseBudget::with_budget(BUDGET);
+
+    let kind = budget
+        .respond(&responder, handler(Duration::ZERO))
+        .await
+        .unwrap();
+    assert_eq!(kind, ResponseKind::Initial);
+
+    let callback =
+        format!("/interactions/{}/token/callback", interaction_data.id);
+    server
+        .single_request(Method::POST, &callback)
+        .await
+        .assert_json_body(json!({
+            "type": 4,
+            "data": { "content": "Done" },
+        }));
+    assert_eq!(sent_requests(&server).await.len(), 1);
+}
+
+#[tokio::test]
+async fn slow_handler_is_deferred_then_edited() {
+    let (server, client) = discord_server().await;
+    let interaction_data = Arc::new(interaction_created_ago(Duration::ZERO));
+    let responder = InteractionResponder::new(client, interaction_data.clone());
+    let budget = ResponseBudget::with_budget(BUDGET);
+
+    let kind = budget
+        .respond(&responder, handler(BUDGET * 3))
+        .await
+        .unwrap();
+    assert_eq!(kind, ResponseKind::Deferred);
+
+    let callback =
+        format!("/interactions/{}/token/callback", interaction_data.id);
+    server
+        .single_request(Method::POST, &callback)
+        .await
+        .assert_json_body(json!({
+            "type": 5,
+            "data": {},
+        }));
+    server
+        .single_request(Method::PATCH, "/webhooks/2/token/messages/@original")
+        .await
+        .assert_json_body(json!({ "content": "Done" }));
+
+    // The deferral is sent before the edit
+    assert_eq!(
+        sent_requests(&server).await,
+        [
+            ("POST".to_owned(), callback),
+            (
+                "PATCH".to_owned(),
+                "/webhooks/2/token/messages/@original".to_owned()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn budget_is_measured_from_interaction_creation() {
+    let (server, client) = discord_server().await;
+    let interaction_data = Arc::new(interaction_created_ago(BUDGET * 2));
+    let responder = InteractionResponder::new(client, interaction_data.clone());
+    let budget = ResponseBudget::with_budget(BUDGET);
+    assert_eq!(budget.remaining(&interaction_data), Duration::ZERO);
+
+    // Even a fast handler is too late for an initial response
+    let kind = budget
+        .respond(&responder, handler(Duration::ZERO))
+        .await
+        .unwrap();
+    assert_eq!(kind, ResponseKind::Deferred);
+    assert_eq!(sent_requests(&server).await.len(), 2);
+}
+
+#[tokio::test]
+async fn deferred_interactions_are_only_edited() {
+    let (server, client) = discord_server().await;
+    let responder = InteractionResponder::deferred(
+        client,
+        Arc::new(interaction_created_ago(Duration::ZERO)),
+    );
+    let budget = ResponseBudget::with_budget(BUDGET);
+
+    let kind = budget
+        .respond(&responder, handler(Duration::ZERO))
+        .await
+        .unwrap();
+    assert_eq!(kind, ResponseKind::Deferred);
+
+    // No callback is sent for an interaction the dispatcher already
+    // acknowledged
+    assert_eq!(
+        sent_requests(&server).await,
+        [(
+            "PATCH".to_owned(),
+            "/webhooks/2/token/messages/@original".to_owned()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn handler_errors_are_returned() {
+    let (server, client) = discord_server().await;
+    let responder = InteractionResponder::new(
+        client,
+        Arc::new(interaction_created_ago(Duration::ZERO)),
+    );
+    let budget = ResponseBudget::with_budget(BUDGET);
+
+    let result = budget
+        .respond(&responder, async {
+            Err(HandleInteractionError::MissingOptions)
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(HandleInteractionError::MissingOptions)
+    ));
+    assert!(sent_requests(&server).await.is_empty());
+}
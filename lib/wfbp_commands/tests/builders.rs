@@ -0,0 +1,151 @@
+use serde_json::json;
+use wfbp_commands::{CommandBuilder, InvalidCommandError, SlashCommand};
+use wfbp_discord::models::{
+    ApplicationIntegrationType, CreateApplicationCommand,
+    InteractionContextType,
+};
+
+#[test]
+fn two_level_command_is_valid() {
+    let command = CommandBuilder::new()
+        .name("admin")
+        .description("Admin commands")
+        .subcommand_group_option(|builder| {
+            builder
+                .name("commands")
+                .description("Command management")
+                .subcommand_option(|builder| {
+                    builder
+                        .name("reset")
+                        .description("Resets the commands")
+                        .string_option(|builder| {
+                            builder.name("scope").description("The scope")
+                        })
+                })
+        })
+        .try_build();
+    assert!(command.is_ok());
+}
+
+#[test]
+fn three_level_command_is_rejected() {
+    let inner_group = CommandBuilder::new()
+        .name("unused")
+        .description("unused")
+        .subcommand_group_option(|builder| {
+            builder
+                .name("inner")
+                .description("Inner group")
+                .subcommand_option(|builder| {
+                    builder.name("reset").description("Resets the commands")
+                })
+        })
+        .build()
+        .options
+        .pop()
+        .unwrap();
+    let result = CommandBuilder::new()
+        .name("admin")
+        .description("Admin commands")
+        .subcommand_group_option(|builder| {
+            builder
+                .name("commands")
+                .description("Command management")
+                .option(inner_group)
+        })
+        .try_build();
+
+    match result {
+        Err(InvalidCommandError::NestedSubCommandGroup(path)) => {
+            assert_eq!(path, "admin commands inner")
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+fn command_json(command: &SlashCommand) -> serde_json::Value {
+    serde_json::to_value(CreateApplicationCommand::from(command)).unwrap()
+}
+
+#[test]
+fn string_shorthands_match_verbose_builders() {
+    let verbose = CommandBuilder::new()
+        .name("pc")
+        .description("Checks prices")
+        .string_option(|builder| {
+            builder
+                .name("query")
+                .description("A search query")
+                .required(false)
+        })
+        .subcommand_option(|builder| {
+            builder
+                .name("item")
+                .description("Searches for an item")
+                .string_option(|builder| {
+                    builder
+                        .name("name")
+                        .description("The name of the item")
+                        .required(true)
+                })
+        })
+        .build();
+    let shorthand = CommandBuilder::new()
+        .name("pc")
+        .description("Checks prices")
+        .optional_string("query", "A search query")
+        .subcommand_option(|builder| {
+            builder
+                .name("item")
+                .description("Searches for an item")
+                .required_string("name", "The name of the item")
+        })
+        .build();
+
+    assert_eq!(command_json(&shorthand), command_json(&verbose));
+}
+
+#[test]
+fn install_contexts_are_serialized_as_numbers() {
+    let command = CommandBuilder::new()
+        .name("pc")
+        .description("Checks prices")
+        .contexts([
+            InteractionContextType::GUILD,
+            InteractionContextType::BOT_DM,
+            InteractionContextType::PRIVATE_CHANNEL,
+        ])
+        .integration_types([
+            ApplicationIntegrationType::GUILD_INSTALL,
+            ApplicationIntegrationType::USER_INSTALL,
+        ])
+        .build();
+
+    assert_eq!(
+        command_json(&command),
+        json!({
+            "type": 1,
+            "name": "pc",
+            "description": "Checks prices",
+            "contexts": [0, 1, 2],
+            "integration_types": [0, 1],
+        })
+    );
+}
+
+#[test]
+fn install_contexts_are_omitted_when_unset() {
+    let command = CommandBuilder::new()
+        .name("pc")
+        .description("Checks prices")
+        .build();
+
+    assert_eq!(
+        command_json(&command),
+        json!({
+            "type": 1,
+            "name": "pc",
+            "description": "Checks prices",
+        })
+    );
+}
@@ -0,0 +1,270 @@
+use reqwest::Method;
+use serde_json::json;
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use wfbp_commands::{
+    CommandRegistry, ComponentData, ComponentHandler, ComponentResponse,
+    HandleInteractionError, InteractionData, MessageUpdate,
+};
+use wfbp_discord::{
+    models::{
+        ButtonStyle, Component, ComponentType, Interaction, InteractionType,
+        Snowflake,
+    },
+    test_util, DiscordRestClient,
+};
+use wfbp_http::test_util::MockRestServer;
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+async fn discord_server() -> (MockRestServer, DiscordRestClient) {
+    let (server, client) = test_util::discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("PATCH"))
+                .and(path("/webhooks/1/token/messages/@original"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(message_json()),
+                ),
+        )
+        .await;
+
+    (server, client)
+}
+
+/// A snowflake for an interaction created just now.
+fn id() -> Snowflake {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+        - Snowflake::DISCORD_EPOCH;
+    Snowflake::new(millis << 22)
+}
+
+fn page_button(page: u32) -> serde_json::Value {
+    json!({
+        "type": 2,
+        "style": 1,
+        "label": format!("Page {}", page),
+        "custom_id": format!("page:{}", page),
+    })
+}
+
+/// The message the components are attached to.
+fn message_json() -> serde_json::Value {
+    json!({
+        "id": "4",
+        "channel_id": "3",
+        "author": {
+            "id": "1",
+            "username": "bot",
+            "discriminator": "0001",
+            "avatar": null,
+        },
+        "content": "Page 1",
+        "timestamp": "2021-01-01T00:00:00+00:00",
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0,
+        "flags": 0,
+        "components": [{
+            "type": 1,
+            "components": [page_button(2)],
+        }],
+    })
+}
+
+fn component_interaction(id: Snowflake) -> Interaction {
+    interaction_with_component(
+        id,
+        json!({
+            "type": 2,
+            "custom_id": "page:2",
+        }),
+    )
+}
+
+fn interaction_with_component(
+    id: Snowflake,
+    component_type: serde_json::Value,
+) -> Interaction {
+    serde_json::from_value(json!({
+        "id": id,
+        "application_id": "1",
+        "type": 3,
+        "channel_id": "3",
+        "token": "token",
+        "version": 1,
+        "component_type": component_type,
+        "message": message_json(),
+    }))
+    .unwrap()
+}
+
+/// The dispatcher already acknowledged the interaction, so sending another
+/// initial response would fail.
+async fn assert_no_callback(server: &MockRestServer) {
+    assert!(server
+        .received_requests()
+        .await
+        .iter()
+        .all(|request| !request.path().ends_with("/callback")));
+}
+
+fn turn_page(
+    _: Arc<InteractionData>,
+    component_data: &ComponentData,
+) -> Result<ComponentResponse, HandleInteractionError> {
+    let page: u32 = component_data.custom_id.parts[0].parse().unwrap();
+    Ok(ComponentResponse::UpdateMessage(
+        MessageUpdate::new()
+            .content(format!("Page {}", page))
+            .components([Component::ActionRow {
+                components: vec![Component::Button {
+                    style: ButtonStyle::PRIMARY,
+                    label: Some(format!("Page {}", page + 1)),
+                    emoji: None,
+                    custom_id: Some(format!("page:{}", page + 1)),
+                    url: None,
+                    disabled: None,
+                }],
+            }]),
+    ))
+}
+
+#[tokio::test]
+async fn update_message_replaces_components() {
+    let id = id();
+    let (server, client) = discord_server().await;
+    let registry = CommandRegistry::new(vec![]);
+    registry
+        .add_component(ComponentHandler::new("page", client, turn_page))
+        .await;
+
+    registry
+        .handle_interaction(component_interaction(id))
+        .await
+        .unwrap();
+
+    server
+        .single_request(Method::PATCH, "/webhooks/1/token/messages/@original")
+        .await
+        .assert_json_body(json!({
+            "content": "Page 2",
+            "allowed_mentions": { "parse": [] },
+            "components": [{
+                "type": 1,
+                "components": [page_button(3)],
+            }],
+        }));
+    assert_no_callback(&server).await;
+}
+
+#[tokio::test]
+async fn unknown_components_are_rejected() {
+    let id = id();
+    let (server, client) = discord_server().await;
+    let registry = CommandRegistry::new(vec![]);
+    registry
+        .add_component(ComponentHandler::new("other", client, turn_page))
+        .await;
+
+    let result = registry.handle_interaction(component_interaction(id)).await;
+    assert!(result.is_err());
+    assert!(server
+        .received_requests()
+        .await
+        .iter()
+        .all(|request| !request.path().starts_with("/webhooks/")));
+    assert_no_callback(&server).await;
+}
+
+fn echo_selection(
+    _: Arc<InteractionData>,
+    component_data: &ComponentData,
+) -> Result<ComponentResponse, HandleInteractionError> {
+    Ok(ComponentResponse::UpdateMessage(
+        MessageUpdate::new().content(component_data.values.join(", ")),
+    ))
+}
+
+#[tokio::test]
+async fn select_menu_values_are_passed_to_handler() {
+    let id = id();
+    let (server, client) = discord_server().await;
+    let registry = CommandRegistry::new(vec![]);
+    registry
+        .add_component(ComponentHandler::new("sort", client, echo_selection))
+        .await;
+
+    let interaction = interaction_with_component(
+        id,
+        json!({
+            "type": 3,
+            "custom_id": "sort",
+            "values": ["price", "reputation"],
+        }),
+    );
+    match &interaction.kind {
+        InteractionType::MessageComponent {
+            component_type: ComponentType::SelectMenu { values, .. },
+            ..
+        } => {
+            assert_eq!(
+                values.as_deref(),
+                Some(&["price".to_owned(), "reputation".to_owned()][..])
+            );
+        }
+        other => panic!("unexpected interaction: {:?}", other),
+    }
+    registry.handle_interaction(interaction).await.unwrap();
+
+    server
+        .single_request(Method::PATCH, "/webhooks/1/token/messages/@original")
+        .await
+        .assert_json_body(json!({
+            "content": "price, reputation",
+            "allowed_mentions": { "parse": [] },
+        }));
+    assert_no_callback(&server).await;
+}
+
+fn keep_message(
+    _: Arc<InteractionData>,
+    _: &ComponentData,
+) -> Result<ComponentResponse, HandleInteractionError> {
+    Ok(ComponentResponse::DeferredUpdateMessage)
+}
+
+#[tokio::test]
+async fn deferred_components_send_nothing() {
+    let id = id();
+    let (server, client) = discord_server().await;
+    let registry = CommandRegistry::new(vec![]);
+    registry
+        .add_component(ComponentHandler::new("page", client, keep_message))
+        .await;
+
+    registry
+        .handle_interaction(component_interaction(id))
+        .await
+        .unwrap();
+
+    assert_no_callback(&server).await;
+    assert!(server
+        .received_requests()
+        .await
+        .iter()
+        .all(|request| !request.path().starts_with("/webhooks/")));
+}
@@ -0,0 +1,194 @@
+use serde_json::json;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use wfbp_commands::{
+    test_util::interaction_data, CommandBuilder, CommandOptionRegistry,
+    CooldownCallback, CooldownTracker, HandleInteractionError, InteractionData,
+    SlashCommandData,
+};
+use wfbp_discord::{
+    models::Snowflake,
+    test_util::{self, message_json},
+    DiscordRestClient,
+};
+use wfbp_http::test_util::MockRestServer;
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+const WINDOW: Duration = Duration::from_secs(10);
+
+#[test]
+fn denies_uses_within_window() {
+    let tracker = CooldownTracker::new(WINDOW);
+    let user = Snowflake::new(1);
+    let start = Instant::now();
+
+    assert_eq!(tracker.try_use_at(user, "pc", start), Ok(()));
+    assert_eq!(
+        tracker.try_use_at(user, "pc", start + Duration::from_secs(4)),
+        Err(Duration::from_secs(6)),
+    );
+    assert_eq!(tracker.try_use_at(user, "pc", start + WINDOW), Ok(()));
+}
+
+#[test]
+fn cooldowns_are_per_user_and_command() {
+    let tracker = CooldownTracker::new(WINDOW);
+    let start = Instant::now();
+
+    assert_eq!(tracker.try_use_at(Snowflake::new(1), "pc", start), Ok(()));
+    assert_eq!(tracker.try_use_at(Snowflake::new(2), "pc", start), Ok(()));
+    assert_eq!(
+        tracker.try_use_at(Snowflake::new(1), "admin", start),
+        Ok(())
+    );
+    assert!(tracker.try_use_at(Snowflake::new(1), "pc", start).is_err());
+}
+
+#[test]
+fn denied_uses_do_not_extend_cooldown() {
+    let tracker = CooldownTracker::new(WINDOW);
+    let user = Snowflake::new(1);
+    let start = Instant::now();
+
+    assert_eq!(tracker.try_use_at(user, "pc", start), Ok(()));
+    assert!(tracker
+        .try_use_at(user, "pc", start + Duration::from_secs(9))
+        .is_err());
+    assert_eq!(tracker.try_use_at(user, "pc", start + WINDOW), Ok(()));
+}
+
+#[test]
+fn expired_cooldowns_are_swept() {
+    let tracker = CooldownTracker::new(WINDOW);
+    let start = Instant::now();
+
+    for id in 0..10 {
+        tracker.try_use_at(Snowflake::new(id), "pc", start).unwrap();
+    }
+    assert_eq!(tracker.len(), 10);
+
+    tracker
+        .try_use_at(Snowflake::new(100), "pc", start + WINDOW * 2)
+        .unwrap();
+    assert_eq!(tracker.len(), 1);
+}
+
+async fn discord_server() -> (MockRestServer, DiscordRestClient) {
+    let (server, client) = test_util::discord_server(Snowflake::new(2)).await;
+    server
+        .mock(
+            Mock::given(method("DELETE"))
+                .and(path("/webhooks/2/token/messages/@original"))
+                .respond_with(ResponseTemplate::new(204)),
+        )
+        .await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/webhooks/2/token"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(message_json("3", "Slow down")),
+                ),
+        )
+        .await;
+
+    (server, client)
+}
+
+/// An interaction from a user, so their uses of commands are tracked.
+fn user_interaction() -> Arc<InteractionData> {
+    Arc::new(InteractionData {
+        user: Some(
+            serde_json::from_value(json!({
+                "id": "7",
+                "username": "user",
+                "discriminator": "0001",
+                "avatar": null,
+            }))
+            .unwrap(),
+        ),
+        ..interaction_data()
+    })
+}
+
+fn invocation() -> SlashCommandData {
+    SlashCommandData {
+        command_id: Snowflake::new(4),
+        name: "pc".into(),
+        resolved: Default::default(),
+        options: vec![],
+    }
+}
+
+#[tokio::test]
+async fn notice_replaces_deferred_response() {
+    let (server, client) = discord_server().await;
+    let invoked = Arc::new(AtomicUsize::new(0));
+    let callback = {
+        let invoked = invoked.clone();
+        move |_, _: &SlashCommandData, _: CommandOptionRegistry| {
+            invoked.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, HandleInteractionError>(())
+        }
+    };
+    let command = CommandBuilder::new()
+        .name("pc")
+        .description("Checks prices")
+        .callback(
+            CooldownCallback::new(
+                callback,
+                CooldownTracker::new(WINDOW),
+                client,
+                Snowflake::new(2),
+            )
+            .notice(|_, remaining| {
+                format!("Slow down for {}s", remaining.as_secs_f32().ceil())
+            }),
+        )
+        .build();
+
+    command
+        .handle(user_interaction(), invocation(), None)
+        .await
+        .unwrap();
+    command
+        .handle(user_interaction(), invocation(), None)
+        .await
+        .unwrap();
+    assert_eq!(invoked.load(Ordering::SeqCst), 1);
+
+    // The public loading message is removed so the notice can be ephemeral
+    let requests: Vec<_> = server
+        .received_requests()
+        .await
+        .into_iter()
+        .filter(|req| req.path() != "/oauth2/token")
+        .collect();
+    let sent: Vec<_> = requests
+        .iter()
+        .map(|req| (req.method(), req.path().to_owned()))
+        .collect();
+    assert_eq!(
+        sent,
+        [
+            (
+                "DELETE".to_owned(),
+                "/webhooks/2/token/messages/@original".to_owned()
+            ),
+            ("POST".to_owned(), "/webhooks/2/token".to_owned()),
+        ]
+    );
+
+    let body = requests[1].json_body();
+    assert_eq!(body["content"], "Slow down for 10s");
+    assert_eq!(body["flags"], 64);
+}
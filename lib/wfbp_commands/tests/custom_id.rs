@@ -0,0 +1,72 @@
+use reqwest::Client;
+use std::sync::Arc;
+use wfbp_commands::{
+    ComponentHandler, ComponentResponse, ComponentRouter, CustomId,
+    CustomIdError, MessageUpdate,
+};
+use wfbp_discord::{models::Snowflake, DiscordRestClient};
+
+fn handler(prefix: &'static str) -> ComponentHandler {
+    let client = DiscordRestClient::new_with_base_url(
+        Client::new(),
+        "http://localhost",
+        Snowflake::new(1),
+        Arc::new("secret".to_owned().into()),
+    );
+    ComponentHandler::new(prefix, client, |_, _: &_| {
+        Ok(ComponentResponse::UpdateMessage(MessageUpdate::new()))
+    })
+}
+
+#[test]
+fn custom_ids_round_trip() {
+    let id = CustomId::new("pc:page").part(2).part("sell");
+    let encoded = id.encode().unwrap();
+    assert_eq!(encoded, "pc:page:2:sell");
+    assert_eq!(CustomId::decode(&encoded, "pc:page"), Some(id));
+}
+
+#[test]
+fn parts_are_escaped() {
+    let id = CustomId::new("pc").part("a:b").part("100%").part("");
+    let encoded = id.encode().unwrap();
+    assert_eq!(encoded, "pc:a%3Ab:100%25:");
+    assert_eq!(CustomId::decode(&encoded, "pc"), Some(id));
+}
+
+#[test]
+fn ids_without_parts_round_trip() {
+    let id = CustomId::new("refresh");
+    let encoded = id.encode().unwrap();
+    assert_eq!(encoded, "refresh");
+    assert_eq!(CustomId::decode(&encoded, "refresh"), Some(id));
+}
+
+#[test]
+fn decoding_requires_matching_prefix() {
+    assert_eq!(CustomId::decode("pc:page:2", "pcx"), None);
+    assert_eq!(CustomId::decode("pcx:page", "pc"), None);
+}
+
+#[test]
+fn long_ids_are_rejected() {
+    let result = CustomId::new("pc").part("a".repeat(100)).encode();
+    assert!(matches!(result, Err(CustomIdError::TooLong(_))));
+}
+
+#[test]
+fn router_uses_longest_matching_prefix() {
+    let mut router = ComponentRouter::new();
+    router.add(handler("pc"));
+    router.add(handler("pc:page"));
+
+    let (handler, id) = router.route("pc:page:2:sell").unwrap();
+    assert_eq!(handler.prefix, "pc:page");
+    assert_eq!(id.parts, ["2", "sell"]);
+
+    let (handler, id) = router.route("pc:whisper:Seller%3A1").unwrap();
+    assert_eq!(handler.prefix, "pc");
+    assert_eq!(id.parts, ["whisper", "Seller:1"]);
+
+    assert!(router.route("pcx:page").is_none());
+}
@@ -0,0 +1,120 @@
+use std::sync::{Arc, Mutex};
+use wfbp_commands::{
+    test_util::interaction_data, CommandBuilder, CommandOptionRegistry,
+    HandleInteractionError, SlashCommand, SlashCommandData,
+};
+use wfbp_discord::models::{
+    ApplicationCommandInteractionDataOption,
+    ApplicationCommandInteractionDataOptionType, Snowflake,
+};
+
+fn admin_command(received: Arc<Mutex<Option<String>>>) -> SlashCommand {
+    CommandBuilder::new()
+        .name("admin")
+        .description("Admin commands")
+        .subcommand_group_option(|builder| {
+            builder
+                .name("commands")
+                .description("Command management")
+                .subcommand_option(|builder| {
+                    builder
+                        .name("reset")
+                        .description("Resets the commands")
+                        .string_option(|builder| {
+                            builder.name("scope").description("The scope")
+                        })
+                        .callback(
+                            move |_,
+                                  _: &SlashCommandData,
+                                  options: CommandOptionRegistry| {
+                                let scope: String =
+                                    options.get_option("scope")?;
+                                *received.lock().unwrap() = Some(scope);
+                                Ok::<_, HandleInteractionError>(())
+                            },
+                        )
+                })
+        })
+        .build()
+}
+
+fn invocation(
+    kind: ApplicationCommandInteractionDataOptionType,
+) -> SlashCommandData {
+    SlashCommandData {
+        command_id: Snowflake::new(4),
+        name: "admin".into(),
+        resolved: Default::default(),
+        options: vec![ApplicationCommandInteractionDataOption {
+            name: "commands".into(),
+            kind,
+        }],
+    }
+}
+
+#[tokio::test]
+async fn nested_subcommand_receives_its_options() {
+    let received = Arc::new(Mutex::new(None));
+    let command = admin_command(received.clone());
+    let data = invocation(
+        ApplicationCommandInteractionDataOptionType::SubCommandGroup {
+            options: Some(vec![ApplicationCommandInteractionDataOption {
+                name: "reset".into(),
+                kind: ApplicationCommandInteractionDataOptionType::SubCommand {
+                    options: Some(vec![
+                        ApplicationCommandInteractionDataOption {
+                            name: "scope".into(),
+                            kind: ApplicationCommandInteractionDataOptionType::String {
+                                value: "global".into(),
+                            },
+                        },
+                    ]),
+                },
+            }]),
+        },
+    );
+
+    command
+        .handle(Arc::new(interaction_data()), data, None)
+        .await
+        .unwrap();
+    assert_eq!(received.lock().unwrap().as_deref(), Some("global"));
+}
+
+#[tokio::test]
+async fn mismatched_option_kind_is_invalid_data() {
+    let command = admin_command(Arc::new(Mutex::new(None)));
+    let data =
+        invocation(ApplicationCommandInteractionDataOptionType::SubCommand {
+            options: None,
+        });
+
+    let result = command
+        .handle(Arc::new(interaction_data()), data, None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(HandleInteractionError::InvalidData(name)) if name == "commands"
+    ));
+}
+
+#[test]
+fn subcommand_path_includes_groups() {
+    let data = invocation(
+        ApplicationCommandInteractionDataOptionType::SubCommandGroup {
+            options: Some(vec![ApplicationCommandInteractionDataOption {
+                name: "reset".into(),
+                kind: ApplicationCommandInteractionDataOptionType::SubCommand {
+                    options: None,
+                },
+            }]),
+        },
+    );
+    assert_eq!(data.subcommand_path(), ["commands", "reset"]);
+
+    let data =
+        invocation(ApplicationCommandInteractionDataOptionType::String {
+            value: "global".into(),
+        });
+    assert!(data.subcommand_path().is_empty());
+}
@@ -0,0 +1,70 @@
+use anyhow::Context;
+use reqwest::StatusCode;
+use wfbp_commands::{GetOptionError, HandleInteractionError};
+use wfbp_http::RequestError;
+
+fn api_error(status: StatusCode) -> RequestError {
+    RequestError::ApiError {
+        status,
+        code: None,
+        message: "error".into(),
+    }
+}
+
+fn status(error: &HandleInteractionError) -> Option<StatusCode> {
+    match error.request_error()? {
+        RequestError::ApiError { status, .. } => Some(*status),
+        _ => None,
+    }
+}
+
+fn handle(
+    result: Result<(), RequestError>,
+) -> Result<(), HandleInteractionError> {
+    result?;
+    Ok(())
+}
+
+fn handle_with_context(
+    result: Result<(), RequestError>,
+) -> Result<(), HandleInteractionError> {
+    result.context("error getting item orders")?;
+    Ok(())
+}
+
+#[test]
+fn request_errors_keep_their_kind() {
+    for handle in [handle, handle_with_context] {
+        let error = handle(Err(api_error(StatusCode::NOT_FOUND))).unwrap_err();
+        assert_eq!(status(&error), Some(StatusCode::NOT_FOUND));
+
+        let error =
+            handle(Err(api_error(StatusCode::TOO_MANY_REQUESTS))).unwrap_err();
+        assert_eq!(status(&error), Some(StatusCode::TOO_MANY_REQUESTS));
+
+        let error =
+            handle(Err(RequestError::Custom(anyhow::anyhow!("unavailable"))))
+                .unwrap_err();
+        assert!(matches!(
+            error.request_error(),
+            Some(RequestError::Custom(_))
+        ));
+    }
+}
+
+#[test]
+fn request_errors_convert_directly() {
+    let error: HandleInteractionError = api_error(StatusCode::NOT_FOUND).into();
+    assert!(matches!(error, HandleInteractionError::Request(_)));
+    assert_eq!(error.to_string(), "API error (404 Not Found): error");
+}
+
+#[test]
+fn other_errors_have_no_request_error() {
+    let error: HandleInteractionError =
+        GetOptionError::MissingOption("name".into()).into();
+    assert!(error.request_error().is_none());
+
+    let error: HandleInteractionError = anyhow::anyhow!("oops").into();
+    assert!(error.request_error().is_none());
+}
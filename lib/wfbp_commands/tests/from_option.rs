@@ -0,0 +1,115 @@
+use std::num::NonZeroU32;
+use wfbp_commands::{BoundedI64, CommaSeparated, FromOption, FromOptionError};
+use wfbp_discord::models::{
+    ApplicationCommandInteractionDataOption,
+    ApplicationCommandInteractionDataOptionType, Snowflake,
+};
+
+fn string_option(value: &str) -> ApplicationCommandInteractionDataOption {
+    ApplicationCommandInteractionDataOption {
+        name: "items".into(),
+        kind: ApplicationCommandInteractionDataOptionType::String {
+            value: value.into(),
+        },
+    }
+}
+
+#[test]
+fn comma_separated_splits_and_trims_values() {
+    let option = string_option(" Soma Prime,Braton Prime , ,Ash Prime ");
+    let CommaSeparated(values): CommaSeparated<String> =
+        FromOption::from_option(&option).unwrap();
+    assert_eq!(values, ["Soma Prime", "Braton Prime", "Ash Prime"]);
+}
+
+#[test]
+fn comma_separated_parses_values() {
+    let option = string_option("1, 2, 3");
+    let CommaSeparated(values): CommaSeparated<u8> =
+        FromOption::from_option(&option).unwrap();
+    assert_eq!(values, [1, 2, 3]);
+
+    let option = string_option("1, two");
+    let result: Result<CommaSeparated<u8>, _> =
+        FromOption::from_option(&option);
+    assert!(matches!(result, Err(FromOptionError::ParseError(_))));
+}
+
+#[test]
+fn comma_separated_requires_string_option() {
+    let option = ApplicationCommandInteractionDataOption {
+        name: "items".into(),
+        kind: ApplicationCommandInteractionDataOptionType::Integer { value: 1 },
+    };
+    let result: Result<CommaSeparated<String>, _> =
+        FromOption::from_option(&option);
+    assert!(matches!(
+        result,
+        Err(FromOptionError::InvalidType {
+            expected: ["String"],
+            actual: "Integer",
+        })
+    ));
+}
+
+#[test]
+fn invalid_type_reports_expected_and_actual_types() {
+    let option = string_option("5");
+    let error = <i64 as FromOption>::from_option(&option).unwrap_err();
+    assert_eq!(error.to_string(), "expected Integer option, got String");
+
+    let error = <Snowflake as FromOption>::from_option(&option).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected User or Channel or Role or Mentionable option, got String"
+    );
+}
+
+fn integer_option(value: i64) -> ApplicationCommandInteractionDataOption {
+    ApplicationCommandInteractionDataOption {
+        name: "rank".into(),
+        kind: ApplicationCommandInteractionDataOptionType::Integer { value },
+    }
+}
+
+#[test]
+fn bounded_integer_accepts_values_in_range() {
+    for value in [0, 5, 10] {
+        let rank: BoundedI64<0, 10> =
+            FromOption::from_option(&integer_option(value)).unwrap();
+        assert_eq!(rank.get(), value);
+    }
+}
+
+#[test]
+fn bounded_integer_rejects_values_out_of_range() {
+    let result: Result<BoundedI64<0, 10>, _> =
+        FromOption::from_option(&integer_option(11));
+    let error = result.unwrap_err();
+    assert!(matches!(
+        error,
+        FromOptionError::OutOfRange {
+            value: 11,
+            min: 0,
+            max: 10,
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "expected a value between 0 and 10, got 11"
+    );
+
+    let result: Result<BoundedI64<0, 10>, _> =
+        FromOption::from_option(&integer_option(-1));
+    assert!(matches!(result, Err(FromOptionError::OutOfRange { .. })));
+}
+
+#[test]
+fn non_zero_integer_rejects_zero() {
+    let page: NonZeroU32 = FromOption::from_option(&integer_option(3)).unwrap();
+    assert_eq!(page.get(), 3);
+
+    let result: Result<NonZeroU32, _> =
+        FromOption::from_option(&integer_option(0));
+    assert!(matches!(result, Err(FromOptionError::Zero)));
+}
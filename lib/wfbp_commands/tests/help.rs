@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use wfbp_commands::{CommandBuilder, CommandRegistry};
+
+fn registry() -> Arc<CommandRegistry> {
+    CommandRegistry::new(vec![
+        CommandBuilder::new()
+            .name("pc")
+            .description("Price checks an item")
+            .long_help("Checks the price of an item on warframe.market.")
+            .example("/pc mod name: serration rank: 10")
+            .subcommand_option(|builder| {
+                builder
+                    .name("mod")
+                    .description("Price checks a mod")
+                    .required_string("name", "The name of the mod")
+                    .integer_option(|builder| {
+                        builder
+                            .name("rank")
+                            .description("The rank of the mod")
+                            .required(false)
+                    })
+            })
+            .build(),
+        CommandBuilder::new()
+            .name("help")
+            .description("Shows help")
+            .optional_string("command", "The command to show help for")
+            .build(),
+    ])
+}
+
+#[tokio::test]
+async fn help_lists_subcommands_options_and_examples() {
+    let embed = registry().help_embed("pc").await.expect("missing help");
+    assert_eq!(embed.title.as_deref(), Some("/pc"));
+    assert_eq!(
+        embed.description.as_deref(),
+        Some("Checks the price of an item on warframe.market.")
+    );
+
+    let fields = embed.fields.expect("missing fields");
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|field| (field.name.as_str(), field.value.as_str()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            (
+                "/pc mod",
+                "Price checks a mod\n\
+                `name` (required): The name of the mod\n\
+                `rank`: The rank of the mod"
+            ),
+            ("Examples", "`/pc mod name: serration rank: 10`"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn help_lists_top_level_options() {
+    let embed = registry().help_embed("help").await.expect("missing help");
+    assert_eq!(embed.description.as_deref(), Some("Shows help"));
+
+    let fields = embed.fields.expect("missing fields");
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].name, "Options");
+    assert_eq!(fields[0].value, "`command`: The command to show help for");
+}
+
+#[tokio::test]
+async fn help_overview_lists_commands() {
+    let registry = registry();
+    assert!(registry.help_embed("unknown").await.is_none());
+
+    let embed = registry.help_overview().await;
+    assert_eq!(
+        embed.description.as_deref(),
+        Some("`/help`: Shows help\n`/pc`: Price checks an item")
+    );
+}
@@ -0,0 +1,57 @@
+use serde_json::json;
+use std::time::Duration;
+use wfbp_commands::{test_util::interaction_created_ago, UserHasher};
+use wfbp_discord::models::{Snowflake, User};
+
+#[test]
+fn new_interaction_token_is_valid() {
+    let interaction = interaction_created_ago(Duration::from_secs(60));
+    assert!(!interaction.is_token_expired());
+
+    let remaining = interaction.time_remaining();
+    assert!(remaining <= Duration::from_secs(14 * 60));
+    assert!(remaining > Duration::from_secs(13 * 60));
+}
+
+#[test]
+fn old_interaction_token_is_expired() {
+    let interaction = interaction_created_ago(Duration::from_secs(20 * 60));
+    assert!(interaction.is_token_expired());
+    assert_eq!(interaction.time_remaining(), Duration::ZERO);
+    assert!(interaction.age() >= Duration::from_secs(20 * 60));
+}
+
+#[test]
+fn user_hash_hides_user_id() {
+    let hasher = UserHasher::new("key");
+    let mut interaction = interaction_created_ago(Duration::from_secs(60));
+    assert!(interaction.user_hash(&hasher).is_none());
+
+    let user: User = serde_json::from_value(json!({
+        "id": "123456789012345678",
+        "username": "trader",
+        "discriminator": "0001",
+        "avatar": null,
+    }))
+    .unwrap();
+    interaction.user = Some(user);
+
+    let hash = interaction.user_hash(&hasher).expect("missing user hash");
+    assert!(!hash.contains("123456789012345678"));
+    assert_eq!(interaction.user_hash(&hasher), Some(hash.clone()));
+
+    // Other deployments can't correlate users with this one
+    let other_hasher = UserHasher::new("other key");
+    assert_ne!(interaction.user_hash(&other_hasher), Some(hash));
+}
+
+#[test]
+fn user_hasher_uses_hmac_sha256() {
+    // HMAC-SHA256 of the big-endian ID, truncated to 16 bytes
+    let hasher = UserHasher::new("key");
+    assert_eq!(
+        hasher.hash(Snowflake::new(1)),
+        "786712674f398ad3e27ce407be55518f"
+    );
+    assert_eq!(format!("{hasher:?}"), r#"UserHasher { key: "<secret>" }"#);
+}
@@ -0,0 +1,88 @@
+use serde_json::json;
+use wfbp_commands::{CommandOptionRegistry, GetOptionError, MentionableTarget};
+use wfbp_discord::models::{
+    ApplicationCommandInteractionDataOption,
+    ApplicationCommandInteractionDataOptionType,
+    ApplicationCommandInteractionDataResolved, Snowflake,
+};
+
+fn mentionable(name: &str, id: u64) -> ApplicationCommandInteractionDataOption {
+    ApplicationCommandInteractionDataOption {
+        name: name.into(),
+        kind: ApplicationCommandInteractionDataOptionType::Mentionable {
+            value: Snowflake::new(id),
+        },
+    }
+}
+
+fn resolved() -> ApplicationCommandInteractionDataResolved {
+    serde_json::from_value(json!({
+        "users": {
+            "10": {
+                "id": "10",
+                "username": "trader",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+        },
+        "roles": {
+            "20": {
+                "id": "20",
+                "name": "Traders",
+                "color": 0,
+                "hoist": false,
+                "position": 1,
+                "permissions": "0",
+                "managed": false,
+                "mentionable": true,
+            },
+        },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn resolves_users_and_roles() {
+    let options = [mentionable("user", 10), mentionable("role", 20)];
+    let resolved = resolved();
+    let registry =
+        CommandOptionRegistry::new(&options).with_resolved(&resolved);
+
+    match registry.get_mentionable("user").unwrap() {
+        MentionableTarget::User(id, Some(user)) => {
+            assert_eq!(id, Snowflake::new(10));
+            assert_eq!(user.id(), id);
+        }
+        other => panic!("expected a user, got {:?}", other),
+    }
+    match registry.get_mentionable("role").unwrap() {
+        MentionableTarget::Role(id, Some(role)) => {
+            assert_eq!(id, Snowflake::new(20));
+            assert_eq!(role.name, "Traders");
+        }
+        other => panic!("expected a role, got {:?}", other),
+    }
+}
+
+#[test]
+fn unresolved_mentionables_are_errors() {
+    let options = [mentionable("target", 30)];
+    let resolved = resolved();
+
+    let registry =
+        CommandOptionRegistry::new(&options).with_resolved(&resolved);
+    assert!(matches!(
+        registry.get_mentionable("target"),
+        Err(GetOptionError::UnresolvedMentionable(name)) if name == "target"
+    ));
+
+    let registry = CommandOptionRegistry::new(&options);
+    assert!(matches!(
+        registry.get_mentionable("target"),
+        Err(GetOptionError::UnresolvedMentionable(_))
+    ));
+    assert!(matches!(
+        registry.get_mentionable("missing"),
+        Err(GetOptionError::MissingOption(_))
+    ));
+}
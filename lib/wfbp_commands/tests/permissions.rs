@@ -0,0 +1,127 @@
+use serde_json::json;
+use std::time::Duration;
+use wfbp_commands::{
+    test_util::interaction_data, GuildRoleCache, InteractionData,
+};
+use wfbp_discord::{
+    models::{Permissions, Snowflake},
+    test_util::discord_server,
+};
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+fn interaction(member: serde_json::Value) -> InteractionData {
+    InteractionData {
+        guild_id: Some(Snowflake::new(10)),
+        channel_id: Snowflake::new(20),
+        member: Some(serde_json::from_value(member).unwrap()),
+        ..interaction_data()
+    }
+}
+
+fn member(permissions: Option<&str>) -> serde_json::Value {
+    let mut member = json!({
+        "user": {
+            "id": "40",
+            "username": "user",
+            "discriminator": "0001",
+            "avatar": null,
+        },
+        "roles": ["30"],
+        "joined_at": "2021-01-01T00:00:00+00:00",
+        "deaf": false,
+        "mute": false,
+    });
+    if let Some(permissions) = permissions {
+        member["permissions"] = permissions.into();
+    }
+
+    member
+}
+
+fn role(id: &str, permissions: Permissions) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": id,
+        "color": 0,
+        "hoist": false,
+        "position": 0,
+        "permissions": permissions.bits().to_string(),
+        "managed": false,
+        "mentionable": false,
+    })
+}
+
+#[tokio::test]
+async fn uses_interaction_permissions_when_present() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    let cache = GuildRoleCache::new(client, Duration::from_secs(60));
+    let bits = Permissions::MANAGE_GUILD.bits().to_string();
+    let interaction = interaction(member(Some(&bits)));
+
+    let has_manage_guild = cache
+        .member_has_permission(&interaction, Permissions::MANAGE_GUILD)
+        .await
+        .unwrap();
+    let has_ban_members = cache
+        .member_has_permission(&interaction, Permissions::BAN_MEMBERS)
+        .await
+        .unwrap();
+    assert!(has_manage_guild);
+    assert!(!has_ban_members);
+    assert!(server.received_requests().await.is_empty());
+}
+
+#[tokio::test]
+async fn resolves_permissions_from_roles_and_overwrites() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/guilds/10/roles"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!(
+                    [
+                        role("10", Permissions::SEND_MESSAGES),
+                        role("30", Permissions::MANAGE_GUILD),
+                    ]
+                )))
+                .expect(1),
+        )
+        .await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/channels/20"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "20",
+                "type": 0,
+                "guild_id": "10",
+                "name": "general",
+                "permission_overwrites": [
+                    {
+                        "id": "10",
+                        "type": 0,
+                        "allow": "0",
+                        "deny": Permissions::SEND_MESSAGES.bits().to_string(),
+                    },
+                ],
+            }))),
+        )
+        .await;
+
+    let cache = GuildRoleCache::new(client, Duration::from_secs(60));
+    let interaction = interaction(member(None));
+
+    let has_manage_guild = cache
+        .member_has_permission(&interaction, Permissions::MANAGE_GUILD)
+        .await
+        .unwrap();
+    let has_send_messages = cache
+        .member_has_permission(&interaction, Permissions::SEND_MESSAGES)
+        .await
+        .unwrap();
+    assert!(has_manage_guild);
+    assert!(!has_send_messages);
+}
@@ -0,0 +1,398 @@
+use async_trait::async_trait;
+use reqwest::Method;
+use serde_json::json;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+use wfbp_commands::{
+    CommandBuilder, CommandDiff, CommandOptionRegistry, CommandRegistry,
+    HandleInteractionError, RegistrationLock, SlashCommand, SlashCommandData,
+};
+use wfbp_discord::{
+    models::{
+        ApplicationCommand, ApplicationIntegrationType, Interaction, Snowflake,
+    },
+    test_util, DiscordRestClient,
+};
+use wfbp_http::test_util::MockRestServer;
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+fn registry() -> Arc<CommandRegistry> {
+    CommandRegistry::new(vec![
+        CommandBuilder::new()
+            .name("pc")
+            .description("Price checks an item")
+            .string_option(|builder| {
+                builder.name("name").description("The item").required(true)
+            })
+            .build(),
+        CommandBuilder::new()
+            .name("help")
+            .description("Shows help")
+            .build(),
+    ])
+}
+
+fn registered(
+    name: &str,
+    description: &str,
+    options: serde_json::Value,
+) -> ApplicationCommand {
+    serde_json::from_value(json!({
+        "type": 1,
+        "id": "1",
+        "application_id": "2",
+        "name": name,
+        "description": description,
+        "options": options,
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn unchanged_commands_have_no_diff() {
+    let registered = [
+        registered(
+            "pc",
+            "Price checks an item",
+            json!([{
+                "type": 3,
+                "name": "name",
+                "description": "The item",
+                "required": true,
+            }]),
+        ),
+        registered("help", "Shows help", json!([])),
+    ];
+
+    let diff = registry().diff(&registered).await;
+    assert!(diff.is_empty(), "unexpected diff: {:?}", diff);
+}
+
+#[tokio::test]
+async fn default_integration_types_have_no_diff() {
+    let mut help = registered("help", "Shows help", json!([]));
+    let mut pc = registered(
+        "pc",
+        "Price checks an item",
+        json!([{
+            "type": 3,
+            "name": "name",
+            "description": "The item",
+            "required": true,
+        }]),
+    );
+    for command in [&mut help, &mut pc] {
+        if let ApplicationCommand::ChatInput {
+            integration_types, ..
+        } = command
+        {
+            *integration_types =
+                Some(vec![ApplicationIntegrationType::GUILD_INSTALL]);
+        }
+    }
+
+    let diff = registry().diff(&[pc, help]).await;
+    assert!(diff.is_empty(), "unexpected diff: {:?}", diff);
+}
+
+#[tokio::test]
+async fn diff_detects_added_changed_and_removed_commands() {
+    let registered = [
+        registered(
+            "pc",
+            "Price checks an item",
+            json!([{
+                "type": 3,
+                "name": "name",
+                "description": "The item",
+            }]),
+        ),
+        registered("old", "An old command", json!([])),
+    ];
+
+    let diff = registry().diff(&registered).await;
+    assert_eq!(
+        diff,
+        CommandDiff {
+            added: vec!["help".into()],
+            changed: vec!["pc".into()],
+            removed: vec!["old".into()],
+        }
+    );
+}
+
+async fn discord_server() -> (MockRestServer, DiscordRestClient) {
+    let (server, client) = test_util::discord_server(Snowflake::new(2)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/applications/2/commands"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(json!([])),
+                ),
+        )
+        .await;
+    server
+        .mock(
+            Mock::given(method("PUT"))
+                .and(path("/applications/2/commands"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(json!([])),
+                ),
+        )
+        .await;
+
+    (server, client)
+}
+
+/// A lock shared between simulated instances.
+#[derive(Clone, Default)]
+struct SharedLock {
+    held: Arc<AtomicBool>,
+    releases: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl RegistrationLock for SharedLock {
+    async fn try_acquire(&self) -> anyhow::Result<bool> {
+        Ok(!self.held.swap(true, Ordering::SeqCst))
+    }
+
+    async fn release(&self) -> anyhow::Result<()> {
+        self.held.store(false, Ordering::SeqCst);
+        self.releases.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+async fn command_requests(server: &MockRestServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .iter()
+        .filter(|req| req.path() == "/applications/2/commands")
+        .count()
+}
+
+#[tokio::test]
+async fn sync_is_skipped_while_lock_is_held() {
+    let (server, client) = discord_server().await;
+    let lock = SharedLock::default();
+    let registry = registry();
+    registry.set_registration_lock(lock.clone()).await;
+
+    // Another instance is registering commands
+    assert!(lock.try_acquire().await.unwrap());
+    let diff = registry
+        .sync_commands(&client, Snowflake::new(2), true)
+        .await
+        .unwrap();
+    assert_eq!(diff, None);
+    assert_eq!(command_requests(&server).await, 0);
+    assert_eq!(lock.releases.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn sync_releases_lock_after_registering() {
+    let (server, client) = discord_server().await;
+    let lock = SharedLock::default();
+    let registry = registry();
+    registry.set_registration_lock(lock.clone()).await;
+
+    let diff = registry
+        .sync_commands(&client, Snowflake::new(2), false)
+        .await
+        .unwrap()
+        .expect("lock should have been acquired");
+    assert_eq!(diff.added, ["help", "pc"]);
+    assert_eq!(command_requests(&server).await, 2);
+    assert!(!lock.held.load(Ordering::SeqCst));
+    assert_eq!(lock.releases.load(Ordering::SeqCst), 1);
+}
+
+fn counted_command(
+    name: &'static str,
+    calls: Arc<AtomicUsize>,
+) -> SlashCommand {
+    CommandBuilder::new()
+        .name(name)
+        .description("Counts its calls")
+        .callback(move |_, _: &SlashCommandData, _: CommandOptionRegistry| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, HandleInteractionError>(())
+        })
+        .build()
+}
+
+fn command_interaction(name: &str) -> Interaction {
+    serde_json::from_value(command_interaction_json(name)).unwrap()
+}
+
+fn guild_command_interaction(name: &str, guild_id: u64) -> Interaction {
+    let mut interaction = command_interaction_json(name);
+    interaction["guild_id"] = json!(guild_id.to_string());
+    serde_json::from_value(interaction).unwrap()
+}
+
+fn command_interaction_json(name: &str) -> serde_json::Value {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+        - Snowflake::DISCORD_EPOCH;
+    json!({
+        "id": Snowflake::new(millis << 22),
+        "application_id": "2",
+        "type": 2,
+        "channel_id": "3",
+        "token": "token",
+        "version": 1,
+        "data": {
+            "type": 1,
+            "id": "4",
+            "name": name,
+        },
+    })
+}
+
+#[tokio::test]
+async fn disabled_commands_are_not_executed() {
+    let (server, client) = discord_server().await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/webhooks/2/token"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({
+                        "id": "5",
+                        "channel_id": "3",
+                        "author": {
+                            "id": "2",
+                            "username": "bot",
+                            "discriminator": "0001",
+                            "avatar": null,
+                        },
+                        "content": "This command is temporarily disabled.",
+                        "timestamp": "2021-01-01T00:00:00+00:00",
+                        "edited_timestamp": null,
+                        "tts": false,
+                        "mention_everyone": false,
+                        "mentions": [],
+                        "mention_roles": [],
+                        "attachments": [],
+                        "embeds": [],
+                        "pinned": false,
+                        "type": 0,
+                        "flags": 0,
+                    }),
+                )),
+        )
+        .await;
+    let pc_calls = Arc::new(AtomicUsize::new(0));
+    let help_calls = Arc::new(AtomicUsize::new(0));
+    let registry = CommandRegistry::new(vec![
+        counted_command("pc", pc_calls.clone()),
+        counted_command("help", help_calls.clone()),
+    ]);
+    registry.set_discord_client(client).await;
+
+    assert!(registry.set_command_enabled("pc", false).await);
+    assert!(!registry.set_command_enabled("missing", false).await);
+    registry
+        .handle_interaction(command_interaction("pc"))
+        .await
+        .unwrap();
+    registry
+        .handle_interaction(command_interaction("help"))
+        .await
+        .unwrap();
+
+    assert_eq!(pc_calls.load(Ordering::SeqCst), 0);
+    assert_eq!(help_calls.load(Ordering::SeqCst), 1);
+    server
+        .single_request(Method::POST, "/webhooks/2/token")
+        .await
+        .assert_json_body(json!({
+            "content": "This command is temporarily disabled.",
+        }));
+
+    // Re-enabling a command runs it again
+    assert!(registry.set_command_enabled("pc", true).await);
+    registry
+        .handle_interaction(command_interaction("pc"))
+        .await
+        .unwrap();
+    assert_eq!(pc_calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn disallowed_guilds_are_not_handled() {
+    let (server, client) = discord_server().await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/webhooks/2/token"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({
+                        "id": "5",
+                        "channel_id": "3",
+                        "author": {
+                            "id": "2",
+                            "username": "bot",
+                            "discriminator": "0001",
+                            "avatar": null,
+                        },
+                        "content": "This bot isn't enabled in this server.",
+                        "timestamp": "2021-01-01T00:00:00+00:00",
+                        "edited_timestamp": null,
+                        "tts": false,
+                        "mention_everyone": false,
+                        "mentions": [],
+                        "mention_roles": [],
+                        "attachments": [],
+                        "embeds": [],
+                        "pinned": false,
+                        "type": 0,
+                        "flags": 0,
+                    }),
+                )),
+        )
+        .await;
+    let pc_calls = Arc::new(AtomicUsize::new(0));
+    let registry =
+        CommandRegistry::new(vec![counted_command("pc", pc_calls.clone())]);
+    registry.set_discord_client(client).await;
+
+    // Every guild is allowed by default
+    assert!(registry.is_guild_allowed(Some(Snowflake::new(20))).await);
+
+    registry.set_allowed_guilds([Snowflake::new(10)]).await;
+    assert!(registry.is_guild_allowed(Some(Snowflake::new(10))).await);
+    assert!(!registry.is_guild_allowed(Some(Snowflake::new(20))).await);
+    assert!(registry.is_guild_allowed(None).await);
+
+    registry
+        .handle_interaction(guild_command_interaction("pc", 10))
+        .await
+        .unwrap();
+    registry
+        .handle_interaction(guild_command_interaction("pc", 20))
+        .await
+        .unwrap();
+
+    assert_eq!(pc_calls.load(Ordering::SeqCst), 1);
+    server
+        .single_request(Method::POST, "/webhooks/2/token")
+        .await
+        .assert_json_body(json!({
+            "content": "This bot isn't enabled in this server.",
+        }));
+}
@@ -0,0 +1,174 @@
+use reqwest::Method;
+use serde_json::json;
+use std::sync::Arc;
+use wfbp_commands::{
+    test_util::interaction_data, InteractionResponder, ResponderError,
+    ResponseState,
+};
+use wfbp_discord::{
+    models::{CreateWebhookMessage, EditWebhookMessage, Snowflake},
+    test_util::{self, message_json},
+    DiscordRestClient,
+};
+use wfbp_http::test_util::MockRestServer;
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+async fn discord_server() -> (MockRestServer, DiscordRestClient) {
+    let (server, client) = test_util::discord_server(Snowflake::new(2)).await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/interactions/1/token/callback"))
+                .respond_with(ResponseTemplate::new(204)),
+        )
+        .await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/webhooks/2/token"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(message_json("3", "Done")),
+                ),
+        )
+        .await;
+    server
+        .mock(
+            Mock::given(method("PATCH"))
+                .and(path("/webhooks/2/token/messages/@original"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(message_json("3", "Done")),
+                ),
+        )
+        .await;
+
+    (server, client)
+}
+
+async fn request_paths(server: &MockRestServer) -> Vec<(String, String)> {
+    server
+        .received_requests()
+        .await
+        .into_iter()
+        .filter(|req| req.path() != "/oauth2/token")
+        .map(|req| (req.method().to_owned(), req.path().to_owned()))
+        .collect()
+}
+
+#[tokio::test]
+async fn deferred_response_can_be_edited() {
+    let (server, client) = discord_server().await;
+    let responder =
+        InteractionResponder::new(client, Arc::new(interaction_data()));
+    assert_eq!(responder.state(), ResponseState::Pending);
+
+    responder.defer().await.unwrap();
+    assert_eq!(responder.state(), ResponseState::Deferred);
+
+    let message = responder
+        .edit(EditWebhookMessage {
+            content: Some("Done".into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(message.id, Snowflake::new(5));
+    assert_eq!(responder.state(), ResponseState::Sent);
+
+    server
+        .single_request(Method::POST, "/interactions/1/token/callback")
+        .await
+        .assert_json_body(json!({
+            "type": 5,
+            "data": {},
+        }));
+    server
+        .single_request(Method::PATCH, "/webhooks/2/token/messages/@original")
+        .await
+        .assert_json_body(json!({ "content": "Done" }));
+    assert_eq!(
+        request_paths(&server).await,
+        [
+            (
+                "POST".to_owned(),
+                "/interactions/1/token/callback".to_owned()
+            ),
+            (
+                "PATCH".to_owned(),
+                "/webhooks/2/token/messages/@original".to_owned()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn direct_response_can_be_followed_up() {
+    let (server, client) = discord_server().await;
+    let responder =
+        InteractionResponder::new(client, Arc::new(interaction_data()));
+
+    responder
+        .respond(CreateWebhookMessage::builder().content("First"))
+        .await
+        .unwrap();
+    assert_eq!(responder.state(), ResponseState::Sent);
+    responder
+        .followup(CreateWebhookMessage::builder().content("Second").build())
+        .await
+        .unwrap();
+
+    server
+        .single_request(Method::POST, "/interactions/1/token/callback")
+        .await
+        .assert_json_body(json!({
+            "type": 4,
+            "data": { "content": "First" },
+        }));
+    server
+        .single_request(Method::POST, "/webhooks/2/token")
+        .await
+        .assert_json_body(json!({ "content": "Second" }));
+
+    // Responding again would be rejected by Discord
+    let result = responder
+        .respond(CreateWebhookMessage::builder().content("Third"))
+        .await;
+    assert!(matches!(result, Err(ResponderError::AlreadyResponded)));
+}
+
+#[tokio::test]
+async fn followup_requires_a_response() {
+    let (server, client) = discord_server().await;
+    let responder =
+        InteractionResponder::new(client, Arc::new(interaction_data()));
+
+    let result = responder
+        .followup(CreateWebhookMessage::builder().content("Done").build())
+        .await;
+    assert!(matches!(result, Err(ResponderError::NotResponded)));
+    assert!(request_paths(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn already_deferred_response_is_edited() {
+    let (server, client) = discord_server().await;
+    let responder =
+        InteractionResponder::deferred(client, Arc::new(interaction_data()));
+
+    responder
+        .respond(CreateWebhookMessage::builder().content("Done"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        request_paths(&server).await,
+        [(
+            "PATCH".to_owned(),
+            "/webhooks/2/token/messages/@original".to_owned()
+        )]
+    );
+}
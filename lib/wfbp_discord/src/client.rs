@@ -1,24 +1,35 @@
 use crate::{
-    middleware::{AuthenticationLayer, ClientSecret, RateLimitLayer},
+    middleware::{
+        AuthenticationLayer, ClientSecret, MetricsLayer, OAuthScope,
+        RateLimitLayer,
+    },
     models::Snowflake,
     routes::DiscordRouteInfo,
+    Metrics, NoopMetrics, RateLimitBucket, RemainingInfo,
 };
 use async_trait::async_trait;
-use reqwest::{Client, RequestBuilder, Response};
-use std::{fmt::Debug, sync::Arc};
-use tower::ServiceBuilder;
+use reqwest::Client;
+use std::{borrow::Cow, fmt::Debug, sync::Arc};
 use wfbp_http::{
-    middleware::{
-        BackoffLayer, ExecuteRequestService, JitterLayer, LimitLayer,
-        RestRequestBuilder, RetryLayer, RouteLayer,
-        TransientRequestRetryPolicy,
-    },
-    RequestError, RestClient, RestRequestLayer, Route, StandardRestClient,
+    RequestError, ReqwestTransport, RestClient, Route, StandardRestClient,
+    Transport,
 };
 
+/// Configuration for a [`DiscordRestClient`].
+#[derive(Clone, Debug, Default)]
+pub struct DiscordClientConfig {
+    /// The OAuth2 scopes to request when authenticating the client.
+    pub scope: OAuthScope,
+    /// The bot's token, which is needed for routes like
+    /// [`GetGatewayBot`](crate::routes::GetGatewayBot) that don't accept an
+    /// OAuth2 access token.
+    pub bot_token: Option<Arc<ClientSecret>>,
+}
+
 #[derive(Clone, Debug)]
 pub struct DiscordRestClient {
     inner: StandardRestClient,
+    rate_limits: RateLimitLayer,
 }
 
 impl DiscordRestClient {
@@ -29,24 +40,108 @@ impl DiscordRestClient {
         client_id: Snowflake,
         client_secret: Arc<ClientSecret>,
     ) -> Self {
+        Self::new_with_base_url(
+            client,
+            Self::BASE_URL,
+            client_id,
+            client_secret,
+        )
+    }
+
+    /// Creates a client which sends its requests to a different base URL.
+    pub fn new_with_base_url(
+        client: Client,
+        base_url: impl Into<Cow<'static, str>>,
+        client_id: Snowflake,
+        client_secret: Arc<ClientSecret>,
+    ) -> Self {
+        Self::new_with_config(
+            client,
+            base_url,
+            client_id,
+            client_secret,
+            DiscordClientConfig::default(),
+        )
+    }
+
+    pub fn new_with_config(
+        client: Client,
+        base_url: impl Into<Cow<'static, str>>,
+        client_id: Snowflake,
+        client_secret: Arc<ClientSecret>,
+        config: DiscordClientConfig,
+    ) -> Self {
+        Self::new_with_metrics(
+            client,
+            base_url,
+            client_id,
+            client_secret,
+            config,
+            Arc::new(NoopMetrics),
+        )
+    }
+
+    /// Creates a client which reports its requests to a [`Metrics`]
+    /// implementation.
+    pub fn new_with_metrics(
+        client: Client,
+        base_url: impl Into<Cow<'static, str>>,
+        client_id: Snowflake,
+        client_secret: Arc<ClientSecret>,
+        config: DiscordClientConfig,
+        metrics: Arc<dyn Metrics>,
+    ) -> Self {
+        Self::new_with_transport(
+            client,
+            base_url,
+            client_id,
+            client_secret,
+            config,
+            metrics,
+            Arc::new(ReqwestTransport),
+        )
+    }
+
+    /// Creates a client which sends its requests using a different
+    /// [`Transport`].
+    pub fn new_with_transport(
+        client: Client,
+        base_url: impl Into<Cow<'static, str>>,
+        client_id: Snowflake,
+        client_secret: Arc<ClientSecret>,
+        config: DiscordClientConfig,
+        metrics: Arc<dyn Metrics>,
+        transport: Arc<dyn Transport>,
+    ) -> Self {
+        let base_url = base_url.into();
         let auth_client =
-            StandardRestClient::new(client.clone(), Self::BASE_URL);
-
-        let request_layer = ServiceBuilder::new()
-            .layer(RetryLayer::new(TransientRequestRetryPolicy::default()))
-            .layer(LimitLayer::new(10))
-            .layer(AuthenticationLayer::new(auth_client, client_id, client_secret))
-            .layer(BackoffLayer::default())
-            .layer(RateLimitLayer::default())
-            .layer(JitterLayer::default())
-            .map_request(RequestBuilder::from)
-            .map_err(RequestError::from)
-            .check_service::<ExecuteRequestService, RestRequestBuilder, Response, RequestError>();
-        let inner = StandardRestClient::new_from_layers(
-            RouteLayer::new(client, Self::BASE_URL.into()),
-            RestRequestLayer::new(request_layer),
+            StandardRestClient::builder(client.clone(), base_url.clone())
+                .transport(transport.clone())
+                .build();
+        let mut auth = AuthenticationLayer::new(
+            auth_client,
+            client_id,
+            client_secret,
+            config.scope,
         );
-        DiscordRestClient { inner }
+        if let Some(bot_token) = config.bot_token {
+            auth = auth.with_bot_token(bot_token);
+        }
+
+        let rate_limits = RateLimitLayer::new(metrics.clone());
+        let inner = StandardRestClient::builder(client, base_url)
+            .transport(transport)
+            .layer(auth)
+            .transport_layer(rate_limits.clone())
+            .transport_layer(MetricsLayer::new(metrics))
+            .build();
+        DiscordRestClient { inner, rate_limits }
+    }
+
+    /// Gets the state of each rate limit bucket the client has made requests
+    /// to. This doesn't wait for in-flight requests.
+    pub fn rate_limit_snapshot(&self) -> Vec<(RateLimitBucket, RemainingInfo)> {
+        self.rate_limits.snapshot()
     }
 }
 
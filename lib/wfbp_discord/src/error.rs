@@ -0,0 +1,20 @@
+use crate::models::DiscordErrorCode;
+use wfbp_http::RequestError;
+
+/// Extension methods for reading Discord-specific details from a
+/// [`RequestError`].
+pub trait DiscordRequestErrorExt {
+    /// The Discord JSON error code, if the API returned one.
+    fn code(&self) -> Option<DiscordErrorCode>;
+}
+
+impl DiscordRequestErrorExt for RequestError {
+    fn code(&self) -> Option<DiscordErrorCode> {
+        match self {
+            RequestError::ApiError { code, .. } => {
+                code.map(DiscordErrorCode::from)
+            }
+            _ => None,
+        }
+    }
+}
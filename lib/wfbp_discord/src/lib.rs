@@ -1,9 +1,15 @@
 pub mod middleware;
 pub mod models;
 pub mod routes;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 mod client;
+mod error;
+mod metrics;
 mod rate_limit;
 
 pub use client::*;
+pub use error::*;
+pub use metrics::*;
 pub use rate_limit::*;
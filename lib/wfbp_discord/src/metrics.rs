@@ -0,0 +1,127 @@
+use crate::RateLimitBucket;
+use reqwest::StatusCode;
+use std::{fmt::Debug, time::Duration};
+
+/// Receives metrics about the requests made by a
+/// [`DiscordRestClient`](crate::DiscordRestClient).
+pub trait Metrics: Debug + Send + Sync + 'static {
+    /// Called when a request is about to be sent.
+    fn on_request_start(&self, _bucket: &RateLimitBucket) {}
+
+    /// Called when a request completes. `status` is [`None`] if no response
+    /// was received.
+    fn on_request_end(
+        &self,
+        _bucket: &RateLimitBucket,
+        _status: Option<StatusCode>,
+        _duration: Duration,
+    ) {
+    }
+
+    /// Called after a request was delayed due to a rate limit.
+    fn on_rate_limit_wait(
+        &self,
+        _bucket: &RateLimitBucket,
+        _duration: Duration,
+    ) {
+    }
+}
+
+/// Ignores all metrics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {}
+
+#[cfg(feature = "metrics")]
+pub use self::prometheus_metrics::*;
+
+#[cfg(feature = "metrics")]
+mod prometheus_metrics {
+    use super::Metrics;
+    use crate::RateLimitBucket;
+    use prometheus::{
+        HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry,
+    };
+    use reqwest::StatusCode;
+    use std::time::Duration;
+
+    /// Records metrics to a Prometheus registry.
+    #[derive(Clone, Debug)]
+    pub struct PrometheusMetrics {
+        requests: IntCounterVec,
+        request_duration: HistogramVec,
+        rate_limit_wait: HistogramVec,
+    }
+
+    impl PrometheusMetrics {
+        /// Creates the metrics and registers them to a registry.
+        pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+            let requests = IntCounterVec::new(
+                Opts::new(
+                    "discord_requests_total",
+                    "Number of requests sent to Discord.",
+                ),
+                &["method", "route", "status"],
+            )?;
+            let request_duration = HistogramVec::new(
+                HistogramOpts::new(
+                    "discord_request_duration_seconds",
+                    "Time taken for Discord to respond to a request.",
+                ),
+                &["method", "route"],
+            )?;
+            let rate_limit_wait = HistogramVec::new(
+                HistogramOpts::new(
+                    "discord_rate_limit_wait_seconds",
+                    "Time spent waiting for rate limits to reset.",
+                ),
+                &["method", "route"],
+            )?;
+
+            registry.register(Box::new(requests.clone()))?;
+            registry.register(Box::new(request_duration.clone()))?;
+            registry.register(Box::new(rate_limit_wait.clone()))?;
+
+            Ok(PrometheusMetrics {
+                requests,
+                request_duration,
+                rate_limit_wait,
+            })
+        }
+    }
+
+    impl Metrics for PrometheusMetrics {
+        fn on_request_end(
+            &self,
+            bucket: &RateLimitBucket,
+            status: Option<StatusCode>,
+            duration: Duration,
+        ) {
+            let status = status.map_or_else(
+                || "error".to_owned(),
+                |status| status.as_u16().to_string(),
+            );
+            self.requests
+                .with_label_values(&[
+                    bucket.method.as_str(),
+                    bucket.route,
+                    &status,
+                ])
+                .inc();
+            self.request_duration
+                .with_label_values(&[bucket.method.as_str(), bucket.route])
+                .observe(duration.as_secs_f64());
+        }
+
+        fn on_rate_limit_wait(
+            &self,
+            bucket: &RateLimitBucket,
+            duration: Duration,
+        ) {
+            self.rate_limit_wait
+                .with_label_values(&[bucket.method.as_str(), bucket.route])
+                .observe(duration.as_secs_f64());
+        }
+    }
+}
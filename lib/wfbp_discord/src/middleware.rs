@@ -1,5 +1,7 @@
 mod auth;
+mod metrics;
 mod rate_limit;
 
 pub use auth::*;
+pub use metrics::*;
 pub use rate_limit::*;
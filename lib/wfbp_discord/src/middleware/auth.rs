@@ -1,15 +1,17 @@
 use crate::{
     models::{ClientCredentialsRequest, Snowflake},
-    routes::{AuthenticateClientCredentialsGrant, DiscordRouteInfo},
+    routes::{AuthenticateClientCredentialsGrant, DiscordRouteInfo, RouteAuth},
 };
 use derive_more::{Display, Error};
 use futures::{future::BoxFuture, ready, FutureExt};
-use http::StatusCode;
+use http::{header::AUTHORIZATION, HeaderValue, StatusCode};
 use reqwest::Response;
 use serde::{Deserialize, Serialize};
 use std::{
+    convert::TryFrom,
     fmt::{Debug, Formatter},
     ops::Deref,
+    str::FromStr,
     sync::Arc,
     task::{Context, Poll},
 };
@@ -24,6 +26,8 @@ pub struct AuthenticationLayer<C> {
     auth_client: C,
     client_id: Snowflake,
     client_secret: Arc<ClientSecret>,
+    scope: OAuthScope,
+    bot_token: Option<Arc<ClientSecret>>,
     access_token: Arc<RwLock<Option<ClientSecret>>>,
 }
 
@@ -32,14 +36,24 @@ impl<C> AuthenticationLayer<C> {
         auth_client: C,
         client_id: Snowflake,
         client_secret: Arc<ClientSecret>,
+        scope: OAuthScope,
     ) -> Self {
         AuthenticationLayer {
             auth_client,
             client_id,
             client_secret,
+            scope,
+            bot_token: None,
             access_token: Arc::new(RwLock::new(None)),
         }
     }
+
+    /// Sets the bot token used for routes which don't accept an OAuth2
+    /// access token.
+    pub fn with_bot_token(mut self, bot_token: Arc<ClientSecret>) -> Self {
+        self.bot_token = Some(bot_token);
+        self
+    }
 }
 
 impl<C, Next> Layer<Next> for AuthenticationLayer<C>
@@ -53,6 +67,8 @@ where
             auth_client: self.auth_client.clone(),
             client_id: self.client_id,
             client_secret: self.client_secret.clone(),
+            scope: self.scope.clone(),
+            bot_token: self.bot_token.clone(),
             access_token: self.access_token.clone(),
             next: Arc::new(Mutex::new(next)),
         }
@@ -64,6 +80,8 @@ pub struct AuthenticationService<C, Next> {
     auth_client: C,
     client_id: Snowflake,
     client_secret: Arc<ClientSecret>,
+    scope: OAuthScope,
+    bot_token: Option<Arc<ClientSecret>>,
     access_token: Arc<RwLock<Option<ClientSecret>>>,
     next: Arc<Mutex<Next>>,
 }
@@ -103,19 +121,53 @@ where
             }
         };
 
-        // Check if auth is needed
-        if !info.needs_auth {
-            let next = self.next.clone();
-            return Box::pin(async move {
-                let mut next = next.lock().await;
-                next.call(req).await
-            });
+        // Check which auth is needed
+        match info.auth {
+            RouteAuth::None => {
+                let next = self.next.clone();
+                return Box::pin(async move {
+                    let mut next = next.lock().await;
+                    next.call(req).await
+                });
+            }
+            RouteAuth::Bot => {
+                let bot_token = match self.bot_token.as_deref() {
+                    Some(bot_token) => bot_token,
+                    None => {
+                        return Box::pin(async move {
+                            Err(AuthenticationError::MissingBotToken)?
+                        })
+                    }
+                };
+                let mut header = match HeaderValue::try_from(format!(
+                    "Bot {}",
+                    &**bot_token
+                )) {
+                    Ok(header) => header,
+                    Err(_) => {
+                        return Box::pin(async move {
+                            Err(AuthenticationError::InvalidBotToken)?
+                        })
+                    }
+                };
+                header.set_sensitive(true);
+                let req = req.with_modified_request(|req| {
+                    req.header(AUTHORIZATION, header)
+                });
+                let next = self.next.clone();
+                return Box::pin(async move {
+                    let mut next = next.lock().await;
+                    next.call(req).await
+                });
+            }
+            RouteAuth::Bearer => {}
         }
 
         let access_token = self.access_token.clone();
         let auth_client = self.auth_client.clone();
         let client_id = self.client_id;
         let client_secret = self.client_secret.clone();
+        let scope = self.scope.clone();
         let next = self.next.clone();
 
         Box::pin(async move {
@@ -140,8 +192,7 @@ where
                                 &auth_client,
                                 ClientCredentialsRequest {
                                     grant_type: "client_credentials".to_owned(),
-                                    scope: "applications.commands.update"
-                                        .to_owned(),
+                                    scope: scope.to_string(),
                                 },
                                 client_id,
                                 client_secret.clone(),
@@ -216,6 +267,74 @@ impl<'de> Deserialize<'de> for ClientSecret {
     }
 }
 
+/// The space-separated OAuth2 scopes requested with the client credentials
+/// grant. Common scopes are:
+///
+/// - `applications.commands.update`: create and update the application's
+///   commands (the default).
+/// - `applications.commands`: use commands in guilds.
+/// - `identify`: get information about the application's owner with
+///   `/users/@me`.
+/// - `guilds`: list the guilds the application's owner is in.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Serialize, Deserialize)]
+#[display(fmt = "{}", _0)]
+#[serde(try_from = "String", into = "String")]
+pub struct OAuthScope(String);
+
+impl OAuthScope {
+    pub const DEFAULT: &'static str = "applications.commands.update";
+
+    /// Creates a scope, checking that at least one scope is requested.
+    pub fn new(scope: impl Into<String>) -> Result<Self, InvalidScopeError> {
+        let scope = scope.into();
+        if scope.trim().is_empty() {
+            return Err(InvalidScopeError::Empty);
+        }
+
+        Ok(OAuthScope(scope))
+    }
+
+    /// The individual scopes being requested.
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.0.split_whitespace()
+    }
+}
+
+impl Default for OAuthScope {
+    fn default() -> Self {
+        OAuthScope(Self::DEFAULT.to_owned())
+    }
+}
+
+impl FromStr for OAuthScope {
+    type Err = InvalidScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for OAuthScope {
+    type Error = InvalidScopeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<OAuthScope> for String {
+    fn from(scope: OAuthScope) -> Self {
+        scope.0
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[non_exhaustive]
+pub enum InvalidScopeError {
+    #[display(fmt = "at least one OAuth2 scope must be requested")]
+    Empty,
+}
+
 #[derive(Debug, Display, Error)]
 #[non_exhaustive]
 pub enum AuthenticationError {
@@ -223,6 +342,10 @@ pub enum AuthenticationError {
     ErrorGettingAccessToken(RequestError),
     #[display(fmt = "missing Discord API route info")]
     MissingRouteInfo,
+    #[display(fmt = "route requires a bot token, but none is configured")]
+    MissingBotToken,
+    #[display(fmt = "bot token is not a valid header value")]
+    InvalidBotToken,
 }
 
 impl From<AuthenticationError> for RequestError {
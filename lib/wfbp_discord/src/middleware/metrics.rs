@@ -0,0 +1,78 @@
+use crate::{routes::DiscordRouteInfo, Metrics};
+use futures::future::BoxFuture;
+use reqwest::Response;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+use tower::{Layer, Service};
+use wfbp_http::middleware::RestRequestBuilder;
+
+/// Reports each request sent to Discord to a [`Metrics`] implementation.
+#[derive(Clone, Debug)]
+pub struct MetricsLayer {
+    metrics: Arc<dyn Metrics>,
+}
+
+impl MetricsLayer {
+    pub fn new(metrics: Arc<dyn Metrics>) -> Self {
+        MetricsLayer { metrics }
+    }
+}
+
+impl<Next> Layer<Next> for MetricsLayer {
+    type Service = MetricsService<Next>;
+
+    fn layer(&self, next: Next) -> Self::Service {
+        MetricsService {
+            metrics: self.metrics.clone(),
+            next,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MetricsService<Next> {
+    metrics: Arc<dyn Metrics>,
+    next: Next,
+}
+
+impl<Next> Service<RestRequestBuilder> for MetricsService<Next>
+where
+    Next: Service<RestRequestBuilder, Response = Response>,
+    Next::Future: Send + 'static,
+{
+    type Response = Next::Response;
+    type Error = Next::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.next.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RestRequestBuilder) -> Self::Future {
+        let bucket = req
+            .get::<DiscordRouteInfo>()
+            .map(|info| info.bucket.clone());
+        if let Some(bucket) = bucket.as_ref() {
+            self.metrics.on_request_start(bucket);
+        }
+
+        let metrics = self.metrics.clone();
+        let start = Instant::now();
+        let next_fut = self.next.call(req);
+        Box::pin(async move {
+            let result = next_fut.await;
+            if let Some(bucket) = bucket.as_ref() {
+                let status = result.as_ref().ok().map(Response::status);
+                metrics.on_request_end(bucket, status, start.elapsed());
+            }
+
+            result
+        })
+    }
+}
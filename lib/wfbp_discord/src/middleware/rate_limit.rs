@@ -1,14 +1,14 @@
 use crate::{
-    models::RateLimit, routes::DiscordRouteInfo, RateLimitBucket, RateLimiter,
+    models::RateLimit, routes::DiscordRouteInfo, Metrics, NoopMetrics,
+    RateLimitBucket, RateLimiter, RemainingInfo,
 };
 use anyhow::anyhow;
-use chrono::Utc;
 use derive_more::{Display, Error, From};
 use futures::future::BoxFuture;
 use reqwest::{Response, ResponseBuilderExt};
 use std::{
     collections::HashMap,
-    sync::Arc,
+    sync::{Arc, Mutex as StdMutex, RwLock},
     task::{Context, Poll},
     time::Duration,
 };
@@ -17,9 +17,57 @@ use tower::{Layer, Service};
 use tracing::warn;
 use wfbp_http::{middleware::RestRequestBuilder, RequestError};
 
-#[derive(Clone, Debug, Default)]
+/// The rate limiter for each bucket. Each limiter has its own lock so requests
+/// to different buckets don't wait on each other, and requests to the same
+/// bucket are made one at a time in the order they were queued. This keeps
+/// requests waiting on a bucket from all being sent as soon as it resets.
+type RateLimiters =
+    Arc<StdMutex<HashMap<RateLimitBucket, Arc<Mutex<RateLimiter>>>>>;
+
+/// A copy of the state of each rate limiter. This is kept separately so it
+/// can be read without waiting on requests holding the rate limiters.
+type RateLimitSnapshots = Arc<RwLock<HashMap<RateLimitBucket, RemainingInfo>>>;
+
+#[derive(Clone, Debug)]
 pub struct RateLimitLayer {
-    rate_limiters: Arc<Mutex<HashMap<RateLimitBucket, RateLimiter>>>,
+    rate_limiters: RateLimiters,
+    snapshots: RateLimitSnapshots,
+    metrics: Arc<dyn Metrics>,
+}
+
+impl RateLimitLayer {
+    pub fn new(metrics: Arc<dyn Metrics>) -> Self {
+        RateLimitLayer {
+            rate_limiters: Default::default(),
+            snapshots: Default::default(),
+            metrics,
+        }
+    }
+
+    /// Gets the state of each known rate limit bucket, sorted by route.
+    pub fn snapshot(&self) -> Vec<(RateLimitBucket, RemainingInfo)> {
+        let mut snapshot: Vec<_> = self
+            .snapshots
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(bucket, info)| (bucket.clone(), *info))
+            .collect();
+        snapshot.sort_by(|(a, _), (b, _)| {
+            (a.route, a.method.as_str(), a.major_parameters).cmp(&(
+                b.route,
+                b.method.as_str(),
+                b.major_parameters,
+            ))
+        });
+        snapshot
+    }
+}
+
+impl Default for RateLimitLayer {
+    fn default() -> Self {
+        RateLimitLayer::new(Arc::new(NoopMetrics))
+    }
 }
 
 impl<Next> Layer<Next> for RateLimitLayer {
@@ -28,6 +76,8 @@ impl<Next> Layer<Next> for RateLimitLayer {
     fn layer(&self, next: Next) -> Self::Service {
         RateLimitService {
             rate_limiters: self.rate_limiters.clone(),
+            snapshots: self.snapshots.clone(),
+            metrics: self.metrics.clone(),
             next,
         }
     }
@@ -35,16 +85,15 @@ impl<Next> Layer<Next> for RateLimitLayer {
 
 #[derive(Clone, Debug)]
 pub struct RateLimitService<Next> {
-    rate_limiters: Arc<Mutex<HashMap<RateLimitBucket, RateLimiter>>>,
+    rate_limiters: RateLimiters,
+    snapshots: RateLimitSnapshots,
+    metrics: Arc<dyn Metrics>,
     next: Next,
 }
 
 impl<Next> Service<RestRequestBuilder> for RateLimitService<Next>
 where
-    Next: Service<RestRequestBuilder, Response = Response>
-        + Send
-        + Sync
-        + 'static,
+    Next: Service<RestRequestBuilder, Response = Response> + Send + 'static,
     Next::Error: From<RateLimitError>,
     Next::Future: Send + 'static,
 {
@@ -73,25 +122,35 @@ where
         let bucket = info.bucket.clone();
         let next_fut = self.next.call(req);
         let rate_limiters = self.rate_limiters.clone();
+        let snapshots = self.snapshots.clone();
+        let metrics = self.metrics.clone();
         Box::pin(async move {
             // Get rate limiter for bucket
-            let mut limiter_guard = rate_limiters.lock().await;
-            let limiter =
-                limiter_guard.entry(bucket.clone()).or_insert(RateLimiter {
-                    bucket: bucket.clone(),
-                    limit: 1,
-                    remaining: 1,
-                    reset: Utc::now(),
-                });
+            let limiter = rate_limiters
+                .lock()
+                .unwrap()
+                .entry(bucket.clone())
+                .or_insert_with(|| {
+                    Arc::new(Mutex::new(RateLimiter::new(bucket.clone())))
+                })
+                .clone();
+            let mut limiter = limiter.lock().await;
 
             // Wait until rate limit is refreshed if needed
-            limiter.wait().await;
+            let waited = limiter.wait().await;
+            if !waited.is_zero() {
+                metrics.on_rate_limit_wait(&bucket, waited);
+            }
 
             // Execute request
             let mut response = next_fut.await?;
 
             // Process response
             limiter.update(&response);
+            snapshots
+                .write()
+                .unwrap()
+                .insert(bucket.clone(), limiter.info());
 
             // Check for global rate limit
             let global_limit_hit = response
@@ -113,8 +172,9 @@ where
                 let limit: RateLimit = serde_json::from_slice(&body)
                     .map_err(RateLimitError::GlobalRateLimitParseError)?;
                 warn!(?limit, "hit global rate limit");
-                tokio::time::sleep(Duration::from_secs_f32(limit.retry_after))
-                    .await;
+                let waited = Duration::from_secs_f32(limit.retry_after);
+                tokio::time::sleep(waited).await;
+                metrics.on_rate_limit_wait(&bucket, waited);
 
                 // Reconstruct response
                 let mut builder = http::Response::builder();
@@ -148,9 +208,7 @@ pub enum RateLimitError {
 impl From<RateLimitError> for RequestError {
     fn from(err: RateLimitError) -> Self {
         match err {
-            RateLimitError::ReadBodyError(err) => {
-                RequestError::ReqwestError(err)
-            }
+            RateLimitError::ReadBodyError(err) => err.into(),
             RateLimitError::MissingRouteInfo => anyhow!("{err}").into(),
             RateLimitError::GlobalRateLimitParseError(_) => {
                 anyhow!("{err}").into()
@@ -2,9 +2,13 @@ mod application;
 mod channel;
 mod common;
 mod emoji;
+mod error;
+mod gateway;
 mod guild;
 mod interactions;
+mod invite;
 mod macros;
+mod mention;
 mod permissions;
 mod rate_limit;
 mod stages;
@@ -17,9 +21,13 @@ pub use application::*;
 pub use channel::*;
 pub use common::*;
 pub use emoji::*;
+pub use error::*;
+pub use gateway::*;
 pub use guild::*;
 pub use interactions::*;
+pub use invite::*;
 pub use macros::*;
+pub use mention::*;
 pub use permissions::*;
 pub use rate_limit::*;
 pub use stages::*;
@@ -1,4 +1,4 @@
-use crate::models::{Snowflake, Team, User};
+use crate::models::{Permissions, Snowflake, Team, User};
 use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
 
@@ -56,6 +56,42 @@ pub struct Application {
     /// The application's public flags.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub flags: Option<ApplicationFlags>,
+    /// Up to 5 tags describing the content and functionality of the
+    /// application.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Settings for the application's default in-app authorization link, if
+    /// enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_params: Option<InstallParams>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstallParams {
+    /// The scopes to add the application to the server with.
+    pub scopes: Vec<String>,
+    /// The permissions to request for the bot role.
+    pub permissions: Permissions,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EditCurrentApplication {
+    /// Description of the app.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Icon for the app, as a data URI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// List of tags describing the content and functionality of the app. Max
+    /// of 5 tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Settings for the app's default in-app authorization link, if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_params: Option<InstallParams>,
+    /// App's public flags. Only limited intent flags can be updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<ApplicationFlags>,
 }
 
 bitflags! {
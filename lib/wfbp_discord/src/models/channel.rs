@@ -1,13 +1,18 @@
 use crate::{
     models::{
-        Application, Component, Emoji, GuildMember, Nonce, Permissions,
-        Snowflake, Sticker, StickerItem, Timestamp, User,
+        Application, Component, Emoji, GuildMember,
+        InteractionApplicationCommandCallbackData,
+        InteractionResponseDataFlags, Mention, Nonce, Permissions, Snowflake,
+        Sticker, StickerItem, Timestamp, User,
     },
     serde_inner_enum,
 };
 use bitflags::bitflags;
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
+use std::{borrow::Cow, mem};
+use wfbp_http::MultipartForm;
+use wfbp_http::RequestError;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Channel {
@@ -64,6 +69,12 @@ pub struct Channel {
     pub permissions: Option<Permissions>,
 }
 
+impl Mention for Channel {
+    fn mention(&self) -> String {
+        format!("<#{}>", self.id)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ChannelType(pub u8);
@@ -145,7 +156,7 @@ pub struct Message {
     pub application_id: Option<Snowflake>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub message_reference: Option<MessageReference>,
-    pub flags: u32,
+    pub flags: MessageFlags,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub thread: Option<Channel>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -236,6 +247,8 @@ bitflags! {
         const EPHEMERAL = 1 << 6;
         /// This message is an interaction response and the bot is "thinking".
         const LOADING =  1 << 7;
+        /// This message will not trigger push or desktop notifications.
+        const SUPPRESS_NOTIFICATIONS = 1 << 12;
     }
 }
 
@@ -275,9 +288,9 @@ pub struct Overwrite {
     #[serde(rename = "type")]
     pub kind: OverwriteType,
     /// Permission bit set.
-    pub allow: String,
+    pub allow: Permissions,
     /// Permission bit set.
-    pub deny: String,
+    pub deny: Permissions,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
@@ -287,6 +300,24 @@ pub struct OverwriteType(pub u8);
 impl OverwriteType {
     pub const ROLE: OverwriteType = OverwriteType(0);
     pub const MEMBER: OverwriteType = OverwriteType(1);
+
+    /// Whether this is one of the overwrite types known to Discord.
+    pub fn is_known(self) -> bool {
+        matches!(self, OverwriteType::ROLE | OverwriteType::MEMBER)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EditChannelPermissions {
+    /// The bitwise value of all allowed permissions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Permissions>,
+    /// The bitwise value of all disallowed permissions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny: Option<Permissions>,
+    /// Either 0 (role) or 1 (member).
+    #[serde(rename = "type")]
+    pub kind: OverwriteType,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -319,9 +350,59 @@ pub struct ThreadMember {
     pub flags: ThreadMemberFlags,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct ThreadMemberFlags(pub u32);
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct ThreadMemberFlags: u32 {
+        /// The user has interacted with the thread.
+        const HAS_INTERACTED = 1 << 0;
+        /// The user is notified of all messages in the thread.
+        const ALL_MESSAGES = 1 << 1;
+        /// The user is only notified of messages mentioning them.
+        const ONLY_MENTIONS = 1 << 2;
+        /// The user isn't notified of messages in the thread.
+        const NO_MESSAGES = 1 << 3;
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StartThreadFromMessage {
+    /// 1-100 character channel name.
+    pub name: String,
+    /// Duration in minutes to automatically archive the thread after recent
+    /// activity, can be set to: 60, 1440, 4320, 10080.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StartThreadWithoutMessage {
+    /// 1-100 character channel name.
+    pub name: String,
+    /// Duration in minutes to automatically archive the thread after recent
+    /// activity, can be set to: 60, 1440, 4320, 10080.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<u32>,
+    /// The type of thread to create, one of
+    /// [GUILD_NEWS_THREAD](ChannelType::GUILD_NEWS_THREAD),
+    /// [GUILD_PUBLIC_THREAD](ChannelType::GUILD_PUBLIC_THREAD), or
+    /// [GUILD_PRIVATE_THREAD](ChannelType::GUILD_PRIVATE_THREAD).
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+    /// Whether non-moderators can add other non-moderators to a private
+    /// thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invitable: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActiveThreads {
+    /// The active threads.
+    pub threads: Vec<Channel>,
+    /// A thread member object for each returned thread the current user has
+    /// joined.
+    pub members: Vec<ThreadMember>,
+}
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Embed {
@@ -351,6 +432,37 @@ pub struct Embed {
     pub fields: Option<Vec<EmbedField>>,
 }
 
+impl Embed {
+    /// The most fields an embed can have.
+    pub const MAX_FIELDS: usize = 25;
+    /// The most characters the embeds in a message can have in total.
+    pub const MAX_TOTAL_LENGTH: usize = 6000;
+    /// The most characters an embed's description can have.
+    pub const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+    /// The number of characters in the embed which count towards
+    /// [`MAX_TOTAL_LENGTH`](Self::MAX_TOTAL_LENGTH). This includes the title,
+    /// description, field names and values, footer text and author name.
+    pub fn text_length(&self) -> usize {
+        fn length(text: &Option<String>) -> usize {
+            text.as_deref().map_or(0, |text| text.chars().count())
+        }
+
+        let fields = self.fields.as_deref().unwrap_or_default();
+        length(&self.title)
+            + length(&self.description)
+            + fields.iter().map(EmbedField::text_length).sum::<usize>()
+            + self
+                .footer
+                .as_ref()
+                .map_or(0, |footer| footer.text.chars().count())
+            + self
+                .author
+                .as_ref()
+                .map_or(0, |author| length(&author.name))
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct EmbedThumbnail {
     /// Source url of thumbnail (only supports http(s) and attachments).
@@ -383,7 +495,7 @@ pub struct EmbedVideo {
     pub width: Option<u32>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct EmbedImage {
     /// Source url of image (only supports http(s) and attachments).
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -436,6 +548,13 @@ pub struct EmbedField {
     pub inline: Option<bool>,
 }
 
+impl EmbedField {
+    /// The number of characters in the field's name and value.
+    pub fn text_length(&self) -> usize {
+        self.name.chars().count() + self.value.chars().count()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Attachment {
     pub id: Snowflake,
@@ -466,6 +585,10 @@ pub struct CreateMessage {
     /// The message contents (up to 2000 characters).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    /// Can be used to verify a message was sent (up to 25 characters). If
+    /// set, the request is treated as idempotent and may be retried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Nonce>,
     /// `true` if this is a TTS message.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tts: Option<bool>,
@@ -490,6 +613,93 @@ pub struct CreateMessage {
     /// IDs of up to 3 stickers in the server to send in the message.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sticker_ids: Option<Vec<Snowflake>>,
+    /// Message flags. Only [SUPPRESS_EMBEDS](MessageFlags::SUPPRESS_EMBEDS)
+    /// and [SUPPRESS_NOTIFICATIONS](MessageFlags::SUPPRESS_NOTIFICATIONS) can
+    /// be set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+}
+
+impl CreateMessage {
+    /// The most characters a message's content can have.
+    pub const MAX_CONTENT_LENGTH: usize = 2000;
+
+    /// Checks that Discord will accept the message's content and embeds.
+    pub fn validate_content(&self) -> Result<(), MessageContentError> {
+        validate_message_content(
+            self.content.as_deref(),
+            self.embeds.as_deref().unwrap_or_default(),
+        )
+    }
+}
+
+/// Checks that Discord will accept a message's content and embeds. Content
+/// can have up to 2000 characters, and a message can have up to 10 embeds
+/// with up to 6000 characters between them.
+pub fn validate_message_content(
+    content: Option<&str>,
+    embeds: &[Embed],
+) -> Result<(), MessageContentError> {
+    let content_length = content.map_or(0, |content| content.chars().count());
+    if content_length > CreateMessage::MAX_CONTENT_LENGTH {
+        return Err(MessageContentError::ContentTooLong(content_length));
+    }
+
+    if embeds.len() > CreateWebhookMessage::MAX_EMBEDS {
+        return Err(MessageContentError::TooManyEmbeds(embeds.len()));
+    }
+
+    for (index, embed) in embeds.iter().enumerate() {
+        let fields = embed.fields.as_ref().map_or(0, Vec::len);
+        if fields > Embed::MAX_FIELDS {
+            return Err(MessageContentError::TooManyFields(index));
+        }
+
+        let description = embed
+            .description
+            .as_ref()
+            .map_or(0, |description| description.chars().count());
+        if description > Embed::MAX_DESCRIPTION_LENGTH {
+            return Err(MessageContentError::DescriptionTooLong(index));
+        }
+    }
+
+    let embeds_length = embeds.iter().map(Embed::text_length).sum();
+    if embeds_length > Embed::MAX_TOTAL_LENGTH {
+        return Err(MessageContentError::EmbedsTooLong(embeds_length));
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, Display, Error)]
+#[non_exhaustive]
+pub enum MessageContentError {
+    #[display(
+        fmt = "message content can have at most 2000 characters, found {}",
+        _0
+    )]
+    ContentTooLong(#[error(ignore)] usize),
+    #[display(fmt = "a message can have at most 10 embeds, found {}", _0)]
+    TooManyEmbeds(#[error(ignore)] usize),
+    #[display(fmt = "embed {} can have at most 25 fields", _0)]
+    TooManyFields(#[error(ignore)] usize),
+    #[display(
+        fmt = "embed {} description can have at most 4096 characters",
+        _0
+    )]
+    DescriptionTooLong(#[error(ignore)] usize),
+    #[display(
+        fmt = "embeds can have at most 6000 characters in total, found {}",
+        _0
+    )]
+    EmbedsTooLong(#[error(ignore)] usize),
+}
+
+impl From<MessageContentError> for RequestError {
+    fn from(error: MessageContentError) -> Self {
+        RequestError::Custom(error.into())
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -509,6 +719,79 @@ pub struct AllowedMentions {
     pub replied_user: Option<bool>,
 }
 
+impl AllowedMentions {
+    /// Suppresses all mentions.
+    pub fn none() -> Self {
+        AllowedMentions {
+            parse: Some(vec![]),
+            ..Default::default()
+        }
+    }
+
+    /// Allows all mentions.
+    pub fn all() -> Self {
+        AllowedMentions {
+            parse: Some(vec![
+                AllowedMentionType::ROLE,
+                AllowedMentionType::USER,
+                AllowedMentionType::EVERYONE,
+            ]),
+            ..Default::default()
+        }
+    }
+
+    /// Only allows mentions of the given users.
+    pub fn only_users(ids: impl IntoIterator<Item = Snowflake>) -> Self {
+        AllowedMentions {
+            parse: Some(vec![]),
+            users: Some(ids.into_iter().collect()),
+            ..Default::default()
+        }
+    }
+
+    /// Only allows mentions of the given roles.
+    pub fn only_roles(ids: impl IntoIterator<Item = Snowflake>) -> Self {
+        AllowedMentions {
+            parse: Some(vec![]),
+            roles: Some(ids.into_iter().collect()),
+            ..Default::default()
+        }
+    }
+
+    /// Checks that Discord will accept these allowed mentions. Roles and
+    /// users can't be listed explicitly while also being parsed from the
+    /// content.
+    pub fn validate(&self) -> Result<(), AllowedMentionsError> {
+        let parse = self.parse.as_deref().unwrap_or_default();
+        let has_roles = matches!(&self.roles, Some(roles) if !roles.is_empty());
+        if has_roles && parse.contains(&AllowedMentionType::ROLE) {
+            return Err(AllowedMentionsError::RolesConflict);
+        }
+
+        let has_users = matches!(&self.users, Some(users) if !users.is_empty());
+        if has_users && parse.contains(&AllowedMentionType::USER) {
+            return Err(AllowedMentionsError::UsersConflict);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Display, Error)]
+#[non_exhaustive]
+pub enum AllowedMentionsError {
+    #[display(fmt = "roles can't be listed while also parsing role mentions")]
+    RolesConflict,
+    #[display(fmt = "users can't be listed while also parsing user mentions")]
+    UsersConflict,
+}
+
+impl From<AllowedMentionsError> for RequestError {
+    fn from(error: AllowedMentionsError) -> Self {
+        RequestError::Custom(error.into())
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct AllowedMentionType(pub Cow<'static, str>);
@@ -554,12 +837,222 @@ pub struct CreateWebhookMessage {
     /// The components to include with the message.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub components: Option<Vec<Component>>,
-    /// For followup messages, can be set to
-    /// [EPHEMERAL](MessageFlags::EPHEMERAL).
+    /// Message flags. Only [SUPPRESS_EMBEDS](MessageFlags::SUPPRESS_EMBEDS)
+    /// and [SUPPRESS_NOTIFICATIONS](MessageFlags::SUPPRESS_NOTIFICATIONS) can
+    /// be set, or [EPHEMERAL](MessageFlags::EPHEMERAL) for followup messages.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub flags: Option<MessageFlags>,
 }
 
+impl CreateWebhookMessage {
+    /// The most embeds a message can have.
+    pub const MAX_EMBEDS: usize = 10;
+
+    /// Creates a builder for a webhook message.
+    pub fn builder() -> CreateWebhookMessageBuilder {
+        CreateWebhookMessageBuilder::default()
+    }
+
+    /// Checks that Discord will accept the message's content and embeds.
+    pub fn validate_content(&self) -> Result<(), MessageContentError> {
+        validate_message_content(
+            self.content.as_deref(),
+            self.embeds.as_deref().unwrap_or_default(),
+        )
+    }
+}
+
+/// Builder for a [`CreateWebhookMessage`]. This can also build the callback
+/// data for an initial interaction response.
+#[derive(Clone, Debug, Default)]
+pub struct CreateWebhookMessageBuilder {
+    message: CreateWebhookMessage,
+    ephemeral: bool,
+    silent: bool,
+}
+
+impl CreateWebhookMessageBuilder {
+    /// Sets the message contents (up to 2000 characters).
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.message.content = Some(content.into());
+        self
+    }
+
+    /// Adds an embed to the message.
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.message.embeds.get_or_insert_with(Vec::new).push(embed);
+        self
+    }
+
+    /// Adds multiple embeds to the message.
+    pub fn embeds(mut self, embeds: impl IntoIterator<Item = Embed>) -> Self {
+        self.message
+            .embeds
+            .get_or_insert_with(Vec::new)
+            .extend(embeds);
+        self
+    }
+
+    /// Adds an embed with the given fields, splitting them across additional
+    /// embeds if there are more than [`Embed::MAX_FIELDS`]. The first embed is
+    /// `template`, and the rest only keep its color. Fields which would exceed
+    /// [`CreateWebhookMessage::MAX_EMBEDS`] or [`Embed::MAX_TOTAL_LENGTH`]
+    /// are dropped. If the message already has
+    /// [`CreateWebhookMessage::MAX_EMBEDS`] embeds, nothing is added.
+    pub fn field_embeds(
+        mut self,
+        template: Embed,
+        fields: impl IntoIterator<Item = EmbedField>,
+    ) -> Self {
+        let color = template.color;
+        let embeds = self.message.embeds.get_or_insert_with(Vec::new);
+        if embeds.len() >= CreateWebhookMessage::MAX_EMBEDS {
+            return self;
+        }
+
+        let mut length: usize = embeds.iter().map(Embed::text_length).sum();
+        length += template.text_length();
+        let mut current = template;
+        for field in fields {
+            let field_length = field.text_length();
+            if length + field_length > Embed::MAX_TOTAL_LENGTH {
+                break;
+            }
+
+            let field_count = current.fields.as_ref().map_or(0, Vec::len);
+            if field_count >= Embed::MAX_FIELDS {
+                if embeds.len() + 2 > CreateWebhookMessage::MAX_EMBEDS {
+                    break;
+                }
+
+                let next = Embed {
+                    color,
+                    ..Default::default()
+                };
+                embeds.push(mem::replace(&mut current, next));
+            }
+
+            length += field_length;
+            current.fields.get_or_insert_with(Vec::new).push(field);
+        }
+
+        embeds.push(current);
+        self
+    }
+
+    /// Makes the message only visible to the user who invoked the
+    /// interaction.
+    pub fn ephemeral(mut self) -> Self {
+        self.ephemeral = true;
+        self
+    }
+
+    /// Sends the message without triggering push or desktop notifications.
+    pub fn silent(mut self) -> Self {
+        self.silent = true;
+        self
+    }
+
+    /// Sets the components to include with the message.
+    pub fn components(
+        mut self,
+        components: impl IntoIterator<Item = Component>,
+    ) -> Self {
+        self.message.components = Some(components.into_iter().collect());
+        self
+    }
+
+    /// Sets the allowed mentions for the message.
+    pub fn allowed_mentions(
+        mut self,
+        allowed_mentions: AllowedMentions,
+    ) -> Self {
+        self.message.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Builds a webhook message, used for followup messages.
+    pub fn build(self) -> CreateWebhookMessage {
+        let mut message = self.message;
+        if self.ephemeral {
+            let flags = message.flags.get_or_insert_with(Default::default);
+            flags.insert(MessageFlags::EPHEMERAL);
+        }
+        if self.silent {
+            let flags = message.flags.get_or_insert_with(Default::default);
+            flags.insert(MessageFlags::SUPPRESS_NOTIFICATIONS);
+        }
+
+        message
+    }
+
+    /// Builds the data for an interaction response.
+    pub fn build_callback_data(
+        self,
+    ) -> InteractionApplicationCommandCallbackData {
+        let mut flags = InteractionResponseDataFlags::empty();
+        flags.set(InteractionResponseDataFlags::EPHEMERAL, self.ephemeral);
+        flags.set(
+            InteractionResponseDataFlags::SUPPRESS_NOTIFICATIONS,
+            self.silent,
+        );
+        let flags = if flags.is_empty() { None } else { Some(flags) };
+        InteractionApplicationCommandCallbackData {
+            tts: self.message.tts,
+            content: self.message.content,
+            embeds: self.message.embeds,
+            allowed_mentions: self.message.allowed_mentions,
+            flags,
+            components: self.message.components,
+        }
+    }
+}
+
+/// A file to upload with a message.
+#[derive(Clone, Debug)]
+pub struct AttachmentFile {
+    /// Name of the uploaded file.
+    pub file_name: String,
+    /// MIME type of the file.
+    pub content_type: String,
+    /// Contents of the file.
+    pub data: Vec<u8>,
+}
+
+impl AttachmentFile {
+    /// The URL that embeds in the same message can use to refer to the file.
+    pub fn attachment_url(&self) -> String {
+        format!("attachment://{}", self.file_name)
+    }
+}
+
+/// A webhook message with files, sent as `multipart/form-data`.
+#[derive(Clone, Debug)]
+pub struct CreateWebhookMessageWithFiles {
+    /// The message to send.
+    pub message: CreateWebhookMessage,
+    /// The files to upload.
+    pub files: Vec<AttachmentFile>,
+}
+
+impl From<&CreateWebhookMessageWithFiles> for MultipartForm {
+    fn from(message: &CreateWebhookMessageWithFiles) -> Self {
+        let payload_json = serde_json::to_string(&message.message)
+            .expect("error serializing message");
+        message.files.iter().enumerate().fold(
+            MultipartForm::new().text("payload_json", payload_json),
+            |form, (index, file)| {
+                form.file(
+                    format!("files[{}]", index),
+                    file.file_name.clone(),
+                    file.content_type.clone(),
+                    file.data.clone(),
+                )
+            },
+        )
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct EditWebhookMessage {
     /// The message contents (up to 2000 characters).
@@ -1,8 +1,9 @@
-use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize};
 use std::{
     fmt::{Display, Formatter},
-    num::TryFromIntError,
+    num::{ParseIntError, TryFromIntError},
+    str::FromStr,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
@@ -23,12 +24,19 @@ impl Snowflake {
         self.0
     }
 
+    /// The time at which this ID was generated.
+    pub fn created_at(self) -> Timestamp {
+        // The timestamp is only 42 bits, so it always fits in an i64
+        let millis = (self.0 >> 22) + Self::DISCORD_EPOCH;
+        let datetime = Utc.timestamp_millis_opt(millis as i64).unwrap();
+        Timestamp(datetime)
+    }
+
+    /// The time at which this ID was generated. This can't fail, so use
+    /// [`Snowflake::created_at`] instead.
+    #[deprecated(note = "use `Snowflake::created_at` instead")]
     pub fn timestamp(self) -> Result<Timestamp, TryFromIntError> {
-        let timestamp = (self.0 >> 22) + Self::DISCORD_EPOCH;
-        let timestamp = timestamp.try_into()?;
-        let naive = NaiveDateTime::from_timestamp(timestamp, 0);
-        let datetime = DateTime::from_utc(naive, FixedOffset::east(0));
-        Ok(Timestamp(datetime))
+        Ok(self.created_at())
     }
 
     pub fn worker_id(self) -> u8 {
@@ -50,11 +58,21 @@ impl Display for Snowflake {
     }
 }
 
+impl FromStr for Snowflake {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Snowflake)
+    }
+}
+
 impl Serialize for Snowflake {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
+        // Discord sends snowflakes as strings since they may not fit in the
+        // integers supported by some JSON parsers
         serializer.serialize_str(&self.0.to_string())
     }
 }
@@ -76,7 +94,7 @@ impl<'de> Deserialize<'de> for Snowflake {
             where
                 E: serde::de::Error,
             {
-                v.parse().map(Snowflake).map_err(serde::de::Error::custom)
+                v.parse().map_err(serde::de::Error::custom)
             }
         }
 
@@ -97,7 +115,196 @@ impl<'de> Deserialize<'de> for Snowflake {
     Deserialize,
 )]
 #[serde(transparent)]
-pub struct Timestamp(pub DateTime<FixedOffset>);
+pub struct Timestamp(pub DateTime<Utc>);
+
+impl Timestamp {
+    /// The current time.
+    pub fn now() -> Self {
+        Timestamp(Utc::now())
+    }
+
+    /// Creates a timestamp from the number of seconds since the Unix epoch.
+    pub fn from_unix(secs: i64) -> Option<Self> {
+        Utc.timestamp_opt(secs, 0).single().map(Timestamp)
+    }
+
+    /// The number of seconds since the Unix epoch.
+    pub fn to_unix(self) -> i64 {
+        self.0.timestamp()
+    }
+
+    /// Formats the timestamp using Discord's timestamp markdown, which is
+    /// displayed in each user's local time zone.
+    pub fn to_discord_markdown(self, style: TimestampStyle) -> String {
+        format!("<t:{}:{}>", self.to_unix(), style.0)
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        Timestamp(datetime)
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct TimestampStyle(pub char);
+
+impl TimestampStyle {
+    /// 16:20
+    pub const SHORT_TIME: TimestampStyle = TimestampStyle('t');
+    /// 16:20:30
+    pub const LONG_TIME: TimestampStyle = TimestampStyle('T');
+    /// 20/04/2021
+    pub const SHORT_DATE: TimestampStyle = TimestampStyle('d');
+    /// 20 April 2021
+    pub const LONG_DATE: TimestampStyle = TimestampStyle('D');
+    /// 20 April 2021 16:20
+    pub const SHORT_DATE_TIME: TimestampStyle = TimestampStyle('f');
+    /// Tuesday, 20 April 2021 16:20
+    pub const LONG_DATE_TIME: TimestampStyle = TimestampStyle('F');
+    /// 2 months ago
+    pub const RELATIVE: TimestampStyle = TimestampStyle('R');
+}
+
+/// An RGB color, encoded as `0xRRGGBB`.
+#[derive(
+    Clone, Copy, PartialEq, Eq, Debug, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Color(pub u32);
+
+impl Color {
+    /// The color used when no color is set.
+    pub const DEFAULT: Color = Color(0);
+
+    pub const fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        Color((red as u32) << 16 | (green as u32) << 8 | blue as u32)
+    }
+
+    pub const fn red(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    pub const fn green(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    pub const fn blue(self) -> u8 {
+        self.0 as u8
+    }
+}
+
+impl From<u32> for Color {
+    fn from(value: u32) -> Self {
+        Color(value)
+    }
+}
+
+/// Raw image data. This is sent to Discord as a base64-encoded data URI.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct ImageData(pub Vec<u8>);
+
+impl ImageData {
+    /// Detects the format of the image from its header.
+    pub fn format(&self) -> Option<ImageFormat> {
+        ImageFormat::detect(&self.0)
+    }
+
+    /// Creates a data URI from the image. Returns [`None`] if the format of
+    /// the image is not supported by Discord.
+    pub fn to_data_uri(&self) -> Option<String> {
+        self.format().map(|format| {
+            format!(
+                "data:{};base64,{}",
+                format.mime_type(),
+                base64::encode(&self.0)
+            )
+        })
+    }
+}
+
+impl From<Vec<u8>> for ImageData {
+    fn from(data: Vec<u8>) -> Self {
+        ImageData(data)
+    }
+}
+
+impl Serialize for ImageData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let data_uri = self.to_data_uri().ok_or_else(|| {
+            serde::ser::Error::custom("unsupported image format")
+        })?;
+        serializer.serialize_str(&data_uri)
+    }
+}
+
+impl<'de> Deserialize<'de> for ImageData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ImageDataVisitor;
+        impl<'de> Visitor<'de> for ImageDataVisitor {
+            type Value = ImageData;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                write!(f, "a base64-encoded data URI")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                let (_, encoded) = v
+                    .strip_prefix("data:")
+                    .and_then(|v| v.split_once(";base64,"))
+                    .ok_or_else(|| E::custom("invalid data URI"))?;
+                base64::decode(encoded).map(ImageData).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ImageDataVisitor)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+}
+
+impl ImageFormat {
+    /// Detects the format of an image from its magic bytes.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(ImageFormat::Png)
+        } else if data.starts_with(b"\xff\xd8\xff") {
+            Some(ImageFormat::Jpeg)
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            Some(ImageFormat::Gif)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Gif => "image/gif",
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub enum Nonce {
@@ -1,4 +1,4 @@
-use crate::models::{Snowflake, User};
+use crate::models::{ImageData, Mention, Snowflake, User};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -18,3 +18,39 @@ pub struct Emoji {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub available: Option<bool>,
 }
+
+impl Mention for Emoji {
+    /// Formats this emoji so it can be used in a message. Custom emojis are
+    /// formatted as `<:name:id>` (or `<a:name:id>` if animated) and unicode
+    /// emojis are formatted as the emoji itself.
+    fn mention(&self) -> String {
+        let name = self.name.as_deref().unwrap_or_default();
+        match self.id {
+            Some(id) if self.animated == Some(true) => {
+                format!("<a:{}:{}>", name, id)
+            }
+            Some(id) => format!("<:{}:{}>", name, id),
+            None => name.to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateGuildEmoji {
+    /// Name of the emoji.
+    pub name: String,
+    /// The 128x128 emoji image.
+    pub image: ImageData,
+    /// Roles allowed to use this emoji.
+    pub roles: Vec<Snowflake>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModifyGuildEmoji {
+    /// Name of the emoji.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Roles allowed to use this emoji.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<Snowflake>>,
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// A JSON error code returned by the Discord API.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DiscordErrorCode(pub i32);
+
+impl DiscordErrorCode {
+    /// General error (such as a malformed request body, amongst other
+    /// things).
+    pub const GENERAL_ERROR: DiscordErrorCode = DiscordErrorCode(0);
+    /// Unknown account.
+    pub const UNKNOWN_ACCOUNT: DiscordErrorCode = DiscordErrorCode(10001);
+    /// Unknown application.
+    pub const UNKNOWN_APPLICATION: DiscordErrorCode = DiscordErrorCode(10002);
+    /// Unknown channel.
+    pub const UNKNOWN_CHANNEL: DiscordErrorCode = DiscordErrorCode(10003);
+    /// Unknown guild.
+    pub const UNKNOWN_GUILD: DiscordErrorCode = DiscordErrorCode(10004);
+    /// Unknown integration.
+    pub const UNKNOWN_INTEGRATION: DiscordErrorCode = DiscordErrorCode(10005);
+    /// Unknown invite.
+    pub const UNKNOWN_INVITE: DiscordErrorCode = DiscordErrorCode(10006);
+    /// Unknown member.
+    pub const UNKNOWN_MEMBER: DiscordErrorCode = DiscordErrorCode(10007);
+    /// Unknown message.
+    pub const UNKNOWN_MESSAGE: DiscordErrorCode = DiscordErrorCode(10008);
+    /// Unknown permission overwrite.
+    pub const UNKNOWN_PERMISSION_OVERWRITE: DiscordErrorCode =
+        DiscordErrorCode(10009);
+    /// Unknown role.
+    pub const UNKNOWN_ROLE: DiscordErrorCode = DiscordErrorCode(10011);
+    /// Unknown token.
+    pub const UNKNOWN_TOKEN: DiscordErrorCode = DiscordErrorCode(10012);
+    /// Unknown user.
+    pub const UNKNOWN_USER: DiscordErrorCode = DiscordErrorCode(10013);
+    /// Unknown emoji.
+    pub const UNKNOWN_EMOJI: DiscordErrorCode = DiscordErrorCode(10014);
+    /// Unknown webhook.
+    pub const UNKNOWN_WEBHOOK: DiscordErrorCode = DiscordErrorCode(10015);
+    /// Unknown interaction.
+    pub const UNKNOWN_INTERACTION: DiscordErrorCode = DiscordErrorCode(10062);
+    /// Unknown application command.
+    pub const UNKNOWN_APPLICATION_COMMAND: DiscordErrorCode =
+        DiscordErrorCode(10063);
+    /// Bots cannot use this endpoint.
+    pub const BOTS_CANNOT_USE_ENDPOINT: DiscordErrorCode =
+        DiscordErrorCode(20001);
+    /// Only bots can use this endpoint.
+    pub const ONLY_BOTS_CAN_USE_ENDPOINT: DiscordErrorCode =
+        DiscordErrorCode(20002);
+    /// Maximum number of pins reached for the channel (50).
+    pub const MAX_PINS_REACHED: DiscordErrorCode = DiscordErrorCode(30003);
+    /// Maximum number of emojis reached.
+    pub const MAX_EMOJIS_REACHED: DiscordErrorCode = DiscordErrorCode(30008);
+    /// Unauthorized. Provide a valid token and try again.
+    pub const UNAUTHORIZED: DiscordErrorCode = DiscordErrorCode(40001);
+    /// Interaction has already been acknowledged.
+    pub const INTERACTION_ALREADY_ACKNOWLEDGED: DiscordErrorCode =
+        DiscordErrorCode(40060);
+    /// Missing access.
+    pub const MISSING_ACCESS: DiscordErrorCode = DiscordErrorCode(50001);
+    /// Cannot send an empty message.
+    pub const CANNOT_SEND_EMPTY_MESSAGE: DiscordErrorCode =
+        DiscordErrorCode(50006);
+    /// Cannot send messages to this user.
+    pub const CANNOT_SEND_MESSAGES_TO_USER: DiscordErrorCode =
+        DiscordErrorCode(50007);
+    /// Missing permissions.
+    pub const MISSING_PERMISSIONS: DiscordErrorCode = DiscordErrorCode(50013);
+    /// Invalid form body (returned for both `application/json` and
+    /// `multipart/form-data` bodies), or invalid `Content-Type` provided.
+    pub const INVALID_FORM_BODY: DiscordErrorCode = DiscordErrorCode(50035);
+}
+
+impl From<i32> for DiscordErrorCode {
+    fn from(value: i32) -> Self {
+        DiscordErrorCode(value)
+    }
+}
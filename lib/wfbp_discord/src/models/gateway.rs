@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Gateway {
+    /// The WSS URL that can be used for connecting to the gateway.
+    pub url: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GatewayBot {
+    /// The WSS URL that can be used for connecting to the gateway.
+    pub url: String,
+    /// The recommended number of shards to use when connecting.
+    pub shards: u32,
+    /// Information on the current session start limit.
+    pub session_start_limit: SessionStartLimit,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionStartLimit {
+    /// The total number of session starts the current user is allowed.
+    pub total: u32,
+    /// The remaining number of session starts the current user is allowed.
+    pub remaining: u32,
+    /// The number of milliseconds after which the limit resets.
+    pub reset_after: u64,
+    /// The number of identify requests allowed per 5 seconds.
+    pub max_concurrency: u32,
+}
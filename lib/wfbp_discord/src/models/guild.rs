@@ -1,10 +1,12 @@
 use crate::models::{
-    Channel, Emoji, Permissions, Role, Snowflake, StageInstance, Sticker,
-    Timestamp, User, VoiceState,
+    Channel, ChannelType, Emoji, Mention, Overwrite, Permissions, Role,
+    Snowflake, StageInstance, Sticker, Timestamp, User, VoiceState,
 };
 use bitflags::bitflags;
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use wfbp_http::RequestError;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Guild {
@@ -292,6 +294,63 @@ pub struct GuildMember {
     permissions: Option<Permissions>,
 }
 
+impl GuildMember {
+    /// The user this guild member represents. This is not included in
+    /// `MESSAGE_CREATE` and `MESSAGE_UPDATE` gateway events.
+    pub fn user(&self) -> Option<&User> {
+        self.user.as_ref()
+    }
+
+    /// IDs of the roles assigned to the member.
+    pub fn roles(&self) -> &[Snowflake] {
+        &self.roles
+    }
+
+    /// Total permissions of the member in the channel, including overwrites.
+    /// This is only included in interactions.
+    pub fn permissions(&self) -> Option<Permissions> {
+        self.permissions
+    }
+}
+
+impl Mention for GuildMember {
+    /// Formats a mention of this member, or an empty string if the member
+    /// has no user attached.
+    fn mention(&self) -> String {
+        self.user.as_ref().map(User::mention).unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModifyGuildMember {
+    /// Value to set user's nickname to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+    /// Array of role IDs the member is assigned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<Snowflake>>,
+    /// Whether the user is muted in voice channels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    /// Whether the user is deafened in voice channels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+    /// ID of channel to move user to (if they are connected to voice).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Snowflake>,
+    /// When the user's timeout will expire and the user will be able to
+    /// communicate in the guild again (up to 28 days in the future).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModifyCurrentMember {
+    /// Value to set user's nickname to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Integration {
     /// Integration id.
@@ -401,3 +460,493 @@ pub struct WelcomeScreenChannel {
     /// no emoji is set.
     emoji_name: Option<String>,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditLog {
+    /// List of audit log entries.
+    pub audit_log_entries: Vec<AuditLogEntry>,
+    /// List of partial integration objects.
+    pub integrations: Vec<AuditLogIntegration>,
+    /// List of threads found in the audit log.
+    pub threads: Vec<Channel>,
+    /// List of users found in the audit log.
+    pub users: Vec<User>,
+    // TODO: needs a webhook model
+    // pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    /// ID of the affected entity (webhook, user, role, etc.).
+    pub target_id: Option<String>,
+    /// Changes made to the target_id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Vec<AuditLogChange>>,
+    /// The user who made the changes.
+    pub user_id: Option<Snowflake>,
+    /// ID of the entry.
+    pub id: Snowflake,
+    /// Type of action that occurred.
+    pub action_type: AuditLogEvent,
+    /// Additional info for certain action types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<OptionalAuditEntryInfo>,
+    /// The reason for the change (0-512 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditLogChange {
+    /// New value of the key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<serde_json::Value>,
+    /// Old value of the key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<serde_json::Value>,
+    /// Name of the audit log change key.
+    pub key: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OptionalAuditEntryInfo {
+    /// Channel in which the entities were targeted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Snowflake>,
+    /// Number of entities that were targeted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<String>,
+    /// Number of days after which inactive members were kicked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_member_days: Option<String>,
+    /// ID of the overwritten entity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Snowflake>,
+    /// Number of members removed by the prune.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members_removed: Option<String>,
+    /// ID of the message that was targeted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<Snowflake>,
+    /// Name of the role if type is "0" (not present if type is "1").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_name: Option<String>,
+    /// Type of overwritten entity - "0" for role or "1" for member.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditLogIntegration {
+    /// Integration id.
+    pub id: Snowflake,
+    /// Integration name.
+    pub name: String,
+    /// Integration type (twitch, youtube, or discord).
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Integration account information.
+    pub account: IntegrationAccount,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuditLogEvent(pub u8);
+
+impl AuditLogEvent {
+    pub const GUILD_UPDATE: AuditLogEvent = AuditLogEvent(1);
+    pub const CHANNEL_CREATE: AuditLogEvent = AuditLogEvent(10);
+    pub const CHANNEL_UPDATE: AuditLogEvent = AuditLogEvent(11);
+    pub const CHANNEL_DELETE: AuditLogEvent = AuditLogEvent(12);
+    pub const CHANNEL_OVERWRITE_CREATE: AuditLogEvent = AuditLogEvent(13);
+    pub const CHANNEL_OVERWRITE_UPDATE: AuditLogEvent = AuditLogEvent(14);
+    pub const CHANNEL_OVERWRITE_DELETE: AuditLogEvent = AuditLogEvent(15);
+    pub const MEMBER_KICK: AuditLogEvent = AuditLogEvent(20);
+    pub const MEMBER_PRUNE: AuditLogEvent = AuditLogEvent(21);
+    pub const MEMBER_BAN_ADD: AuditLogEvent = AuditLogEvent(22);
+    pub const MEMBER_BAN_REMOVE: AuditLogEvent = AuditLogEvent(23);
+    pub const MEMBER_UPDATE: AuditLogEvent = AuditLogEvent(24);
+    pub const MEMBER_ROLE_UPDATE: AuditLogEvent = AuditLogEvent(25);
+    pub const MEMBER_MOVE: AuditLogEvent = AuditLogEvent(26);
+    pub const MEMBER_DISCONNECT: AuditLogEvent = AuditLogEvent(27);
+    pub const BOT_ADD: AuditLogEvent = AuditLogEvent(28);
+    pub const ROLE_CREATE: AuditLogEvent = AuditLogEvent(30);
+    pub const ROLE_UPDATE: AuditLogEvent = AuditLogEvent(31);
+    pub const ROLE_DELETE: AuditLogEvent = AuditLogEvent(32);
+    pub const INVITE_CREATE: AuditLogEvent = AuditLogEvent(40);
+    pub const INVITE_UPDATE: AuditLogEvent = AuditLogEvent(41);
+    pub const INVITE_DELETE: AuditLogEvent = AuditLogEvent(42);
+    pub const WEBHOOK_CREATE: AuditLogEvent = AuditLogEvent(50);
+    pub const WEBHOOK_UPDATE: AuditLogEvent = AuditLogEvent(51);
+    pub const WEBHOOK_DELETE: AuditLogEvent = AuditLogEvent(52);
+    pub const EMOJI_CREATE: AuditLogEvent = AuditLogEvent(60);
+    pub const EMOJI_UPDATE: AuditLogEvent = AuditLogEvent(61);
+    pub const EMOJI_DELETE: AuditLogEvent = AuditLogEvent(62);
+    pub const MESSAGE_DELETE: AuditLogEvent = AuditLogEvent(72);
+    pub const MESSAGE_BULK_DELETE: AuditLogEvent = AuditLogEvent(73);
+    pub const MESSAGE_PIN: AuditLogEvent = AuditLogEvent(74);
+    pub const MESSAGE_UNPIN: AuditLogEvent = AuditLogEvent(75);
+    pub const INTEGRATION_CREATE: AuditLogEvent = AuditLogEvent(80);
+    pub const INTEGRATION_UPDATE: AuditLogEvent = AuditLogEvent(81);
+    pub const INTEGRATION_DELETE: AuditLogEvent = AuditLogEvent(82);
+    pub const STAGE_INSTANCE_CREATE: AuditLogEvent = AuditLogEvent(83);
+    pub const STAGE_INSTANCE_UPDATE: AuditLogEvent = AuditLogEvent(84);
+    pub const STAGE_INSTANCE_DELETE: AuditLogEvent = AuditLogEvent(85);
+    pub const STICKER_CREATE: AuditLogEvent = AuditLogEvent(90);
+    pub const STICKER_UPDATE: AuditLogEvent = AuditLogEvent(91);
+    pub const STICKER_DELETE: AuditLogEvent = AuditLogEvent(92);
+    pub const THREAD_CREATE: AuditLogEvent = AuditLogEvent(110);
+    pub const THREAD_UPDATE: AuditLogEvent = AuditLogEvent(111);
+    pub const THREAD_DELETE: AuditLogEvent = AuditLogEvent(112);
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GetGuildAuditLogQuery {
+    /// Filter the log for actions made by a user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Snowflake>,
+    /// The type of audit log event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_type: Option<AuditLogEvent>,
+    /// Filter the log before a certain entry id. Used to paginate through
+    /// older entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Snowflake>,
+    /// How many entries are returned (default 50, minimum 1, maximum 100).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateGuildChannel {
+    /// Channel name (1-100 characters).
+    pub name: String,
+    /// The type of channel.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChannelType>,
+    /// Channel topic (0-1024 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// The bitrate (in bits) of the voice channel (voice only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    /// The user limit of the voice channel (voice only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u32>,
+    /// Amount of seconds a user has to wait before sending another message
+    /// (0-21600).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u32>,
+    /// Sorting position of the channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u16>,
+    /// The channel's permission overwrites.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_overwrites: Option<Vec<Overwrite>>,
+    /// ID of the parent category for a channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Snowflake>,
+    /// Whether the channel is nsfw.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModifyGuildChannelPosition {
+    /// Channel ID.
+    pub id: Snowflake,
+    /// Sorting position of the channel.
+    pub position: Option<u16>,
+    /// Syncs the permission overwrites with the new parent, if moving to a
+    /// new category.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_permissions: Option<bool>,
+    /// The new parent ID for the channel that is moved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Snowflake>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GuildScheduledEvent {
+    /// The ID of the scheduled event.
+    pub id: Snowflake,
+    /// The guild ID which the scheduled event belongs to.
+    pub guild_id: Snowflake,
+    /// The channel ID in which the scheduled event will be hosted, or `None`
+    /// for external events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Snowflake>,
+    /// The ID of the user that created the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator_id: Option<Snowflake>,
+    /// The name of the scheduled event (1-100 characters).
+    pub name: String,
+    /// The description of the scheduled event (1-1000 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The time the scheduled event will start.
+    pub scheduled_start_time: Timestamp,
+    /// The time the scheduled event will end. This is required for external
+    /// events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_time: Option<Timestamp>,
+    /// The privacy level of the scheduled event.
+    pub privacy_level: GuildScheduledEventPrivacyLevel,
+    /// The status of the scheduled event.
+    pub status: GuildScheduledEventStatus,
+    /// The type of the scheduled event.
+    pub entity_type: GuildScheduledEventEntityType,
+    /// The ID of an entity associated with the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_id: Option<Snowflake>,
+    /// Additional metadata for the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<GuildScheduledEventEntityMetadata>,
+    /// The user that created the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<User>,
+    /// The number of users subscribed to the scheduled event. This is only
+    /// included when requested with `with_user_count`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_count: Option<u32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GuildScheduledEventPrivacyLevel(pub u8);
+
+impl GuildScheduledEventPrivacyLevel {
+    /// The scheduled event is only accessible to guild members.
+    pub const GUILD_ONLY: GuildScheduledEventPrivacyLevel =
+        GuildScheduledEventPrivacyLevel(2);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GuildScheduledEventEntityType(pub u8);
+
+impl GuildScheduledEventEntityType {
+    /// The event is hosted in a stage channel.
+    pub const STAGE_INSTANCE: GuildScheduledEventEntityType =
+        GuildScheduledEventEntityType(1);
+    /// The event is hosted in a voice channel.
+    pub const VOICE: GuildScheduledEventEntityType =
+        GuildScheduledEventEntityType(2);
+    /// The event is hosted somewhere else, given by its location.
+    pub const EXTERNAL: GuildScheduledEventEntityType =
+        GuildScheduledEventEntityType(3);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GuildScheduledEventStatus(pub u8);
+
+impl GuildScheduledEventStatus {
+    pub const SCHEDULED: GuildScheduledEventStatus =
+        GuildScheduledEventStatus(1);
+    pub const ACTIVE: GuildScheduledEventStatus = GuildScheduledEventStatus(2);
+    pub const COMPLETED: GuildScheduledEventStatus =
+        GuildScheduledEventStatus(3);
+    pub const CANCELED: GuildScheduledEventStatus =
+        GuildScheduledEventStatus(4);
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GuildScheduledEventEntityMetadata {
+    /// The location of the event (1-100 characters). This is required for
+    /// external events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GetGuildScheduledEventsQuery {
+    /// Include the number of users subscribed to each event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_user_count: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateGuildScheduledEvent {
+    /// The channel ID of the scheduled event. This must be omitted for
+    /// external events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Snowflake>,
+    /// The entity metadata of the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<GuildScheduledEventEntityMetadata>,
+    /// The name of the scheduled event (1-100 characters).
+    pub name: String,
+    /// The privacy level of the scheduled event.
+    pub privacy_level: GuildScheduledEventPrivacyLevel,
+    /// The time to schedule the event.
+    pub scheduled_start_time: Timestamp,
+    /// The time when the scheduled event is scheduled to end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_time: Option<Timestamp>,
+    /// The description of the scheduled event (1-1000 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The entity type of the scheduled event.
+    pub entity_type: GuildScheduledEventEntityType,
+}
+
+impl CreateGuildScheduledEvent {
+    /// Creates a builder for a guild-only scheduled event.
+    pub fn builder(
+        name: impl Into<String>,
+        scheduled_start_time: Timestamp,
+    ) -> CreateGuildScheduledEventBuilder {
+        CreateGuildScheduledEventBuilder {
+            event: CreateGuildScheduledEvent {
+                channel_id: None,
+                entity_metadata: None,
+                name: name.into(),
+                privacy_level: GuildScheduledEventPrivacyLevel::GUILD_ONLY,
+                scheduled_start_time,
+                scheduled_end_time: None,
+                description: None,
+                entity_type: GuildScheduledEventEntityType::EXTERNAL,
+            },
+        }
+    }
+
+    /// Checks that Discord will accept this scheduled event. External
+    /// events need a location, and other events need a channel.
+    pub fn validate(&self) -> Result<(), GuildScheduledEventError> {
+        if self.entity_type == GuildScheduledEventEntityType::EXTERNAL {
+            validate_location(self.entity_metadata.as_ref())?;
+            if self.channel_id.is_some() {
+                return Err(GuildScheduledEventError::UnexpectedChannel);
+            }
+        } else if self.channel_id.is_none() {
+            return Err(GuildScheduledEventError::MissingChannel);
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for a [`CreateGuildScheduledEvent`]. Events are external unless a
+/// channel is set.
+#[derive(Clone, Debug)]
+pub struct CreateGuildScheduledEventBuilder {
+    event: CreateGuildScheduledEvent,
+}
+
+impl CreateGuildScheduledEventBuilder {
+    /// Sets the description of the event (1-1000 characters).
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.event.description = Some(description.into());
+        self
+    }
+
+    /// Sets the time the event is scheduled to end.
+    pub fn scheduled_end_time(mut self, scheduled_end_time: Timestamp) -> Self {
+        self.event.scheduled_end_time = Some(scheduled_end_time);
+        self
+    }
+
+    /// Hosts the event outside of Discord at the given location.
+    pub fn external(mut self, location: impl Into<String>) -> Self {
+        self.event.entity_type = GuildScheduledEventEntityType::EXTERNAL;
+        self.event.channel_id = None;
+        self.event.entity_metadata = Some(GuildScheduledEventEntityMetadata {
+            location: Some(location.into()),
+        });
+        self
+    }
+
+    /// Hosts the event in a voice channel.
+    pub fn voice(mut self, channel_id: Snowflake) -> Self {
+        self.event.entity_type = GuildScheduledEventEntityType::VOICE;
+        self.event.channel_id = Some(channel_id);
+        self.event.entity_metadata = None;
+        self
+    }
+
+    /// Hosts the event in a stage channel.
+    pub fn stage_instance(mut self, channel_id: Snowflake) -> Self {
+        self.event.entity_type = GuildScheduledEventEntityType::STAGE_INSTANCE;
+        self.event.channel_id = Some(channel_id);
+        self.event.entity_metadata = None;
+        self
+    }
+
+    pub fn build(self) -> CreateGuildScheduledEvent {
+        self.event
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModifyGuildScheduledEvent {
+    /// The channel ID of the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Snowflake>,
+    /// The entity metadata of the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<GuildScheduledEventEntityMetadata>,
+    /// The name of the scheduled event (1-100 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The privacy level of the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy_level: Option<GuildScheduledEventPrivacyLevel>,
+    /// The time to schedule the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_start_time: Option<Timestamp>,
+    /// The time when the scheduled event is scheduled to end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_time: Option<Timestamp>,
+    /// The description of the scheduled event (1-1000 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The entity type of the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<GuildScheduledEventEntityType>,
+    /// The status of the scheduled event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<GuildScheduledEventStatus>,
+}
+
+impl ModifyGuildScheduledEvent {
+    /// Checks that Discord will accept these changes.
+    pub fn validate(&self) -> Result<(), GuildScheduledEventError> {
+        if self.entity_type == Some(GuildScheduledEventEntityType::EXTERNAL) {
+            validate_location(self.entity_metadata.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// External events must have a non-empty location.
+fn validate_location(
+    entity_metadata: Option<&GuildScheduledEventEntityMetadata>,
+) -> Result<(), GuildScheduledEventError> {
+    let location = entity_metadata
+        .and_then(|metadata| metadata.location.as_deref())
+        .unwrap_or_default();
+    if location.is_empty() {
+        Err(GuildScheduledEventError::MissingLocation)
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Display, Error)]
+#[non_exhaustive]
+pub enum GuildScheduledEventError {
+    #[display(fmt = "external events require a location")]
+    MissingLocation,
+    #[display(fmt = "external events can't have a channel")]
+    UnexpectedChannel,
+    #[display(fmt = "stage and voice events require a channel")]
+    MissingChannel,
+}
+
+impl From<GuildScheduledEventError> for RequestError {
+    fn from(error: GuildScheduledEventError) -> Self {
+        RequestError::Custom(error.into())
+    }
+}
@@ -6,8 +6,10 @@ use crate::{
     serde_inner_enum,
 };
 use bitflags::bitflags;
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use wfbp_http::RequestError;
 
 serde_inner_enum! {
     #[derive(Clone, Debug)]
@@ -31,6 +33,109 @@ serde_inner_enum! {
             [?] max_values: Option<u8>,
             [?] disabled: Option<bool>,
         },
+        /// Only valid in modals.
+        TextInput = 4 {
+            custom_id: String,
+            style: TextInputStyle,
+            label: String,
+            [?] min_length: Option<u16>,
+            [?] max_length: Option<u16>,
+            [?] required: Option<bool>,
+            [?] value: Option<String>,
+            [?] placeholder: Option<String>,
+        },
+    }
+}
+
+impl Component {
+    /// The most action rows a message can have.
+    pub const MAX_ACTION_ROWS: usize = 5;
+    /// The most buttons an action row can have.
+    pub const MAX_BUTTONS_PER_ROW: usize = 5;
+}
+
+/// Checks that Discord will accept a message's components. Messages can have
+/// up to 5 action rows, and each row must have either up to 5 buttons or a
+/// single select menu. Text inputs can only be used in modals.
+pub fn validate_components(
+    components: &[Component],
+) -> Result<(), ComponentLayoutError> {
+    if components.len() > Component::MAX_ACTION_ROWS {
+        return Err(ComponentLayoutError::TooManyRows(components.len()));
+    }
+
+    for (row_index, row) in components.iter().enumerate() {
+        let children = match row {
+            Component::ActionRow { components } => components,
+            _ => return Err(ComponentLayoutError::NotInActionRow(row_index)),
+        };
+        if children.is_empty() {
+            return Err(ComponentLayoutError::EmptyRow(row_index));
+        }
+
+        let mut buttons = 0;
+        let mut select_menus = 0;
+        for child in children {
+            match child {
+                Component::ActionRow { .. } => {
+                    return Err(ComponentLayoutError::NestedActionRow(
+                        row_index,
+                    ))
+                }
+                Component::Button { .. } => buttons += 1,
+                Component::SelectMenu { .. } => select_menus += 1,
+                Component::TextInput { .. } => {
+                    return Err(ComponentLayoutError::TextInputInMessage(
+                        row_index,
+                    ))
+                }
+            }
+        }
+
+        if buttons > 0 && select_menus > 0 {
+            return Err(ComponentLayoutError::MixedRow(row_index));
+        }
+        if buttons > Component::MAX_BUTTONS_PER_ROW {
+            return Err(ComponentLayoutError::TooManyButtons(row_index));
+        }
+        if select_menus > 1 {
+            return Err(ComponentLayoutError::TooManySelectMenus(row_index));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, Display, Error)]
+#[non_exhaustive]
+pub enum ComponentLayoutError {
+    #[display(fmt = "a message can have at most 5 action rows, found {}", _0)]
+    TooManyRows(#[error(ignore)] usize),
+    #[display(fmt = "component {} must be an action row", _0)]
+    NotInActionRow(#[error(ignore)] usize),
+    #[display(fmt = "action row {} must have at least 1 component", _0)]
+    EmptyRow(#[error(ignore)] usize),
+    #[display(fmt = "action row {} can't contain another action row", _0)]
+    NestedActionRow(#[error(ignore)] usize),
+    #[display(
+        fmt = "action row {} can't contain a text input outside a modal",
+        _0
+    )]
+    TextInputInMessage(#[error(ignore)] usize),
+    #[display(fmt = "action row {} can have at most 5 buttons", _0)]
+    TooManyButtons(#[error(ignore)] usize),
+    #[display(fmt = "action row {} can have at most 1 select menu", _0)]
+    TooManySelectMenus(#[error(ignore)] usize),
+    #[display(
+        fmt = "action row {} can't contain both buttons and a select menu",
+        _0
+    )]
+    MixedRow(#[error(ignore)] usize),
+}
+
+impl From<ComponentLayoutError> for RequestError {
+    fn from(error: ComponentLayoutError) -> Self {
+        RequestError::Custom(error.into())
     }
 }
 
@@ -46,6 +151,17 @@ impl ButtonStyle {
     pub const LINK: ButtonStyle = ButtonStyle(5);
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TextInputStyle(pub u8);
+
+impl TextInputStyle {
+    /// A single-line input.
+    pub const SHORT: TextInputStyle = TextInputStyle(1);
+    /// A multi-line input.
+    pub const PARAGRAPH: TextInputStyle = TextInputStyle(2);
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SelectOption {
     pub label: String,
@@ -58,6 +174,34 @@ pub struct SelectOption {
     pub default: Option<bool>,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InteractionContextType(pub u8);
+
+impl InteractionContextType {
+    /// Interaction can be used within servers.
+    pub const GUILD: InteractionContextType = InteractionContextType(0);
+    /// Interaction can be used within DMs with the app's bot user.
+    pub const BOT_DM: InteractionContextType = InteractionContextType(1);
+    /// Interaction can be used within Group DMs and DMs other than the app's
+    /// bot user.
+    pub const PRIVATE_CHANNEL: InteractionContextType =
+        InteractionContextType(2);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApplicationIntegrationType(pub u8);
+
+impl ApplicationIntegrationType {
+    /// App is installable to servers.
+    pub const GUILD_INSTALL: ApplicationIntegrationType =
+        ApplicationIntegrationType(0);
+    /// App is installable to users.
+    pub const USER_INSTALL: ApplicationIntegrationType =
+        ApplicationIntegrationType(1);
+}
+
 serde_inner_enum! {
     #[derive(Clone, Debug)]
     pub enum ApplicationCommand = "type" {
@@ -77,6 +221,12 @@ serde_inner_enum! {
             /// Whether the command is enabled by default when the app is added to a
             /// guild (default `true`).
             [?] default_permission: Option<bool>,
+            /// Interaction contexts where the command can be used. Only for
+            /// global commands.
+            [?] contexts: Option<Vec<InteractionContextType>>,
+            /// Installation contexts where the command is available. Only for
+            /// global commands.
+            [?] integration_types: Option<Vec<ApplicationIntegrationType>>,
         },
         User = 2 {
             /// Unique id of the command.
@@ -92,6 +242,12 @@ serde_inner_enum! {
             /// Whether the command is enabled by default when the app is added to a
             /// guild (default `true`).
             [?] default_permission: Option<bool>,
+            /// Interaction contexts where the command can be used. Only for
+            /// global commands.
+            [?] contexts: Option<Vec<InteractionContextType>>,
+            /// Installation contexts where the command is available. Only for
+            /// global commands.
+            [?] integration_types: Option<Vec<ApplicationIntegrationType>>,
         },
         Message = 3 {
             /// Unique id of the command.
@@ -107,6 +263,12 @@ serde_inner_enum! {
             /// Whether the command is enabled by default when the app is added to a
             /// guild (default `true`).
             [?] default_permission: Option<bool>,
+            /// Interaction contexts where the command can be used. Only for
+            /// global commands.
+            [?] contexts: Option<Vec<InteractionContextType>>,
+            /// Installation contexts where the command is available. Only for
+            /// global commands.
+            [?] integration_types: Option<Vec<ApplicationIntegrationType>>,
         },
     }
 }
@@ -185,6 +347,39 @@ pub struct ApplicationCommandOptionChoice<T> {
     pub value: T,
 }
 
+/// The value of a choice, which depends on the type of the option.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ApplicationCommandOptionValue {
+    String(String),
+    Integer(i64),
+    Number(f64),
+}
+
+impl From<String> for ApplicationCommandOptionValue {
+    fn from(value: String) -> Self {
+        ApplicationCommandOptionValue::String(value)
+    }
+}
+
+impl From<&str> for ApplicationCommandOptionValue {
+    fn from(value: &str) -> Self {
+        ApplicationCommandOptionValue::String(value.to_owned())
+    }
+}
+
+impl From<i64> for ApplicationCommandOptionValue {
+    fn from(value: i64) -> Self {
+        ApplicationCommandOptionValue::Integer(value)
+    }
+}
+
+impl From<f64> for ApplicationCommandOptionValue {
+    fn from(value: f64) -> Self {
+        ApplicationCommandOptionValue::Number(value)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GuildApplicationCommandPermissions {
     /// The id of the command.
@@ -199,15 +394,58 @@ pub struct GuildApplicationCommandPermissions {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ApplicationCommandPermission {
-    /// The id of the role or user.
+    /// The id of the role, user, or channel. Can also be a permission
+    /// constant (see [`ApplicationCommandPermission::everyone`] and
+    /// [`ApplicationCommandPermission::all_channels`]).
     pub id: Snowflake,
-    /// Role or user.
+    /// Role, user, or channel.
     #[serde(rename = "type")]
     pub kind: ApplicationCommandPermissionType,
     /// `true` to allow, `false` to disallow.
     pub permission: bool,
 }
 
+impl ApplicationCommandPermission {
+    /// Allows or disallows a role.
+    pub fn role(role_id: Snowflake, permission: bool) -> Self {
+        ApplicationCommandPermission {
+            id: role_id,
+            kind: ApplicationCommandPermissionType::ROLE,
+            permission,
+        }
+    }
+
+    /// Allows or disallows a user.
+    pub fn user(user_id: Snowflake, permission: bool) -> Self {
+        ApplicationCommandPermission {
+            id: user_id,
+            kind: ApplicationCommandPermissionType::USER,
+            permission,
+        }
+    }
+
+    /// Allows or disallows using the command in a channel.
+    pub fn channel(channel_id: Snowflake, permission: bool) -> Self {
+        ApplicationCommandPermission {
+            id: channel_id,
+            kind: ApplicationCommandPermissionType::CHANNEL,
+            permission,
+        }
+    }
+
+    /// Allows or disallows all members in a guild (the `@everyone` role, whose
+    /// id is the guild's id).
+    pub fn everyone(guild_id: Snowflake, permission: bool) -> Self {
+        Self::role(guild_id, permission)
+    }
+
+    /// Allows or disallows using the command in all channels in a guild
+    /// (`guild_id - 1`).
+    pub fn all_channels(guild_id: Snowflake, permission: bool) -> Self {
+        Self::channel(Snowflake::new(guild_id.to_u64() - 1), permission)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ApplicationCommandPermissionType(pub u8);
@@ -217,6 +455,8 @@ impl ApplicationCommandPermissionType {
         ApplicationCommandPermissionType(1);
     pub const USER: ApplicationCommandPermissionType =
         ApplicationCommandPermissionType(2);
+    pub const CHANNEL: ApplicationCommandPermissionType =
+        ApplicationCommandPermissionType(3);
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -248,6 +488,10 @@ serde_inner_enum! {
             [?] member: Option<GuildMember>,
             /// User object for the invoking user, if invoked in a DM.
             [?] user: Option<User>,
+            /// The selected language of the invoking user.
+            [?] locale: Option<String>,
+            /// The guild's preferred locale, if invoked in a guild.
+            [?] guild_locale: Option<String>,
         },
         MessageComponent = 3 {
             /// The guild it was sent from.
@@ -317,6 +561,8 @@ serde_inner_enum! {
             [?] resolved: Option<ApplicationCommandInteractionDataResolved>,
             /// The params + values from the user.
             [?] options: Option<Vec<ApplicationCommandInteractionDataOption>>,
+            /// The values of the options the user selected.
+            [?] values: Option<Vec<String>>,
         }
     }
 }
@@ -365,6 +611,55 @@ serde_inner_enum! {
     }
 }
 
+impl ApplicationCommandInteractionDataOptionType {
+    /// The name of the option's type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SubCommand { .. } => "SubCommand",
+            Self::SubCommandGroup { .. } => "SubCommandGroup",
+            Self::String { .. } => "String",
+            Self::Integer { .. } => "Integer",
+            Self::Boolean { .. } => "Boolean",
+            Self::User { .. } => "User",
+            Self::Channel { .. } => "Channel",
+            Self::Role { .. } => "Role",
+            Self::Mentionable { .. } => "Mentionable",
+            Self::Number { .. } => "Number",
+        }
+    }
+}
+
+impl ApplicationCommandInteractionData {
+    /// The name of the invoked command followed by the names of the invoked
+    /// subcommand group and subcommand, if any.
+    pub fn command_path(&self) -> Vec<&str> {
+        match self {
+            Self::ChatInput { name, options, .. } => {
+                let mut path = vec![name.as_str()];
+                let mut options = options.as_deref().unwrap_or_default();
+                while let Some(option) = options.first() {
+                    match &option.kind {
+                        ApplicationCommandInteractionDataOptionType::SubCommand {
+                            options: inner,
+                        }
+                        | ApplicationCommandInteractionDataOptionType::SubCommandGroup {
+                            options: inner,
+                        } => {
+                            path.push(option.name.as_str());
+                            options = inner.as_deref().unwrap_or_default();
+                        }
+                        _ => break,
+                    }
+                }
+                path
+            }
+            Self::User { name, .. } | Self::Message { name, .. } => {
+                vec![name.as_str()]
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ApplicationCommandInteractionDataResolved {
     /// The IDs and [User] objects.
@@ -399,9 +694,81 @@ serde_inner_enum! {
         UpdateMessage = 7 {
             data: InteractionApplicationCommandCallbackData,
         },
+        /// Only valid for autocomplete interactions.
+        ApplicationCommandAutocompleteResult = 8 {
+            data: InteractionAutocompleteCallbackData,
+        },
+        /// Not valid for modal submit and ping interactions.
+        Modal = 9 {
+            data: InteractionModalCallbackData,
+        },
+    }
+}
+
+impl InteractionResponse {
+    /// Responds to an interaction with a message.
+    pub fn channel_message(
+        data: InteractionApplicationCommandCallbackData,
+    ) -> Self {
+        InteractionResponse::ChannelMessageWithSource { data }
+    }
+
+    /// Responds to a component interaction by editing the message the
+    /// component is attached to.
+    pub fn update_message(
+        data: InteractionApplicationCommandCallbackData,
+    ) -> Self {
+        InteractionResponse::UpdateMessage { data }
+    }
+
+    /// Responds to an interaction by opening a form.
+    pub fn modal(
+        custom_id: impl Into<String>,
+        title: impl Into<String>,
+        components: impl IntoIterator<Item = Component>,
+    ) -> Self {
+        InteractionResponse::Modal {
+            data: InteractionModalCallbackData {
+                custom_id: custom_id.into(),
+                title: title.into(),
+                components: components.into_iter().collect(),
+            },
+        }
+    }
+
+    /// Responds to an autocomplete interaction with suggested choices.
+    pub fn autocomplete(
+        choices: impl IntoIterator<
+            Item = ApplicationCommandOptionChoice<
+                ApplicationCommandOptionValue,
+            >,
+        >,
+    ) -> Self {
+        InteractionResponse::ApplicationCommandAutocompleteResult {
+            data: InteractionAutocompleteCallbackData {
+                choices: choices.into_iter().collect(),
+            },
+        }
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InteractionModalCallbackData {
+    /// A developer-defined identifier for the modal, max 100 characters.
+    pub custom_id: String,
+    /// The title of the popup modal, max 45 characters.
+    pub title: String,
+    /// Between 1 and 5 (inclusive) action rows containing text inputs.
+    pub components: Vec<Component>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct InteractionAutocompleteCallbackData {
+    /// Autocomplete choices (max of 25 choices).
+    pub choices:
+        Vec<ApplicationCommandOptionChoice<ApplicationCommandOptionValue>>,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct InteractionApplicationCommandCallbackData {
     /// Is the response TTS?
@@ -430,6 +797,8 @@ bitflags! {
     pub struct InteractionResponseDataFlags: u32 {
         /// Only the user receiving the message can see it.
         const EPHEMERAL = 1 << 6;
+        /// The message will not trigger push or desktop notifications.
+        const SUPPRESS_NOTIFICATIONS = 1 << 12;
     }
 }
 
@@ -446,6 +815,12 @@ serde_inner_enum! {
             /// Whether the command is enabled by default when the app is added to a
             /// guild (default `true`).
             [?] default_permission: Option<bool>,
+            /// Interaction contexts where the command can be used. Only for
+            /// global commands.
+            [?] contexts: Option<Vec<InteractionContextType>>,
+            /// Installation contexts where the command is available. Only for
+            /// global commands.
+            [?] integration_types: Option<Vec<ApplicationIntegrationType>>,
         },
         User = 2 {
             /// 1-32 lowercase character name matching `^[\w-]{1,32}$`.
@@ -453,6 +828,12 @@ serde_inner_enum! {
             /// Whether the command is enabled by default when the app is added to a
             /// guild (default `true`).
             [?] default_permission: Option<bool>,
+            /// Interaction contexts where the command can be used. Only for
+            /// global commands.
+            [?] contexts: Option<Vec<InteractionContextType>>,
+            /// Installation contexts where the command is available. Only for
+            /// global commands.
+            [?] integration_types: Option<Vec<ApplicationIntegrationType>>,
         },
         Message = 3 {
             /// 1-32 lowercase character name matching `^[\w-]{1,32}$`.
@@ -460,6 +841,12 @@ serde_inner_enum! {
             /// Whether the command is enabled by default when the app is added to a
             /// guild (default `true`).
             [?] default_permission: Option<bool>,
+            /// Interaction contexts where the command can be used. Only for
+            /// global commands.
+            [?] contexts: Option<Vec<InteractionContextType>>,
+            /// Installation contexts where the command is available. Only for
+            /// global commands.
+            [?] integration_types: Option<Vec<ApplicationIntegrationType>>,
         },
     }
 }
@@ -470,6 +857,11 @@ pub struct CreateGuildApplicationCommandPermissions {
     pub permissions: Vec<ApplicationCommandPermission>,
 }
 
+impl CreateGuildApplicationCommandPermissions {
+    /// The most permission overwrites a command can have in a guild.
+    pub const MAX_PERMISSIONS: usize = 100;
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BatchEditGuildApplicationCommandPermissions {
     /// The id of the command.
@@ -0,0 +1,123 @@
+use crate::models::{
+    Application, ChannelType, GuildFeature, NsfwLevel, Snowflake, Timestamp,
+    User, VerificationLevel,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Invite {
+    /// The invite code (unique ID).
+    pub code: String,
+    /// The guild this invite is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild: Option<InviteGuild>,
+    /// The channel this invite is for.
+    pub channel: Option<InviteChannel>,
+    /// The user who created the invite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inviter: Option<User>,
+    /// The type of target for this voice channel invite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<InviteTargetType>,
+    /// The user whose stream to display for this voice channel stream invite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_user: Option<User>,
+    /// The embedded application to open for this voice channel embedded
+    /// application invite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_application: Option<Application>,
+    /// Approximate count of online members, returned when `with_counts` is
+    /// `true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approximate_presence_count: Option<u32>,
+    /// Approximate count of total members, returned when `with_counts` is
+    /// `true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approximate_member_count: Option<u32>,
+    /// The expiration date of this invite, returned when `with_expiration` is
+    /// `true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Timestamp>,
+}
+
+/// The partial guild object included with an invite.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InviteGuild {
+    pub id: Snowflake,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub splash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub features: Vec<GuildFeature>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_level: Option<VerificationLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vanity_url_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nsfw_level: Option<NsfwLevel>,
+}
+
+/// The partial channel object included with an invite.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InviteChannel {
+    pub id: Snowflake,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InviteTargetType(pub u8);
+
+impl InviteTargetType {
+    pub const STREAM: InviteTargetType = InviteTargetType(1);
+    pub const EMBEDDED_APPLICATION: InviteTargetType = InviteTargetType(2);
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GetInviteQuery {
+    /// Whether the invite should contain approximate member counts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_counts: Option<bool>,
+    /// Whether the invite should contain the expiration date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_expiration: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CreateChannelInvite {
+    /// Duration of invite in seconds before expiry, or 0 for never. Between 0
+    /// and 604800 (7 days), defaults to 86400 (24 hours).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u32>,
+    /// Max number of uses or 0 for unlimited. Between 0 and 100, defaults to
+    /// 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u8>,
+    /// Whether this invite only grants temporary membership.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temporary: Option<bool>,
+    /// If true, don't try to reuse a similar invite (useful for creating many
+    /// unique one time use invites).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique: Option<bool>,
+    /// The type of target for this voice channel invite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<InviteTargetType>,
+    /// The ID of the user whose stream to display for this invite, required
+    /// if `target_type` is [STREAM](InviteTargetType::STREAM).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_user_id: Option<Snowflake>,
+    /// The ID of the embedded application to open for this invite, required
+    /// if `target_type` is
+    /// [EMBEDDED_APPLICATION](InviteTargetType::EMBEDDED_APPLICATION).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_application_id: Option<Snowflake>,
+}
@@ -0,0 +1,5 @@
+/// An object which can be mentioned in a message.
+pub trait Mention {
+    /// Formats a mention of this object.
+    fn mention(&self) -> String;
+}
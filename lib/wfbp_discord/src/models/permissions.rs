@@ -1,4 +1,4 @@
-use crate::models::Snowflake;
+use crate::models::{Color, Mention, Overwrite, OverwriteType, Snowflake};
 use bitflags::bitflags;
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Formatter;
@@ -88,12 +88,78 @@ impl<'de> Deserialize<'de> for Permissions {
     }
 }
 
+impl Permissions {
+    /// Computes a member's permissions in a channel from their roles and the
+    /// channel's permission overwrites, following Discord's permission
+    /// hierarchy. The `@everyone` role has the same ID as the guild.
+    ///
+    /// This doesn't account for the guild owner, who always has all
+    /// permissions.
+    pub fn compute(
+        guild_id: Snowflake,
+        user_id: Snowflake,
+        member_roles: &[Snowflake],
+        roles: &[Role],
+        overwrites: &[Overwrite],
+    ) -> Permissions {
+        // Base permissions from roles
+        let permissions = roles
+            .iter()
+            .filter(|role| {
+                role.id == guild_id || member_roles.contains(&role.id)
+            })
+            .fold(Permissions::empty(), |permissions, role| {
+                permissions | role.permissions
+            });
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+
+        // @everyone overwrite
+        let mut permissions = permissions;
+        if let Some(overwrite) =
+            overwrites.iter().find(|overwrite| overwrite.id == guild_id)
+        {
+            permissions &= !overwrite.deny;
+            permissions |= overwrite.allow;
+        }
+
+        // Role overwrites
+        let (allow, deny) = overwrites
+            .iter()
+            .filter(|overwrite| {
+                overwrite.kind == OverwriteType::ROLE
+                    && member_roles.contains(&overwrite.id)
+            })
+            .fold(
+                (Permissions::empty(), Permissions::empty()),
+                |(allow, deny), overwrite| {
+                    (allow | overwrite.allow, deny | overwrite.deny)
+                },
+            );
+        permissions &= !deny;
+        permissions |= allow;
+
+        // Member overwrite
+        if let Some(overwrite) = overwrites.iter().find(|overwrite| {
+            overwrite.kind == OverwriteType::MEMBER && overwrite.id == user_id
+        }) {
+            permissions &= !overwrite.deny;
+            permissions |= overwrite.allow;
+        }
+
+        permissions
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Role {
     pub id: Snowflake,
     pub name: String,
-    pub color: u32,
+    /// The color of the role, or [`Color::DEFAULT`] if the role has no color.
+    pub color: Color,
     pub hoist: bool,
+    /// Position of this role. Roles with the same position are sorted by ID.
     pub position: u16,
     pub permissions: Permissions,
     pub managed: bool,
@@ -102,6 +168,19 @@ pub struct Role {
     pub tags: Option<RoleTags>,
 }
 
+impl Mention for Role {
+    fn mention(&self) -> String {
+        format!("<@&{}>", self.id)
+    }
+}
+
+impl Role {
+    /// Whether this role is managed by an integration.
+    pub fn is_managed(&self) -> bool {
+        self.managed
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RoleTags {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1,5 +1,6 @@
 use crate::models::{Snowflake, User};
 use serde::{Deserialize, Serialize};
+use wfbp_http::MultipartForm;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Sticker {
@@ -60,3 +61,54 @@ pub struct StickerPack {
     pub description: String,
     pub banner_asset_id: Snowflake,
 }
+
+#[derive(Clone, Debug)]
+pub struct CreateGuildSticker {
+    /// Name of the sticker (2-30 characters).
+    pub name: String,
+    /// Description of the sticker (empty or 2-100 characters).
+    pub description: String,
+    /// Autocomplete/suggestion tags for the sticker (max 200 characters).
+    pub tags: String,
+    /// The sticker file to upload, must be a PNG, APNG, or Lottie JSON file,
+    /// max 500 KB.
+    pub file: StickerFile,
+}
+
+#[derive(Clone, Debug)]
+pub struct StickerFile {
+    /// Name of the uploaded file.
+    pub file_name: String,
+    /// MIME type of the file.
+    pub content_type: String,
+    /// Contents of the file.
+    pub data: Vec<u8>,
+}
+
+impl From<&CreateGuildSticker> for MultipartForm {
+    fn from(sticker: &CreateGuildSticker) -> Self {
+        MultipartForm::new()
+            .text("name", sticker.name.clone())
+            .text("description", sticker.description.clone())
+            .text("tags", sticker.tags.clone())
+            .file(
+                "file",
+                sticker.file.file_name.clone(),
+                sticker.file.content_type.clone(),
+                sticker.file.data.clone(),
+            )
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModifyGuildSticker {
+    /// Name of the sticker (2-30 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Description of the sticker (2-100 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+    /// Autocomplete/suggestion tags for the sticker (max 200 characters).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+}
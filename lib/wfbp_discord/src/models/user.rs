@@ -1,4 +1,4 @@
-use crate::models::{Integration, Snowflake};
+use crate::models::{Integration, Mention, Snowflake};
 use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +28,19 @@ pub struct User {
     public_flags: Option<UserFlags>,
 }
 
+impl User {
+    /// The user's ID.
+    pub fn id(&self) -> Snowflake {
+        self.id
+    }
+}
+
+impl Mention for User {
+    fn mention(&self) -> String {
+        format!("<@{}>", self.id)
+    }
+}
+
 bitflags! {
     #[derive(Default, Serialize, Deserialize)]
     #[serde(transparent)]
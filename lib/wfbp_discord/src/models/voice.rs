@@ -36,15 +36,16 @@ pub struct VoiceState {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VoiceRegion {
     /// Unique ID for the region.
-    id: String,
+    pub id: String,
     /// Name of the region.
-    name: String,
-    /// True if this is a vip-only server.
-    vip: bool,
+    pub name: String,
+    /// True if this is a vip-only server. No longer sent by Discord.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vip: Option<bool>,
     /// True for a single server that is closest to the current user's client.
-    optimal: bool,
+    pub optimal: bool,
     /// Whether this is a deprecated voice region (avoid switching to these).
-    deprecated: bool,
+    pub deprecated: bool,
     /// Whether this is a custom voice region (used for events/etc).
-    custom: bool,
+    pub custom: bool,
 }
@@ -1,5 +1,7 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use reqwest::{Method, Response};
+use serde::Serialize;
+use std::time::Duration;
 use tokio::time::sleep;
 use tracing::warn;
 
@@ -24,6 +26,17 @@ impl RateLimitBucket {
     }
 }
 
+/// The state of a rate limit bucket.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub struct RemainingInfo {
+    /// The number of requests that can be made per window.
+    pub limit: u32,
+    /// The number of requests that can still be made in this window.
+    pub remaining: u32,
+    /// When the window resets.
+    pub reset: DateTime<Utc>,
+}
+
 #[derive(Clone, Debug)]
 pub struct RateLimiter {
     pub bucket: RateLimitBucket,
@@ -37,9 +50,35 @@ impl RateLimiter {
     pub const RATELIMIT_LIMIT: &'static str = "x-ratelimit-limit";
     pub const RATELIMIT_REMAINING: &'static str = "x-ratelimit-remaining";
     pub const RATELIMIT_RESET: &'static str = "x-ratelimit-reset";
+    pub const RATELIMIT_RESET_AFTER: &'static str = "x-ratelimit-reset-after";
     pub const RATELIMIT_BUCKET: &'static str = "x-ratelimit-bucket";
 
-    pub async fn wait(&mut self) {
+    /// The longest a request will wait for a bucket to reset.
+    pub const MAX_RESET_WAIT: Duration = Duration::from_secs(60);
+
+    /// Creates a rate limiter for a bucket that hasn't been used yet.
+    pub fn new(bucket: RateLimitBucket) -> Self {
+        RateLimiter {
+            bucket,
+            limit: 1,
+            remaining: 1,
+            reset: Utc::now(),
+        }
+    }
+
+    /// The current state of the bucket.
+    pub fn info(&self) -> RemainingInfo {
+        RemainingInfo {
+            limit: self.limit,
+            remaining: self.remaining,
+            reset: self.reset,
+        }
+    }
+
+    /// Waits until a request can be made, returning how long was waited. If
+    /// the bucket is exhausted, this waits until it resets.
+    pub async fn wait(&mut self) -> Duration {
+        let mut waited = Duration::ZERO;
         self.remaining = match self.remaining.checked_sub(1) {
             Some(remaining) => remaining,
             None => {
@@ -51,14 +90,18 @@ impl RateLimiter {
                             "pre-emptive rate limit hit for {}",
                             self.bucket.route
                         );
-                        sleep(delay).await
+                        let delay = delay.min(Self::MAX_RESET_WAIT);
+                        sleep(delay).await;
+                        waited = delay;
                     }
                     _ => {}
                 }
 
-                self.limit
+                self.limit.saturating_sub(1)
             }
-        }
+        };
+
+        waited
     }
 
     pub fn update(&mut self, response: &Response) {
@@ -72,6 +115,14 @@ impl RateLimiter {
             .get(Self::RATELIMIT_REMAINING)
             .and_then(|v| v.to_str().ok())
             .and_then(|v| v.trim().parse().ok());
+        let reset_after = response
+            .headers()
+            .get(Self::RATELIMIT_RESET_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+            .and_then(|t: f64| Duration::try_from_secs_f64(t).ok())
+            .and_then(|t| chrono::Duration::from_std(t).ok())
+            .and_then(|t| Utc::now().checked_add_signed(t));
         let reset = response
             .headers()
             .get(Self::RATELIMIT_RESET)
@@ -84,6 +135,6 @@ impl RateLimiter {
 
         self.limit = limit.unwrap_or(self.limit);
         self.remaining = remaining.unwrap_or(self.remaining);
-        self.reset = reset.unwrap_or(self.reset);
+        self.reset = reset_after.or(reset).unwrap_or(self.reset);
     }
 }
@@ -1,26 +1,47 @@
 use crate::{
     middleware::ClientSecret,
     models::{
-        ApplicationCommand, BatchEditGuildApplicationCommandPermissions,
-        Channel, ClientCredentials, ClientCredentialsRequest,
-        CreateApplicationCommand, CreateGuildApplicationCommandPermissions,
+        validate_components, validate_message_content, ActiveThreads,
+        AllowedMentions, Application, ApplicationCommand, AuditLog,
+        BatchEditGuildApplicationCommandPermissions, Channel,
+        ClientCredentials, ClientCredentialsRequest, Component,
+        CreateApplicationCommand,
+        CreateChannelInvite as CreateChannelInviteModel,
+        CreateGuildApplicationCommandPermissions,
+        CreateGuildChannel as CreateGuildChannelModel,
+        CreateGuildEmoji as CreateGuildEmojiModel, CreateGuildScheduledEvent,
+        CreateGuildSticker as CreateGuildStickerModel,
         CreateMessage as CreateMessageModel, CreateWebhookMessage,
-        EditWebhookMessage, GuildApplicationCommandPermissions,
-        InteractionResponse, Message, Snowflake,
+        CreateWebhookMessageWithFiles,
+        EditChannelPermissions as EditChannelPermissionsModel,
+        EditCurrentApplication as EditCurrentApplicationModel,
+        EditWebhookMessage, Embed, Emoji, Gateway, GatewayBot,
+        GetGuildAuditLogQuery, GetGuildScheduledEventsQuery, GetInviteQuery,
+        GuildApplicationCommandPermissions, GuildMember, GuildScheduledEvent,
+        InteractionResponse, Invite, Message,
+        ModifyCurrentMember as ModifyCurrentMemberModel,
+        ModifyGuildChannelPosition, ModifyGuildEmoji as ModifyGuildEmojiModel,
+        ModifyGuildMember as ModifyGuildMemberModel, ModifyGuildScheduledEvent,
+        ModifyGuildSticker as ModifyGuildStickerModel, Role, Snowflake,
+        StartThreadFromMessage as StartThreadFromMessageModel,
+        StartThreadWithoutMessage as StartThreadWithoutMessageModel, Sticker,
+        ThreadMember, VoiceRegion,
     },
     rate_limit::RateLimitBucket,
 };
+use anyhow::anyhow;
 use reqwest::Method;
 use std::{
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
     sync::Arc,
+    time::Duration,
 };
-use wfbp_http::routes;
+use wfbp_http::{routes, RequestError};
 
 #[derive(Clone, Debug)]
 pub struct DiscordRouteInfo {
-    pub needs_auth: bool,
+    pub auth: RouteAuth,
     pub bucket: RateLimitBucket,
 }
 
@@ -31,7 +52,7 @@ impl DiscordRouteInfo {
         major_params: [u64; 2],
     ) -> Self {
         DiscordRouteInfo {
-            needs_auth: false,
+            auth: RouteAuth::None,
             bucket: RateLimitBucket::new(method, route, major_params),
         }
     }
@@ -42,10 +63,34 @@ impl DiscordRouteInfo {
         major_params: [u64; 2],
     ) -> Self {
         DiscordRouteInfo {
-            needs_auth: true,
+            auth: RouteAuth::Bearer,
             bucket: RateLimitBucket::new(method, route, major_params),
         }
     }
+
+    /// Creates route info for a route which only accepts a bot token.
+    pub fn with_bot_auth(
+        method: Method,
+        route: &'static str,
+        major_params: [u64; 2],
+    ) -> Self {
+        DiscordRouteInfo {
+            auth: RouteAuth::Bot,
+            bucket: RateLimitBucket::new(method, route, major_params),
+        }
+    }
+}
+
+/// How requests to a route are authenticated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RouteAuth {
+    /// The request is sent without credentials.
+    None,
+    /// The request uses the OAuth2 access token from the client credentials
+    /// grant.
+    Bearer,
+    /// The request uses the bot's token.
+    Bot,
 }
 
 fn hash_str(s: &str) -> u64 {
@@ -54,93 +99,800 @@ fn hash_str(s: &str) -> u64 {
     hasher.finish()
 }
 
+/// Checks that Discord will accept a message's content, embeds, components,
+/// and allowed mentions.
+fn validate_message(
+    content: Option<&str>,
+    embeds: Option<&[Embed]>,
+    components: Option<&[Component]>,
+    allowed_mentions: Option<&AllowedMentions>,
+) -> Result<(), RequestError> {
+    validate_message_content(content, embeds.unwrap_or_default())?;
+    if let Some(components) = components {
+        validate_components(components)?;
+    }
+    if let Some(allowed_mentions) = allowed_mentions {
+        allowed_mentions.validate()?;
+    }
+
+    Ok(())
+}
+
 routes! {
     (
-        GetChannel {
-            channel_id: Snowflake,
+        GetChannel {
+            channel_id: Snowflake,
+        },
+        method = GET "/channels/{channel_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0]
+            )
+        },
+        response = [json] Channel,
+    ),
+    (
+        ModifyChannel {
+            channel_id: Snowflake,
+        },
+        method = PATCH "/channels/{channel_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Channel,
+    ),
+    (
+        DeleteChannel {
+            channel_id: Snowflake,
+        },
+        method = DELETE "/channels/{channel_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Channel,
+    ),
+    (
+        GetChannelMessages {
+            channel_id: Snowflake,
+        },
+        method = GET "/channels/{channel_id}/messages",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Vec<Message>,
+    ),
+    (
+        GetChannelMessage {
+            channel_id: Snowflake,
+            message_id: Snowflake,
+        },
+        method = GET "/channels/{channel_id}/messages/{message_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Message,
+    ),
+    (
+        CreateMessage {
+            channel_id: Snowflake,
+            message: CreateMessageModel,
+        },
+        body = [json] message,
+        method = POST "/channels/{channel_id}/messages",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        validate = |route| {
+            validate_message(
+                route.message.content.as_deref(),
+                route.message.embeds.as_deref(),
+                route.message.components.as_deref(),
+                route.message.allowed_mentions.as_ref(),
+            )
+        },
+        idempotent = |route| route.message.nonce.is_some(),
+        response = [json] Message,
+    ),
+    (
+        EditChannelPermissions {
+            channel_id: Snowflake,
+            overwrite_id: Snowflake,
+            permissions: EditChannelPermissionsModel,
+        },
+        body = [json] permissions,
+        method = PUT "/channels/{channel_id}/permissions/{overwrite_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        validate = |route| {
+            if route.permissions.kind.is_known() {
+                Ok(())
+            } else {
+                Err(RequestError::Custom(anyhow!(
+                    "unknown overwrite type: {}",
+                    route.permissions.kind.0
+                )))
+            }
+        },
+        response = [empty] (),
+    ),
+    (
+        DeleteChannelPermission {
+            channel_id: Snowflake,
+            overwrite_id: Snowflake,
+        },
+        method = DELETE "/channels/{channel_id}/permissions/{overwrite_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    (
+        CreateChannelInvite {
+            channel_id: Snowflake,
+            invite: CreateChannelInviteModel,
+        },
+        body = [json] invite,
+        method = POST "/channels/{channel_id}/invites",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Invite,
+    ),
+    (
+        GetPinnedMessages {
+            channel_id: Snowflake,
+        },
+        method = GET "/channels/{channel_id}/pins",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Vec<Message>,
+    ),
+    (
+        /// Pins a message in a channel.
+        ///
+        /// Discord limits each channel to 50 pinned messages. Pinning beyond
+        /// that limit fails with an error response, so callers that pin
+        /// regularly should unpin old messages first.
+        PinMessage {
+            channel_id: Snowflake,
+            message_id: Snowflake,
+        },
+        method = PUT "/channels/{channel_id}/pins/{message_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    (
+        UnpinMessage {
+            channel_id: Snowflake,
+            message_id: Snowflake,
+        },
+        method = DELETE "/channels/{channel_id}/pins/{message_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    // Threads
+    (
+        StartThreadFromMessage {
+            channel_id: Snowflake,
+            message_id: Snowflake,
+            thread: StartThreadFromMessageModel,
+        },
+        body = [json] thread,
+        method = POST "/channels/{channel_id}/messages/{message_id}/threads",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Channel,
+    ),
+    (
+        StartThreadWithoutMessage {
+            channel_id: Snowflake,
+            thread: StartThreadWithoutMessageModel,
+        },
+        body = [json] thread,
+        method = POST "/channels/{channel_id}/threads",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Channel,
+    ),
+    (
+        JoinThread {
+            channel_id: Snowflake,
+        },
+        method = PUT "/channels/{channel_id}/thread-members/@me",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    (
+        AddThreadMember {
+            channel_id: Snowflake,
+            user_id: Snowflake,
+        },
+        method = PUT "/channels/{channel_id}/thread-members/{user_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    (
+        LeaveThread {
+            channel_id: Snowflake,
+        },
+        method = DELETE "/channels/{channel_id}/thread-members/@me",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    (
+        RemoveThreadMember {
+            channel_id: Snowflake,
+            user_id: Snowflake,
+        },
+        method = DELETE "/channels/{channel_id}/thread-members/{user_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    (
+        GetThreadMember {
+            channel_id: Snowflake,
+            user_id: Snowflake,
+        },
+        method = GET "/channels/{channel_id}/thread-members/{user_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] ThreadMember,
+    ),
+    (
+        ListThreadMembers {
+            channel_id: Snowflake,
+        },
+        method = GET "/channels/{channel_id}/thread-members",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [channel_id.to_u64(), 0],
+            )
+        },
+        response = [json] Vec<ThreadMember>,
+    ),
+    // Guilds
+    (
+        GetGuildAuditLog {
+            guild_id: Snowflake,
+            query: GetGuildAuditLogQuery,
+        },
+        method = GET "/guilds/{guild_id}/audit-logs",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        query = query,
+        response = [json] AuditLog,
+    ),
+    (
+        GetGuildChannels {
+            guild_id: Snowflake,
+        },
+        method = GET "/guilds/{guild_id}/channels",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Vec<Channel>,
+    ),
+    (
+        CreateGuildChannel {
+            guild_id: Snowflake,
+            channel: CreateGuildChannelModel,
+        },
+        body = [json] channel,
+        method = POST "/guilds/{guild_id}/channels",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Channel,
+    ),
+    (
+        ModifyGuildChannelPositions {
+            guild_id: Snowflake,
+            positions: Vec<ModifyGuildChannelPosition>,
+        },
+        body = [json] positions,
+        method = PATCH "/guilds/{guild_id}/channels",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    (
+        GetGuildMember {
+            guild_id: Snowflake,
+            user_id: Snowflake,
+        },
+        method = GET "/guilds/{guild_id}/members/{user_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] GuildMember,
+    ),
+    (
+        ModifyGuildMember {
+            guild_id: Snowflake,
+            user_id: Snowflake,
+            member: ModifyGuildMemberModel,
+        },
+        body = [json] member,
+        method = PATCH "/guilds/{guild_id}/members/{user_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] GuildMember,
+    ),
+    (
+        ModifyCurrentMember {
+            guild_id: Snowflake,
+            member: ModifyCurrentMemberModel,
+        },
+        body = [json] member,
+        method = PATCH "/guilds/{guild_id}/members/@me",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] GuildMember,
+    ),
+    (
+        GetGuildRoles {
+            guild_id: Snowflake,
+        },
+        method = GET "/guilds/{guild_id}/roles",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Vec<Role>,
+    ),
+    (
+        ListActiveGuildThreads {
+            guild_id: Snowflake,
         },
-        method = GET "/channels/{channel_id}",
+        method = GET "/guilds/{guild_id}/threads/active",
         info = |method, route| -> DiscordRouteInfo {
             DiscordRouteInfo::with_auth(
                 method,
                 route,
-                [channel_id.to_u64(), 0]
+                [guild_id.to_u64(), 0],
             )
         },
-        response = [json] Channel,
+        response = [json] ActiveThreads,
     ),
     (
-        ModifyChannel {
-            channel_id: Snowflake,
+        GetGuildVoiceRegions {
+            guild_id: Snowflake,
         },
-        method = PATCH "/channels/{channel_id}",
+        method = GET "/guilds/{guild_id}/regions",
         info = |method, route| -> DiscordRouteInfo {
             DiscordRouteInfo::with_auth(
                 method,
                 route,
-                [channel_id.to_u64(), 0],
+                [guild_id.to_u64(), 0],
             )
         },
-        response = [json] Channel,
+        response = [json] Vec<VoiceRegion>,
     ),
+    // Scheduled events
     (
-        DeleteChannel {
-            channel_id: Snowflake,
+        ListScheduledEvents {
+            guild_id: Snowflake,
+            query: GetGuildScheduledEventsQuery,
         },
-        method = DELETE "/channels/{channel_id}",
+        method = GET "/guilds/{guild_id}/scheduled-events",
         info = |method, route| -> DiscordRouteInfo {
             DiscordRouteInfo::with_auth(
                 method,
                 route,
-                [channel_id.to_u64(), 0],
+                [guild_id.to_u64(), 0],
             )
         },
-        response = [json] Channel,
+        query = query,
+        response = [json] Vec<GuildScheduledEvent>,
     ),
     (
-        GetChannelMessages {
-            channel_id: Snowflake,
+        CreateScheduledEvent {
+            guild_id: Snowflake,
+            event: CreateGuildScheduledEvent,
         },
-        method = GET "/channels/{channel_id}/messages",
+        body = [json] event,
+        method = POST "/guilds/{guild_id}/scheduled-events",
         info = |method, route| -> DiscordRouteInfo {
             DiscordRouteInfo::with_auth(
                 method,
                 route,
-                [channel_id.to_u64(), 0],
+                [guild_id.to_u64(), 0],
             )
         },
-        response = [json] Vec<Message>,
+        validate = |route| route.event.validate().map_err(Into::into),
+        response = [json] GuildScheduledEvent,
     ),
     (
-        GetChannelMessage {
-            channel_id: Snowflake,
-            message_id: Snowflake,
+        GetScheduledEvent {
+            guild_id: Snowflake,
+            guild_scheduled_event_id: Snowflake,
+            query: GetGuildScheduledEventsQuery,
         },
-        method = GET "/channels/{channel_id}/messages/{message_id}",
+        method = GET "/guilds/{guild_id}/scheduled-events/{guild_scheduled_event_id}",
         info = |method, route| -> DiscordRouteInfo {
             DiscordRouteInfo::with_auth(
                 method,
                 route,
-                [channel_id.to_u64(), 0],
+                [guild_id.to_u64(), 0],
             )
         },
-        response = [json] Message,
+        query = query,
+        response = [json] GuildScheduledEvent,
     ),
     (
-        CreateMessage {
-            channel_id: Snowflake,
-            message: CreateMessageModel,
+        ModifyScheduledEvent {
+            guild_id: Snowflake,
+            guild_scheduled_event_id: Snowflake,
+            event: ModifyGuildScheduledEvent,
         },
-        body = [json] message,
-        method = POST "/channels/{channel_id}/messages",
+        body = [json] event,
+        method = PATCH "/guilds/{guild_id}/scheduled-events/{guild_scheduled_event_id}",
         info = |method, route| -> DiscordRouteInfo {
             DiscordRouteInfo::with_auth(
                 method,
                 route,
-                [channel_id.to_u64(), 0],
+                [guild_id.to_u64(), 0],
             )
         },
-        response = [json] Message,
+        validate = |route| route.event.validate().map_err(Into::into),
+        response = [json] GuildScheduledEvent,
+    ),
+    (
+        DeleteScheduledEvent {
+            guild_id: Snowflake,
+            guild_scheduled_event_id: Snowflake,
+        },
+        method = DELETE "/guilds/{guild_id}/scheduled-events/{guild_scheduled_event_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    // Emoji
+    (
+        ListGuildEmojis {
+            guild_id: Snowflake,
+        },
+        method = GET "/guilds/{guild_id}/emojis",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Vec<Emoji>,
+    ),
+    (
+        GetGuildEmoji {
+            guild_id: Snowflake,
+            emoji_id: Snowflake,
+        },
+        method = GET "/guilds/{guild_id}/emojis/{emoji_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Emoji,
+    ),
+    (
+        CreateGuildEmoji {
+            guild_id: Snowflake,
+            emoji: CreateGuildEmojiModel,
+        },
+        body = [json] emoji,
+        method = POST "/guilds/{guild_id}/emojis",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Emoji,
+    ),
+    (
+        ModifyGuildEmoji {
+            guild_id: Snowflake,
+            emoji_id: Snowflake,
+            emoji: ModifyGuildEmojiModel,
+        },
+        body = [json] emoji,
+        method = PATCH "/guilds/{guild_id}/emojis/{emoji_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Emoji,
+    ),
+    (
+        DeleteGuildEmoji {
+            guild_id: Snowflake,
+            emoji_id: Snowflake,
+        },
+        method = DELETE "/guilds/{guild_id}/emojis/{emoji_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    // Stickers
+    (
+        GetSticker {
+            sticker_id: Snowflake,
+        },
+        method = GET "/stickers/{sticker_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [0, 0],
+            )
+        },
+        response = [json] Sticker,
+    ),
+    (
+        ListGuildStickers {
+            guild_id: Snowflake,
+        },
+        method = GET "/guilds/{guild_id}/stickers",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Vec<Sticker>,
+    ),
+    (
+        GetGuildSticker {
+            guild_id: Snowflake,
+            sticker_id: Snowflake,
+        },
+        method = GET "/guilds/{guild_id}/stickers/{sticker_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Sticker,
+    ),
+    (
+        CreateGuildSticker {
+            guild_id: Snowflake,
+            sticker: CreateGuildStickerModel,
+        },
+        body = [multipart] sticker,
+        method = POST "/guilds/{guild_id}/stickers",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Sticker,
+    ),
+    (
+        ModifyGuildSticker {
+            guild_id: Snowflake,
+            sticker_id: Snowflake,
+            sticker: ModifyGuildStickerModel,
+        },
+        body = [json] sticker,
+        method = PATCH "/guilds/{guild_id}/stickers/{sticker_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [json] Sticker,
+    ),
+    (
+        DeleteGuildSticker {
+            guild_id: Snowflake,
+            sticker_id: Snowflake,
+        },
+        method = DELETE "/guilds/{guild_id}/stickers/{sticker_id}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [guild_id.to_u64(), 0],
+            )
+        },
+        response = [empty] (),
+    ),
+    // Invites
+    (
+        GetInvite {
+            invite_code: String,
+            query: GetInviteQuery,
+        },
+        method = GET "/invites/{invite_code}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [hash_str(invite_code), 0],
+            )
+        },
+        query = query,
+        response = [json] Invite,
+    ),
+    (
+        DeleteInvite {
+            invite_code: String,
+        },
+        method = DELETE "/invites/{invite_code}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [hash_str(invite_code), 0],
+            )
+        },
+        response = [json] Invite,
+    ),
+    // Voice
+    (
+        ListVoiceRegions {},
+        method = GET "/voice/regions",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [0, 0],
+            )
+        },
+        response = [json] Vec<VoiceRegion>,
     ),
     // Interactions
     (
@@ -350,7 +1102,21 @@ routes! {
                 [0, 0],
             )
         },
-        response = [json] Message,
+        validate = |route| {
+            match route.response {
+                InteractionResponse::ChannelMessageWithSource { ref data }
+                | InteractionResponse::UpdateMessage { ref data } => {
+                    validate_message(
+                        data.content.as_deref(),
+                        data.embeds.as_deref(),
+                        data.components.as_deref(),
+                        data.allowed_mentions.as_ref(),
+                    )
+                }
+                _ => Ok(()),
+            }
+        },
+        response = [empty] (),
     ),
     (
         GetOriginalInteractionResponse {
@@ -382,6 +1148,14 @@ routes! {
                 [application_id.to_u64(), hash_str(interaction_token)],
             )
         },
+        validate = |route| {
+            validate_message(
+                route.message.content.as_deref(),
+                route.message.embeds.as_deref(),
+                route.message.components.as_deref(),
+                route.message.allowed_mentions.as_ref(),
+            )
+        },
         response = [json] Message,
     ),
     (
@@ -397,7 +1171,7 @@ routes! {
                 [application_id.to_u64(), hash_str(interaction_token)],
             )
         },
-        response = [json] (),
+        response = [empty] (),
     ),
     (
         CreateFollowupMessage {
@@ -414,6 +1188,39 @@ routes! {
                 [application_id.to_u64(), hash_str(interaction_token)],
             )
         },
+        validate = |route| {
+            validate_message(
+                route.message.content.as_deref(),
+                route.message.embeds.as_deref(),
+                route.message.components.as_deref(),
+                route.message.allowed_mentions.as_ref(),
+            )
+        },
+        response = [json] Message,
+    ),
+    (
+        CreateFollowupMessageWithFiles {
+            application_id: Snowflake,
+            interaction_token: String,
+            message: CreateWebhookMessageWithFiles,
+        },
+        body = [multipart] message,
+        method = POST "/webhooks/{application_id}/{interaction_token}",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [application_id.to_u64(), hash_str(interaction_token)],
+            )
+        },
+        validate = |route| {
+            validate_message(
+                route.message.message.content.as_deref(),
+                route.message.message.embeds.as_deref(),
+                route.message.message.components.as_deref(),
+                route.message.message.allowed_mentions.as_ref(),
+            )
+        },
         response = [json] Message,
     ),
     (
@@ -477,7 +1284,7 @@ routes! {
                 [0, 0],
             )
         },
-        response = [json] GuildApplicationCommandPermissions,
+        response = [json] Vec<GuildApplicationCommandPermissions>,
     ),
     (
         GetApplicationCommandPermissions {
@@ -496,6 +1303,8 @@ routes! {
         response = [json] GuildApplicationCommandPermissions,
     ),
     (
+        /// Overwrites the permissions for a command in a guild. Overwrites
+        /// can target roles, users, or channels.
         EditApplicationCommandPermissions {
             application_id: Snowflake,
             guild_id: Snowflake,
@@ -511,9 +1320,22 @@ routes! {
                 [0, 0],
             )
         },
+        validate = |route| {
+            let count = route.permissions.permissions.len();
+            if count > CreateGuildApplicationCommandPermissions::MAX_PERMISSIONS {
+                Err(RequestError::Custom(anyhow!(
+                    "too many permission overwrites: {}",
+                    count
+                )))
+            } else {
+                Ok(())
+            }
+        },
         response = [json] GuildApplicationCommandPermissions,
     ),
     (
+        /// Discord has disabled this endpoint since permissions v2. Use
+        /// [`EditApplicationCommandPermissions`] for each command instead.
         BatchEditApplicationCommandPermissions {
             application_id: Snowflake,
             guild_id: Snowflake,
@@ -530,6 +1352,59 @@ routes! {
         },
         response = [json] Vec<GuildApplicationCommandPermissions>,
     ),
+    // Applications
+    (
+        GetCurrentApplication {},
+        method = GET "/applications/@me",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [0, 0],
+            )
+        },
+        response = [json] Application,
+    ),
+    (
+        EditCurrentApplication {
+            application: EditCurrentApplicationModel,
+        },
+        body = [json] application,
+        method = PATCH "/applications/@me",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_auth(
+                method,
+                route,
+                [0, 0],
+            )
+        },
+        response = [json] Application,
+    ),
+    // Gateway
+    (
+        GetGateway {},
+        method = GET "/gateway",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::without_auth(
+                method,
+                route,
+                [0, 0],
+            )
+        },
+        response = [json] Gateway,
+    ),
+    (
+        GetGatewayBot {},
+        method = GET "/gateway/bot",
+        info = |method, route| -> DiscordRouteInfo {
+            DiscordRouteInfo::with_bot_auth(
+                method,
+                route,
+                [0, 0],
+            )
+        },
+        response = [json] GatewayBot,
+    ),
     // OAuth2
     (
         AuthenticateClientCredentialsGrant {
@@ -546,6 +1421,7 @@ routes! {
                 [0, 0],
             )
         },
+        timeout = Duration::from_secs(10),
         processor = |req| req.basic_auth(client_id, Some(&***client_secret)),
         response = [json] ClientCredentials,
     ),
@@ -0,0 +1,69 @@
+//! Utilities for testing code which sends requests to Discord.
+
+use crate::{models::Snowflake, DiscordRestClient};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::Arc;
+use wfbp_http::test_util::MockRestServer;
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+/// Starts a mock Discord API and creates a client for the application which
+/// sends its requests there. Requests for the client's bearer token are
+/// already handled by the server.
+pub async fn discord_server(
+    application_id: Snowflake,
+) -> (MockRestServer, DiscordRestClient) {
+    let server = MockRestServer::start().await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/oauth2/token"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({
+                        "access_token": "test-token",
+                        "token_type": "Bearer",
+                        "expires_in": 604800,
+                        "scope": "applications.commands.update",
+                    }),
+                )),
+        )
+        .await;
+
+    let client = DiscordRestClient::new_with_base_url(
+        Client::new(),
+        server.uri(),
+        application_id,
+        Arc::new("secret".to_owned().into()),
+    );
+    (server, client)
+}
+
+/// A message with ID 5 sent by the bot, like Discord returns after a message
+/// is created or edited.
+pub fn message_json(channel_id: &str, content: &str) -> serde_json::Value {
+    json!({
+        "id": "5",
+        "channel_id": channel_id,
+        "author": {
+            "id": "2",
+            "username": "bot",
+            "discriminator": "0001",
+            "avatar": null,
+        },
+        "content": content,
+        "timestamp": "2021-01-01T00:00:00+00:00",
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0,
+        "flags": 0,
+    })
+}
@@ -0,0 +1,838 @@
+use serde_json::json;
+use wfbp_discord::models::{
+    validate_components, validate_message_content, AllowedMentionType,
+    AllowedMentions, AllowedMentionsError, ApplicationCommandInteractionData,
+    ApplicationCommandOptionChoice, ApplicationCommandOptionValue,
+    ApplicationCommandPermission, ApplicationCommandPermissionType,
+    ButtonStyle, Channel, Color, Component, ComponentLayoutError,
+    CreateGuildApplicationCommandPermissions, CreateMessage,
+    CreateWebhookMessage, Embed, EmbedField, Emoji,
+    GuildApplicationCommandPermissions, InteractionResponse,
+    InteractionResponseDataFlags, Mention, Message, MessageContentError,
+    MessageFlags, Overwrite, OverwriteType, Permissions, Role, SelectOption,
+    Snowflake, TextInputStyle, ThreadMember, ThreadMemberFlags, Timestamp,
+    TimestampStyle, User,
+};
+
+#[test]
+fn allowed_mentions_presets_are_valid() {
+    assert!(AllowedMentions::none().validate().is_ok());
+    assert!(AllowedMentions::all().validate().is_ok());
+    assert!(AllowedMentions::only_users([Snowflake::new(1)])
+        .validate()
+        .is_ok());
+    assert!(AllowedMentions::only_roles([Snowflake::new(1)])
+        .validate()
+        .is_ok());
+}
+
+#[test]
+fn allowed_mentions_rejects_listed_and_parsed_roles() {
+    let allowed_mentions = AllowedMentions {
+        parse: Some(vec![AllowedMentionType::ROLE]),
+        roles: Some(vec![Snowflake::new(1)]),
+        ..Default::default()
+    };
+    assert!(matches!(
+        allowed_mentions.validate(),
+        Err(AllowedMentionsError::RolesConflict)
+    ));
+}
+
+#[test]
+fn allowed_mentions_rejects_listed_and_parsed_users() {
+    let allowed_mentions = AllowedMentions {
+        parse: Some(vec![AllowedMentionType::USER]),
+        users: Some(vec![Snowflake::new(1)]),
+        ..Default::default()
+    };
+    assert!(matches!(
+        allowed_mentions.validate(),
+        Err(AllowedMentionsError::UsersConflict)
+    ));
+}
+
+#[test]
+fn timestamp_parses_whole_seconds() {
+    let timestamp: Timestamp =
+        serde_json::from_str(r#""2021-04-20T16:20:30+00:00""#).unwrap();
+    assert_eq!(timestamp.to_unix(), 1618935630);
+}
+
+#[test]
+fn timestamp_parses_fractional_seconds() {
+    let timestamp: Timestamp =
+        serde_json::from_str(r#""2021-04-20T16:20:30.123456+00:00""#).unwrap();
+    assert_eq!(timestamp.to_unix(), 1618935630);
+    assert_eq!(timestamp.0.timestamp_subsec_micros(), 123456);
+}
+
+#[test]
+fn timestamp_parses_non_utc_offsets() {
+    let timestamp: Timestamp =
+        serde_json::from_str(r#""2021-04-20T18:20:30.5+02:00""#).unwrap();
+    assert_eq!(timestamp.to_unix(), 1618935630);
+}
+
+#[test]
+fn timestamp_formats_discord_markdown() {
+    let timestamp = Timestamp::from_unix(1618935630).unwrap();
+    assert_eq!(
+        timestamp.to_discord_markdown(TimestampStyle::RELATIVE),
+        "<t:1618935630:R>"
+    );
+}
+
+#[test]
+fn embed_timestamp_serializes_as_iso8601() {
+    let embed = Embed {
+        timestamp: Timestamp::from_unix(1618935630),
+        ..Default::default()
+    };
+    let value = serde_json::to_value(&embed).unwrap();
+    assert_eq!(value["timestamp"], json!("2021-04-20T16:20:30Z"));
+}
+
+#[test]
+fn snowflake_parses_from_strings() {
+    let snowflake: Snowflake = "175928847299117063".parse().unwrap();
+    assert_eq!(snowflake, Snowflake::new(175928847299117063));
+    assert_eq!(snowflake.to_string(), "175928847299117063");
+    assert!("not a snowflake".parse::<Snowflake>().is_err());
+    assert!("-1".parse::<Snowflake>().is_err());
+}
+
+#[test]
+fn snowflake_serializes_as_string() {
+    // Larger than the integers JavaScript can represent exactly
+    let snowflake = Snowflake::new(u64::MAX);
+    let json = serde_json::to_value(snowflake).unwrap();
+    assert_eq!(json, json!("18446744073709551615"));
+
+    let parsed: Snowflake =
+        serde_json::from_str("\"18446744073709551615\"").unwrap();
+    assert_eq!(parsed, snowflake);
+    assert!(serde_json::from_str::<Snowflake>("\"\"").is_err());
+}
+
+#[test]
+fn snowflake_extracts_fields() {
+    let snowflake = Snowflake::new(175928847299117063);
+    assert_eq!(snowflake.created_at().0.timestamp_millis(), 1462015105796);
+    assert_eq!(snowflake.worker_id(), 1);
+    assert_eq!(snowflake.process_id(), 0);
+    assert_eq!(snowflake.increment(), 7);
+}
+
+#[test]
+#[allow(deprecated)]
+fn snowflake_timestamp_matches_created_at() {
+    let snowflake = Snowflake::new(175928847299117063);
+    assert_eq!(snowflake.timestamp().unwrap(), snowflake.created_at());
+}
+
+#[test]
+fn role_formats_mention() {
+    let role = Role {
+        id: Snowflake::new(41771983423143936),
+        name: "Traders".to_owned(),
+        color: Color::from_rgb(0x34, 0x98, 0xdb),
+        hoist: false,
+        position: 1,
+        permissions: Permissions::empty(),
+        managed: false,
+        mentionable: true,
+        tags: None,
+    };
+    assert_eq!(role.mention(), "<@&41771983423143936>");
+    assert_eq!(role.color, Color(0x3498db));
+}
+
+#[test]
+fn user_formats_mention() {
+    let user: User = serde_json::from_value(serde_json::json!({
+        "id": "80351110224678912",
+        "username": "Nelly",
+        "discriminator": "1337",
+        "avatar": null,
+    }))
+    .unwrap();
+    assert_eq!(user.mention(), "<@80351110224678912>");
+}
+
+#[test]
+fn channel_formats_mention() {
+    let channel: Channel = serde_json::from_value(serde_json::json!({
+        "id": "41771983423143937",
+        "type": 0,
+    }))
+    .unwrap();
+    assert_eq!(channel.mention(), "<#41771983423143937>");
+}
+
+fn emoji(id: Option<u64>, name: &str, animated: Option<bool>) -> Emoji {
+    Emoji {
+        id: id.map(Snowflake::new),
+        name: Some(name.to_owned()),
+        roles: None,
+        user: None,
+        require_colons: None,
+        managed: None,
+        animated,
+        available: None,
+    }
+}
+
+#[test]
+fn emoji_formats_mention() {
+    let custom = emoji(Some(380292389798936579), "WFPlatinum", None);
+    assert_eq!(custom.mention(), "<:WFPlatinum:380292389798936579>");
+
+    let animated = emoji(Some(380292389798936579), "WFPlatinum", Some(true));
+    assert_eq!(animated.mention(), "<a:WFPlatinum:380292389798936579>");
+
+    let unicode = emoji(None, "\u{1f48e}", None);
+    assert_eq!(unicode.mention(), "\u{1f48e}");
+}
+
+#[test]
+fn webhook_message_builder_sets_ephemeral_flags() {
+    let message = CreateWebhookMessage::builder()
+        .content("hello")
+        .ephemeral()
+        .build();
+    assert_eq!(message.flags, Some(MessageFlags::EPHEMERAL));
+
+    let data = CreateWebhookMessage::builder()
+        .content("hello")
+        .ephemeral()
+        .build_callback_data();
+    assert_eq!(data.flags, Some(InteractionResponseDataFlags::EPHEMERAL));
+}
+
+#[test]
+fn webhook_message_builder_sets_silent_flags() {
+    let message = CreateWebhookMessage::builder()
+        .content("hello")
+        .silent()
+        .ephemeral()
+        .build();
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({ "content": "hello", "flags": (1 << 12) | (1 << 6) })
+    );
+
+    let data = CreateWebhookMessage::builder()
+        .content("hello")
+        .silent()
+        .build_callback_data();
+    assert_eq!(
+        serde_json::to_value(&data).unwrap(),
+        json!({ "content": "hello", "flags": 4096 })
+    );
+}
+
+#[test]
+fn message_flags_round_trip() {
+    let message: Message = serde_json::from_value(json!({
+        "id": "2",
+        "channel_id": "3",
+        "author": {
+            "id": "1",
+            "username": "bot",
+            "discriminator": "0000",
+            "avatar": null,
+        },
+        "content": "",
+        "timestamp": "2021-01-01T00:00:00+00:00",
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0,
+        "flags": 68,
+    }))
+    .unwrap();
+    assert_eq!(
+        message.flags,
+        MessageFlags::SUPPRESS_EMBEDS | MessageFlags::EPHEMERAL
+    );
+    assert!(message.flags.contains(MessageFlags::EPHEMERAL));
+    assert!(!message.flags.contains(MessageFlags::CROSSPOSTED));
+
+    let json = serde_json::to_value(&message).unwrap();
+    assert_eq!(json["flags"], json!(68));
+}
+
+#[test]
+fn create_message_serializes_suppressed_embeds() {
+    let message = CreateMessage {
+        content: Some("https://warframe.market".into()),
+        flags: Some(MessageFlags::SUPPRESS_EMBEDS),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({
+            "content": "https://warframe.market",
+            "flags": 4,
+        })
+    );
+}
+
+fn overwrite(
+    id: u64,
+    kind: OverwriteType,
+    allow: Permissions,
+    deny: Permissions,
+) -> Overwrite {
+    Overwrite {
+        id: Snowflake::new(id),
+        kind,
+        allow,
+        deny,
+    }
+}
+
+#[test]
+fn permissions_apply_overwrites_in_order() {
+    let roles: Vec<Role> = serde_json::from_value(json!([
+        {
+            "id": "1",
+            "name": "@everyone",
+            "color": 0,
+            "hoist": false,
+            "position": 0,
+            "permissions": Permissions::SEND_MESSAGES.bits().to_string(),
+            "managed": false,
+            "mentionable": false,
+        },
+        {
+            "id": "2",
+            "name": "Mods",
+            "color": 0,
+            "hoist": false,
+            "position": 1,
+            "permissions": Permissions::MANAGE_MESSAGES.bits().to_string(),
+            "managed": false,
+            "mentionable": false,
+        },
+    ]))
+    .unwrap();
+    let overwrites = [
+        overwrite(
+            1,
+            OverwriteType::ROLE,
+            Permissions::empty(),
+            Permissions::SEND_MESSAGES,
+        ),
+        overwrite(
+            2,
+            OverwriteType::ROLE,
+            Permissions::SEND_MESSAGES,
+            Permissions::empty(),
+        ),
+        overwrite(
+            3,
+            OverwriteType::MEMBER,
+            Permissions::empty(),
+            Permissions::MANAGE_MESSAGES,
+        ),
+    ];
+
+    let mod_permissions = Permissions::compute(
+        Snowflake::new(1),
+        Snowflake::new(4),
+        &[Snowflake::new(2)],
+        &roles,
+        &overwrites,
+    );
+    assert_eq!(
+        mod_permissions,
+        Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES,
+    );
+
+    let member_permissions = Permissions::compute(
+        Snowflake::new(1),
+        Snowflake::new(3),
+        &[Snowflake::new(2)],
+        &roles,
+        &overwrites,
+    );
+    assert_eq!(member_permissions, Permissions::SEND_MESSAGES);
+
+    let everyone_permissions = Permissions::compute(
+        Snowflake::new(1),
+        Snowflake::new(5),
+        &[],
+        &roles,
+        &overwrites,
+    );
+    assert_eq!(everyone_permissions, Permissions::empty());
+}
+
+#[test]
+fn command_permissions_use_numeric_types() {
+    let guild_id = Snowflake::new(100);
+    let permissions = CreateGuildApplicationCommandPermissions {
+        permissions: vec![
+            ApplicationCommandPermission::everyone(guild_id, true),
+            ApplicationCommandPermission::all_channels(guild_id, false),
+            ApplicationCommandPermission::channel(Snowflake::new(42), true),
+            ApplicationCommandPermission::user(Snowflake::new(7), true),
+        ],
+    };
+
+    assert_eq!(
+        serde_json::to_value(&permissions).unwrap(),
+        json!({
+            "permissions": [
+                { "id": "100", "type": 1, "permission": true },
+                { "id": "99", "type": 3, "permission": false },
+                { "id": "42", "type": 3, "permission": true },
+                { "id": "7", "type": 2, "permission": true },
+            ],
+        })
+    );
+}
+
+#[test]
+fn command_permissions_parse_channel_overwrites() {
+    let permissions: GuildApplicationCommandPermissions =
+        serde_json::from_value(json!({
+            "id": "1",
+            "application_id": "2",
+            "guild_id": "100",
+            "permissions": [
+                { "id": "99", "type": 3, "permission": false },
+                { "id": "42", "type": 3, "permission": true },
+            ],
+        }))
+        .unwrap();
+
+    let kinds: Vec<_> = permissions
+        .permissions
+        .iter()
+        .map(|permission| (permission.id, permission.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (
+                Snowflake::new(99),
+                ApplicationCommandPermissionType::CHANNEL
+            ),
+            (
+                Snowflake::new(42),
+                ApplicationCommandPermissionType::CHANNEL
+            ),
+        ]
+    );
+}
+
+fn numbered_fields(count: usize) -> impl Iterator<Item = EmbedField> {
+    (0..count).map(|index| EmbedField {
+        name: format!("Field {index}"),
+        value: index.to_string(),
+        inline: None,
+    })
+}
+
+#[test]
+fn field_embeds_splits_fields_across_embeds() {
+    let template = Embed {
+        title: Some("Fields".into()),
+        color: Some(0x5865f2),
+        ..Default::default()
+    };
+    let message = CreateWebhookMessage::builder()
+        .field_embeds(template, numbered_fields(60))
+        .build();
+
+    let embeds = message.embeds.unwrap();
+    let field_counts: Vec<_> = embeds
+        .iter()
+        .map(|embed| embed.fields.as_ref().map_or(0, Vec::len))
+        .collect();
+    assert_eq!(field_counts, [25, 25, 10]);
+    assert_eq!(embeds[0].title.as_deref(), Some("Fields"));
+    assert_eq!(embeds[1].title, None);
+    assert_eq!(embeds[2].color, Some(0x5865f2));
+    assert_eq!(embeds[2].fields.as_ref().unwrap()[9].name, "Field 59");
+}
+
+#[test]
+fn field_embeds_respects_embed_limit() {
+    let message = CreateWebhookMessage::builder()
+        .embed(Embed::default())
+        .field_embeds(Embed::default(), numbered_fields(300))
+        .build();
+
+    let embeds = message.embeds.unwrap();
+    assert_eq!(embeds.len(), CreateWebhookMessage::MAX_EMBEDS);
+    let field_count: usize = embeds
+        .iter()
+        .filter_map(|embed| embed.fields.as_ref())
+        .map(Vec::len)
+        .sum();
+    assert_eq!(field_count, 9 * Embed::MAX_FIELDS);
+}
+
+#[test]
+fn field_embeds_skips_full_messages() {
+    let message = CreateWebhookMessage::builder()
+        .embeds(vec![Embed::default(); CreateWebhookMessage::MAX_EMBEDS])
+        .field_embeds(
+            Embed {
+                title: Some("Fields".into()),
+                ..Default::default()
+            },
+            numbered_fields(5),
+        )
+        .build();
+
+    let embeds = message.embeds.unwrap();
+    assert_eq!(embeds.len(), CreateWebhookMessage::MAX_EMBEDS);
+    assert!(embeds.iter().all(|embed| embed.title.is_none()));
+}
+
+#[test]
+fn field_embeds_respects_total_length() {
+    let fields = (0..10).map(|index| EmbedField {
+        name: index.to_string(),
+        value: "x".repeat(999),
+        inline: None,
+    });
+    let message = CreateWebhookMessage::builder()
+        .field_embeds(Embed::default(), fields)
+        .build();
+
+    let embeds = message.embeds.unwrap();
+    let total: usize = embeds.iter().map(Embed::text_length).sum();
+    assert_eq!(total, Embed::MAX_TOTAL_LENGTH);
+    assert_eq!(embeds[0].fields.as_ref().unwrap().len(), 6);
+}
+
+#[test]
+fn autocomplete_response_serializes_mixed_choices() {
+    let response = InteractionResponse::autocomplete([
+        ApplicationCommandOptionChoice {
+            name: "Mirage Prime Set".into(),
+            value: "mirage_prime_set".into(),
+        },
+        ApplicationCommandOptionChoice {
+            name: "Rank 5".into(),
+            value: 5i64.into(),
+        },
+        ApplicationCommandOptionChoice {
+            name: "Half".into(),
+            value: 0.5.into(),
+        },
+    ]);
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({
+            "type": 8,
+            "data": {
+                "choices": [
+                    { "name": "Mirage Prime Set", "value": "mirage_prime_set" },
+                    { "name": "Rank 5", "value": 5 },
+                    { "name": "Half", "value": 0.5 },
+                ],
+            },
+        })
+    );
+
+    let values: Vec<ApplicationCommandOptionValue> =
+        serde_json::from_value(json!(["a", 5, 0.5])).unwrap();
+    assert_eq!(
+        values,
+        [
+            ApplicationCommandOptionValue::String("a".into()),
+            ApplicationCommandOptionValue::Integer(5),
+            ApplicationCommandOptionValue::Number(0.5),
+        ]
+    );
+}
+
+#[test]
+fn modal_response_serializes_text_inputs() {
+    let response = InteractionResponse::modal(
+        "alert:create",
+        "Create price alert",
+        [Component::ActionRow {
+            components: vec![Component::TextInput {
+                custom_id: "price".into(),
+                style: TextInputStyle::SHORT,
+                label: "Maximum price".into(),
+                min_length: Some(1),
+                max_length: Some(6),
+                required: Some(true),
+                value: None,
+                placeholder: Some("100".into()),
+            }],
+        }],
+    );
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({
+            "type": 9,
+            "data": {
+                "custom_id": "alert:create",
+                "title": "Create price alert",
+                "components": [{
+                    "type": 1,
+                    "components": [{
+                        "type": 4,
+                        "custom_id": "price",
+                        "style": 1,
+                        "label": "Maximum price",
+                        "min_length": 1,
+                        "max_length": 6,
+                        "required": true,
+                        "placeholder": "100",
+                    }],
+                }],
+            },
+        })
+    );
+}
+
+fn button(custom_id: &str) -> Component {
+    Component::Button {
+        style: ButtonStyle::PRIMARY,
+        label: Some(custom_id.to_owned()),
+        emoji: None,
+        custom_id: Some(custom_id.to_owned()),
+        url: None,
+        disabled: None,
+    }
+}
+
+fn select_menu(custom_id: &str) -> Component {
+    Component::SelectMenu {
+        custom_id: custom_id.to_owned(),
+        options: vec![SelectOption {
+            label: "Option".to_owned(),
+            value: "option".to_owned(),
+            description: None,
+            emoji: None,
+            default: None,
+        }],
+        placeholder: None,
+        min_values: None,
+        max_values: None,
+        disabled: None,
+    }
+}
+
+fn action_row(components: Vec<Component>) -> Component {
+    Component::ActionRow { components }
+}
+
+#[test]
+fn components_accept_full_rows() {
+    let rows: Vec<_> = (0..Component::MAX_ACTION_ROWS)
+        .map(|_| {
+            action_row(
+                (0..Component::MAX_BUTTONS_PER_ROW)
+                    .map(|i| button(&i.to_string()))
+                    .collect(),
+            )
+        })
+        .collect();
+    assert!(validate_components(&rows).is_ok());
+    assert!(validate_components(&[action_row(vec![select_menu("a")])]).is_ok());
+}
+
+#[test]
+fn components_reject_over_full_row() {
+    let row = action_row((0..6).map(|i| button(&i.to_string())).collect());
+    assert!(matches!(
+        validate_components(&[action_row(vec![button("a")]), row]),
+        Err(ComponentLayoutError::TooManyButtons(1))
+    ));
+}
+
+#[test]
+fn components_reject_mixed_row() {
+    let row = action_row(vec![button("a"), select_menu("b")]);
+    assert!(matches!(
+        validate_components(&[row]),
+        Err(ComponentLayoutError::MixedRow(0))
+    ));
+}
+
+#[test]
+fn components_reject_too_many_rows_and_select_menus() {
+    let rows: Vec<_> = (0..6).map(|_| action_row(vec![button("a")])).collect();
+    assert!(matches!(
+        validate_components(&rows),
+        Err(ComponentLayoutError::TooManyRows(6))
+    ));
+
+    let row = action_row(vec![select_menu("a"), select_menu("b")]);
+    assert!(matches!(
+        validate_components(&[row]),
+        Err(ComponentLayoutError::TooManySelectMenus(0))
+    ));
+    assert!(matches!(
+        validate_components(&[button("a")]),
+        Err(ComponentLayoutError::NotInActionRow(0))
+    ));
+}
+
+#[test]
+fn components_reject_empty_rows() {
+    let rows = [action_row(vec![button("a")]), action_row(vec![])];
+    assert!(matches!(
+        validate_components(&rows),
+        Err(ComponentLayoutError::EmptyRow(1))
+    ));
+}
+
+#[test]
+fn components_reject_text_inputs() {
+    let text_input = Component::TextInput {
+        custom_id: "name".to_owned(),
+        style: TextInputStyle::SHORT,
+        label: "Name".to_owned(),
+        min_length: None,
+        max_length: None,
+        required: None,
+        value: None,
+        placeholder: None,
+    };
+    assert!(matches!(
+        validate_components(&[action_row(vec![text_input])]),
+        Err(ComponentLayoutError::TextInputInMessage(0))
+    ));
+}
+
+#[test]
+fn thread_member_flags_round_trip() {
+    let member: ThreadMember = serde_json::from_value(json!({
+        "id": "1",
+        "user_id": "2",
+        "join_timestamp": "2021-01-01T00:00:00+00:00",
+        "flags": 5,
+    }))
+    .unwrap();
+    assert_eq!(
+        member.flags,
+        ThreadMemberFlags::HAS_INTERACTED | ThreadMemberFlags::ONLY_MENTIONS
+    );
+    assert!(!member.flags.contains(ThreadMemberFlags::ALL_MESSAGES));
+
+    let json = serde_json::to_value(&member).unwrap();
+    assert_eq!(json["flags"], json!(5));
+}
+
+#[test]
+fn message_content_is_limited_to_2000_characters() {
+    let content = "é".repeat(CreateMessage::MAX_CONTENT_LENGTH);
+    assert!(validate_message_content(Some(&content), &[]).is_ok());
+
+    let message = CreateWebhookMessage::builder()
+        .content(content + "a")
+        .build();
+    assert!(matches!(
+        message.validate_content(),
+        Err(MessageContentError::ContentTooLong(2001))
+    ));
+
+    let message = CreateMessage {
+        content: Some("a".repeat(2001)),
+        ..Default::default()
+    };
+    assert!(matches!(
+        message.validate_content(),
+        Err(MessageContentError::ContentTooLong(2001))
+    ));
+}
+
+#[test]
+fn message_embeds_are_limited() {
+    let description = "a".repeat(Embed::MAX_DESCRIPTION_LENGTH);
+    let embed = Embed {
+        description: Some(description.clone()),
+        ..Default::default()
+    };
+    assert!(validate_message_content(None, &[embed]).is_ok());
+
+    let embed = Embed {
+        description: Some(description + "a"),
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_message_content(None, &[embed]),
+        Err(MessageContentError::DescriptionTooLong(0))
+    ));
+
+    // Each embed is valid, but not together
+    let embed = Embed {
+        description: Some("a".repeat(3000)),
+        ..Default::default()
+    };
+    assert!(
+        validate_message_content(None, &[embed.clone(), embed.clone()]).is_ok()
+    );
+    let embeds = [
+        embed.clone(),
+        embed,
+        Embed {
+            title: Some("a".to_owned()),
+            ..Default::default()
+        },
+    ];
+    assert!(matches!(
+        validate_message_content(None, &embeds),
+        Err(MessageContentError::EmbedsTooLong(6001))
+    ));
+
+    let embeds = vec![Embed::default(); CreateWebhookMessage::MAX_EMBEDS + 1];
+    assert!(matches!(
+        validate_message_content(None, &embeds),
+        Err(MessageContentError::TooManyEmbeds(11))
+    ));
+}
+
+#[test]
+fn command_path_includes_subcommands() {
+    let data: ApplicationCommandInteractionData =
+        serde_json::from_value(json!({
+            "type": 1,
+            "id": "1",
+            "name": "pc",
+            "options": [{
+                "type": 2,
+                "name": "admin",
+                "options": [{
+                    "type": 1,
+                    "name": "refresh-items",
+                    "options": [{
+                        "type": 5,
+                        "name": "force",
+                        "value": true,
+                    }],
+                }],
+            }],
+        }))
+        .unwrap();
+    assert_eq!(data.command_path(), ["pc", "admin", "refresh-items"]);
+
+    let data: ApplicationCommandInteractionData =
+        serde_json::from_value(json!({
+            "type": 1,
+            "id": "1",
+            "name": "help",
+            "options": [{ "type": 3, "name": "command", "value": "pc" }],
+        }))
+        .unwrap();
+    assert_eq!(data.command_path(), ["help"]);
+}
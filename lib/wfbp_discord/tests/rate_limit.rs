@@ -0,0 +1,89 @@
+use futures::future::join_all;
+use reqwest::{Client, Method, Response};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tower::{service_fn, Layer, ServiceExt};
+use wfbp_discord::{
+    middleware::RateLimitLayer, routes::DiscordRouteInfo, RateLimitBucket,
+    RateLimiter,
+};
+use wfbp_http::{middleware::RestRequestBuilder, RequestError};
+
+const RESET_AFTER: Duration = Duration::from_millis(50);
+
+fn request(id: usize) -> RestRequestBuilder {
+    let builder = Client::new()
+        .get("http://localhost/channels/1/messages")
+        .header("x-request-id", id);
+    let mut request = RestRequestBuilder::new(&builder).unwrap();
+    request.insert(DiscordRouteInfo::with_auth(
+        Method::GET,
+        "/channels/{channel_id}/messages",
+        [1, 0],
+    ));
+    request
+}
+
+/// Responds as if each request exhausted the bucket, recording when each
+/// request was sent.
+async fn respond_exhausted(
+    sent: Arc<Mutex<Vec<(String, Instant)>>>,
+    request: RestRequestBuilder,
+) -> Result<Response, RequestError> {
+    let request = request.into_request().build().unwrap();
+    let id = request.headers()["x-request-id"].to_str().unwrap();
+    sent.lock().unwrap().push((id.to_owned(), Instant::now()));
+
+    let response = http::Response::builder()
+        .header(RateLimiter::RATELIMIT_LIMIT, "1")
+        .header(RateLimiter::RATELIMIT_REMAINING, "0")
+        .header(
+            RateLimiter::RATELIMIT_RESET_AFTER,
+            RESET_AFTER.as_secs_f64().to_string(),
+        )
+        .body("")
+        .unwrap();
+    Ok(Response::from(response))
+}
+
+#[tokio::test]
+async fn queued_requests_resume_in_order_after_resets() {
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let service = RateLimitLayer::default().layer(service_fn({
+        let sent = sent.clone();
+        move |request| respond_exhausted(sent.clone(), request)
+    }));
+
+    let requests = (0..4).map(|id| service.clone().oneshot(request(id)));
+    for result in join_all(requests).await {
+        result.expect("error sending request");
+    }
+
+    let sent = sent.lock().unwrap();
+    let ids: Vec<_> = sent.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["0", "1", "2", "3"]);
+    for pair in sent.windows(2) {
+        let gap = pair[1].1 - pair[0].1;
+        assert!(gap >= RESET_AFTER, "requests resumed {:?} apart", gap);
+    }
+}
+
+#[test]
+fn invalid_reset_after_is_ignored() {
+    let bucket = RateLimitBucket::new(Method::GET, "/gateway/bot", [0, 0]);
+    for reset_after in ["1e20", "-1", "NaN", "inf"] {
+        let mut limiter = RateLimiter::new(bucket.clone());
+        let reset = limiter.reset;
+        let response = http::Response::builder()
+            .header(RateLimiter::RATELIMIT_LIMIT, "5")
+            .header(RateLimiter::RATELIMIT_RESET_AFTER, reset_after)
+            .body("")
+            .unwrap();
+
+        limiter.update(&Response::from(response));
+        assert_eq!(limiter.limit, 5);
+        assert_eq!(limiter.reset, reset, "{reset_after}");
+    }
+}
@@ -0,0 +1,926 @@
+use flate2::{write::GzEncoder, Compression};
+use reqwest::{Client, Method, StatusCode};
+use serde_json::json;
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use wfbp_discord::{
+    middleware::{InvalidScopeError, OAuthScope},
+    models::{
+        AllowedMentionType, AllowedMentions, ApplicationFlags, ButtonStyle,
+        Component, CreateGuildScheduledEvent,
+        CreateGuildSticker as CreateGuildStickerModel,
+        CreateMessage as CreateMessageModel, DiscordErrorCode,
+        EditCurrentApplication as EditCurrentApplicationModel,
+        EditWebhookMessage, GetGuildScheduledEventsQuery,
+        GuildScheduledEventEntityType, GuildScheduledEventStatus,
+        InteractionApplicationCommandCallbackData, InteractionResponse,
+        ModifyGuildScheduledEvent, Permissions, Snowflake, StickerFile,
+        StickerFormatType, ThreadMemberFlags, Timestamp,
+    },
+    routes::{
+        CreateGuildSticker, CreateInteractionResponse, CreateMessage,
+        CreateScheduledEvent, EditCurrentApplication,
+        EditOriginalInteractionResponse, GetChannel, GetCurrentApplication,
+        GetGatewayBot, GetGuildChannels, GetGuildVoiceRegions, GetThreadMember,
+        ListScheduledEvents, ListVoiceRegions, ModifyScheduledEvent,
+    },
+    test_util::{discord_server, message_json},
+    DiscordClientConfig, DiscordRequestErrorExt, DiscordRestClient, Metrics,
+    RateLimitBucket,
+};
+use wfbp_http::{RequestError, Route};
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn create_message() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/channels/123/messages"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(message_json("123", "hello")),
+                ),
+        )
+        .await;
+
+    CreateMessage::execute(
+        &client,
+        Snowflake::new(123),
+        CreateMessageModel {
+            content: Some("hello".to_owned()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("error creating message");
+
+    server
+        .single_request(Method::POST, "/channels/123/messages")
+        .await
+        .assert_header("authorization", "Bearer test-token")
+        .assert_json_body(json!({ "content": "hello" }));
+}
+
+#[tokio::test]
+async fn invalid_components_are_rejected_before_sending() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    let button = Component::Button {
+        style: ButtonStyle::PRIMARY,
+        label: Some("Next".to_owned()),
+        emoji: None,
+        custom_id: Some("next".to_owned()),
+        url: None,
+        disabled: None,
+    };
+
+    let result = CreateMessage::execute(
+        &client,
+        Snowflake::new(123),
+        CreateMessageModel {
+            content: Some("hello".to_owned()),
+            components: Some(vec![Component::ActionRow {
+                components: vec![button; 6],
+            }]),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(RequestError::Custom(_))));
+    assert!(server
+        .received_requests()
+        .await
+        .iter()
+        .all(|req| req.path() != "/channels/123/messages"));
+}
+
+#[tokio::test]
+async fn oversized_messages_are_rejected_before_sending() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    let result = CreateMessage::execute(
+        &client,
+        Snowflake::new(123),
+        CreateMessageModel {
+            content: Some(
+                "a".repeat(CreateMessageModel::MAX_CONTENT_LENGTH + 1),
+            ),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(RequestError::Custom(_))));
+    assert!(server
+        .received_requests()
+        .await
+        .iter()
+        .all(|req| req.path() != "/channels/123/messages"));
+}
+
+#[tokio::test]
+async fn interaction_messages_are_validated_before_sending() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+
+    // Edits check their content like new messages do
+    let result = EditOriginalInteractionResponse::execute(
+        &client,
+        Snowflake::new(1),
+        "token".to_owned(),
+        EditWebhookMessage {
+            content: Some(
+                "a".repeat(CreateMessageModel::MAX_CONTENT_LENGTH + 1),
+            ),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(RequestError::Custom(_))));
+
+    // Initial responses check their allowed mentions
+    let result = CreateInteractionResponse::execute(
+        &client,
+        Snowflake::new(1),
+        "token".to_owned(),
+        InteractionResponse::channel_message(
+            InteractionApplicationCommandCallbackData {
+                content: Some("hello".to_owned()),
+                allowed_mentions: Some(AllowedMentions {
+                    parse: Some(vec![AllowedMentionType::ROLE]),
+                    roles: Some(vec![Snowflake::new(2)]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ),
+    )
+    .await;
+    assert!(matches!(result, Err(RequestError::Custom(_))));
+
+    assert!(server
+        .received_requests()
+        .await
+        .iter()
+        .all(|req| req.path() == "/oauth2/token"));
+}
+
+fn voice_regions_json() -> serde_json::Value {
+    json!([
+        {
+            "id": "us-west",
+            "name": "US West",
+            "optimal": true,
+            "deprecated": false,
+            "custom": false,
+        },
+        {
+            "id": "amsterdam",
+            "name": "Amsterdam",
+            "optimal": false,
+            "deprecated": true,
+            "custom": false,
+        },
+    ])
+}
+
+#[tokio::test]
+async fn list_voice_regions() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/voice/regions"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(voice_regions_json()),
+                ),
+        )
+        .await;
+
+    let regions = ListVoiceRegions::execute(&client)
+        .await
+        .expect("error listing voice regions");
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].id, "us-west");
+    assert!(regions[0].optimal);
+    assert!(regions[1].deprecated);
+    assert_eq!(ListVoiceRegions {}.info().bucket.major_parameters, [0, 0]);
+
+    server
+        .single_request(Method::GET, "/voice/regions")
+        .await
+        .assert_header("authorization", "Bearer test-token");
+}
+
+#[tokio::test]
+async fn get_guild_voice_regions() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/guilds/456/regions"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(voice_regions_json()),
+                ),
+        )
+        .await;
+
+    let regions = GetGuildVoiceRegions::execute(&client, Snowflake::new(456))
+        .await
+        .expect("error getting guild voice regions");
+    assert_eq!(regions[1].name, "Amsterdam");
+    assert!(!regions[1].custom);
+    let route = GetGuildVoiceRegions {
+        guild_id: Snowflake::new(456),
+    };
+    assert_eq!(route.info().bucket.major_parameters, [456, 0]);
+
+    server
+        .single_request(Method::GET, "/guilds/456/regions")
+        .await
+        .assert_header("authorization", "Bearer test-token");
+}
+
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[tokio::test]
+async fn compressed_responses_are_decompressed() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    let body = serde_json::to_vec(&voice_regions_json()).unwrap();
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/voice/regions"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-encoding", "gzip")
+                        .insert_header("content-type", "application/json")
+                        .set_body_bytes(gzip(&body)),
+                ),
+        )
+        .await;
+
+    let regions = ListVoiceRegions::execute(&client)
+        .await
+        .expect("error listing voice regions");
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].id, "us-west");
+
+    let request = server.single_request(Method::GET, "/voice/regions").await;
+    let accept_encoding: Vec<_> = request
+        .0
+        .headers
+        .iter()
+        .filter(|(key, _)| key.as_str() == "accept-encoding")
+        .flat_map(|(_, values)| values.iter().map(|value| value.as_str()))
+        .collect();
+    assert!(accept_encoding.contains(&"gzip"));
+    assert!(accept_encoding.contains(&"br"));
+}
+
+#[tokio::test]
+async fn invalid_compressed_responses_are_rejected() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/voice/regions"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-encoding", "gzip")
+                        .insert_header("content-type", "application/json")
+                        .set_body_bytes(b"not gzip".to_vec()),
+                ),
+        )
+        .await;
+
+    let error = ListVoiceRegions::execute(&client).await.unwrap_err();
+    assert!(matches!(error, RequestError::Decompression(_)));
+}
+
+fn application_json(description: &str) -> serde_json::Value {
+    json!({
+        "id": "1",
+        "name": "Butler",
+        "icon": null,
+        "description": description,
+        "bot_public": true,
+        "bot_require_code_grant": false,
+        "summary": "",
+        "verify_key": "abc",
+        "team": null,
+        "flags": 0,
+        "tags": ["warframe", "prices"],
+        "install_params": {
+            "scopes": ["applications.commands"],
+            "permissions": "2048",
+        },
+    })
+}
+
+#[tokio::test]
+async fn get_current_application() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/applications/@me"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(application_json("Price checks")),
+                ),
+        )
+        .await;
+
+    let application = GetCurrentApplication::execute(&client)
+        .await
+        .expect("error getting application");
+    assert_eq!(application.description, "Price checks");
+    assert_eq!(
+        application.tags.as_deref(),
+        Some(&["warframe".to_owned(), "prices".to_owned()][..])
+    );
+    let install_params = application.install_params.unwrap();
+    assert_eq!(install_params.scopes, ["applications.commands"]);
+    assert_eq!(install_params.permissions, Permissions::SEND_MESSAGES);
+}
+
+#[tokio::test]
+async fn edit_current_application_omits_unset_fields() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("PATCH"))
+                .and(path("/applications/@me"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(application_json("Checks prices")),
+                ),
+        )
+        .await;
+
+    let application = EditCurrentApplication::execute(
+        &client,
+        EditCurrentApplicationModel {
+            description: Some("Checks prices".into()),
+            flags: Some(ApplicationFlags::empty()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("error editing application");
+    assert_eq!(application.description, "Checks prices");
+
+    server
+        .single_request(Method::PATCH, "/applications/@me")
+        .await
+        .assert_header("authorization", "Bearer test-token")
+        .assert_json_body(json!({
+            "description": "Checks prices",
+            "flags": 0,
+        }));
+}
+
+#[tokio::test]
+async fn configured_scope_is_requested() {
+    let (server, _) = discord_server(Snowflake::new(1)).await;
+    let client = DiscordRestClient::new_with_config(
+        Client::new(),
+        server.uri(),
+        Snowflake::new(1),
+        Arc::new("secret".to_owned().into()),
+        DiscordClientConfig {
+            scope: "applications.commands.update identify".parse().unwrap(),
+            ..Default::default()
+        },
+    );
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/guilds/456/channels"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(json!([])),
+                ),
+        )
+        .await;
+
+    GetGuildChannels::execute(&client, Snowflake::new(456))
+        .await
+        .expect("error getting guild channels");
+
+    let token_request =
+        server.single_request(Method::POST, "/oauth2/token").await;
+    let body = String::from_utf8(token_request.0.body.clone()).unwrap();
+    assert!(
+        body.contains("scope=applications.commands.update+identify"),
+        "unexpected body: {}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn gateway_bot_uses_bot_token() {
+    let (server, _) = discord_server(Snowflake::new(1)).await;
+    let client = DiscordRestClient::new_with_config(
+        Client::new(),
+        server.uri(),
+        Snowflake::new(1),
+        Arc::new("secret".to_owned().into()),
+        DiscordClientConfig {
+            bot_token: Some(Arc::new("bot-token".to_owned().into())),
+            ..Default::default()
+        },
+    );
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/gateway/bot"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({
+                        "url": "wss://gateway.discord.gg",
+                        "shards": 2,
+                        "session_start_limit": {
+                            "total": 1000,
+                            "remaining": 999,
+                            "reset_after": 14400000,
+                            "max_concurrency": 1,
+                        },
+                    }),
+                )),
+        )
+        .await;
+
+    let gateway = GetGatewayBot::execute(&client)
+        .await
+        .expect("error getting gateway");
+    assert_eq!(gateway.shards, 2);
+    assert_eq!(gateway.session_start_limit.remaining, 999);
+
+    server
+        .single_request(Method::GET, "/gateway/bot")
+        .await
+        .assert_header("authorization", "Bot bot-token");
+
+    // The OAuth2 access token is never requested
+    assert_eq!(server.received_requests().await.len(), 1);
+}
+
+#[tokio::test]
+async fn gateway_bot_requires_bot_token() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+
+    let result = GetGatewayBot::execute(&client).await;
+    assert!(
+        matches!(result, Err(RequestError::Custom(_))),
+        "unexpected result: {result:?}"
+    );
+    assert!(server.received_requests().await.is_empty());
+}
+
+#[test]
+fn empty_scope_is_rejected() {
+    assert_eq!(OAuthScope::new(" "), Err(InvalidScopeError::Empty));
+    assert!(serde_json::from_value::<OAuthScope>(json!("")).is_err());
+    assert_eq!(
+        OAuthScope::default().scopes().collect::<Vec<_>>(),
+        ["applications.commands.update"]
+    );
+}
+
+#[tokio::test]
+async fn get_thread_member() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/channels/10/thread-members/20"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({
+                        "id": "10",
+                        "user_id": "20",
+                        "join_timestamp": "2021-01-01T00:00:00+00:00",
+                        "flags": 9,
+                    }),
+                )),
+        )
+        .await;
+
+    let member = GetThreadMember::execute(
+        &client,
+        Snowflake::new(10),
+        Snowflake::new(20),
+    )
+    .await
+    .expect("error getting thread member");
+    assert_eq!(member.user_id, Some(Snowflake::new(20)));
+    assert_eq!(
+        member.flags,
+        ThreadMemberFlags::HAS_INTERACTED | ThreadMemberFlags::NO_MESSAGES
+    );
+}
+
+#[tokio::test]
+async fn get_guild_channels() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/guilds/456/channels"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!([
+                        {
+                            "id": "123",
+                            "type": 0,
+                            "guild_id": "456",
+                            "name": "general",
+                        },
+                    ]),
+                )),
+        )
+        .await;
+
+    let channels = GetGuildChannels::execute(&client, Snowflake::new(456))
+        .await
+        .expect("error getting guild channels");
+    assert_eq!(channels.len(), 1);
+
+    server
+        .single_request(Method::GET, "/guilds/456/channels")
+        .await
+        .assert_path("/guilds/456/channels");
+}
+
+fn scheduled_event_json(status: u8) -> serde_json::Value {
+    json!({
+        "id": "30",
+        "guild_id": "456",
+        "channel_id": null,
+        "creator_id": "1",
+        "name": "Weekly price discussion",
+        "scheduled_start_time": "2021-01-01T00:00:00+00:00",
+        "scheduled_end_time": "2021-01-01T01:00:00+00:00",
+        "privacy_level": 2,
+        "status": status,
+        "entity_type": 3,
+        "entity_id": null,
+        "entity_metadata": { "location": "Maroo's Bazaar" },
+        "user_count": 12,
+    })
+}
+
+#[tokio::test]
+async fn list_scheduled_events() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/guilds/456/scheduled-events"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!([scheduled_event_json(2)])),
+                ),
+        )
+        .await;
+
+    let events = ListScheduledEvents::execute(
+        &client,
+        Snowflake::new(456),
+        GetGuildScheduledEventsQuery {
+            with_user_count: Some(true),
+        },
+    )
+    .await
+    .expect("error listing scheduled events");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].status, GuildScheduledEventStatus::ACTIVE);
+    assert_eq!(
+        events[0].entity_type,
+        GuildScheduledEventEntityType::EXTERNAL
+    );
+    assert_eq!(events[0].user_count, Some(12));
+
+    let request = server
+        .single_request(Method::GET, "/guilds/456/scheduled-events")
+        .await;
+    assert_eq!(request.0.url.query(), Some("with_user_count=true"));
+}
+
+#[tokio::test]
+async fn create_external_scheduled_event() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/guilds/456/scheduled-events"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(scheduled_event_json(1)),
+                ),
+        )
+        .await;
+
+    let event = CreateGuildScheduledEvent::builder(
+        "Weekly price discussion",
+        Timestamp::from_unix(1609459200).unwrap(),
+    )
+    .scheduled_end_time(Timestamp::from_unix(1609462800).unwrap())
+    .external("Maroo's Bazaar")
+    .build();
+    CreateScheduledEvent::execute(&client, Snowflake::new(456), event)
+        .await
+        .expect("error creating scheduled event");
+
+    server
+        .single_request(Method::POST, "/guilds/456/scheduled-events")
+        .await
+        .assert_json_body(json!({
+            "entity_metadata": { "location": "Maroo's Bazaar" },
+            "name": "Weekly price discussion",
+            "privacy_level": 2,
+            "scheduled_start_time": "2021-01-01T00:00:00Z",
+            "scheduled_end_time": "2021-01-01T01:00:00Z",
+            "entity_type": 3,
+        }));
+}
+
+#[tokio::test]
+async fn external_scheduled_events_require_a_location() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    let event = CreateGuildScheduledEvent::builder(
+        "Weekly price discussion",
+        Timestamp::from_unix(1609459200).unwrap(),
+    )
+    .build();
+    let result =
+        CreateScheduledEvent::execute(&client, Snowflake::new(456), event)
+            .await;
+    assert!(matches!(result, Err(RequestError::Custom(_))));
+
+    let result = ModifyScheduledEvent::execute(
+        &client,
+        Snowflake::new(456),
+        Snowflake::new(30),
+        ModifyGuildScheduledEvent {
+            entity_type: Some(GuildScheduledEventEntityType::EXTERNAL),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(RequestError::Custom(_))));
+    assert!(server
+        .received_requests()
+        .await
+        .iter()
+        .all(|request| !request.path().contains("scheduled-events")));
+}
+
+#[tokio::test]
+async fn rate_limit_snapshot_reports_bucket_state() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/guilds/456/channels"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("x-ratelimit-limit", "5")
+                        .insert_header("x-ratelimit-remaining", "4")
+                        .insert_header("x-ratelimit-reset", "1618935630")
+                        .set_body_json(json!([])),
+                ),
+        )
+        .await;
+    assert!(client.rate_limit_snapshot().is_empty());
+
+    GetGuildChannels::execute(&client, Snowflake::new(456))
+        .await
+        .expect("error getting guild channels");
+
+    let snapshot = client.rate_limit_snapshot();
+    assert_eq!(snapshot.len(), 1);
+    let (bucket, info) = &snapshot[0];
+    assert_eq!(bucket.method, Method::GET);
+    assert_eq!(bucket.major_parameters, [456, 0]);
+    assert_eq!(info.limit, 5);
+    assert_eq!(info.remaining, 4);
+    assert_eq!(info.reset.timestamp(), 1618935630);
+}
+
+#[tokio::test]
+async fn create_guild_sticker_uploads_multipart_body() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/guilds/456/stickers"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({
+                        "id": "789",
+                        "name": "plat",
+                        "description": "Platinum",
+                        "tags": "money",
+                        "type": 2,
+                        "format_type": 1,
+                        "guild_id": "456",
+                    }),
+                )),
+        )
+        .await;
+
+    let sticker = CreateGuildSticker::execute(
+        &client,
+        Snowflake::new(456),
+        CreateGuildStickerModel {
+            name: "plat".into(),
+            description: "Platinum".into(),
+            tags: "money".into(),
+            file: StickerFile {
+                file_name: "plat.png".into(),
+                content_type: "image/png".into(),
+                data: b"not really a png".to_vec(),
+            },
+        },
+    )
+    .await
+    .expect("error creating sticker");
+    assert_eq!(sticker.id, Snowflake::new(789));
+    assert_eq!(sticker.format_type, StickerFormatType::PNG);
+
+    let request = server
+        .single_request(Method::POST, "/guilds/456/stickers")
+        .await;
+    let content_type = request.header("content-type").unwrap();
+    assert!(content_type.starts_with("multipart/form-data; boundary="));
+    let body = String::from_utf8(request.0.body.clone()).unwrap();
+    assert!(body.contains("name=\"tags\"\r\n\r\nmoney\r\n"));
+    assert!(body.contains(
+        "name=\"file\"; filename=\"plat.png\"\r\nContent-Type: image/png"
+    ));
+    assert!(body.contains("not really a png"));
+}
+
+#[tokio::test]
+async fn api_errors_preserve_error_body() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/channels/123/messages"))
+                .respond_with(ResponseTemplate::new(403).set_body_json(
+                    json!({
+                        "code": 50013,
+                        "message": "Missing Permissions",
+                    }),
+                )),
+        )
+        .await;
+
+    let error = CreateMessage::execute(
+        &client,
+        Snowflake::new(123),
+        CreateMessageModel {
+            content: Some("hello".to_owned()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect_err("request should fail");
+
+    assert_eq!(error.status(), Some(StatusCode::FORBIDDEN));
+    assert_eq!(error.code(), Some(DiscordErrorCode::MISSING_PERMISSIONS));
+    assert!(matches!(
+        error,
+        RequestError::ApiError { ref message, .. }
+            if message == "Missing Permissions"
+    ));
+}
+
+#[tokio::test]
+async fn idempotent_routes_are_retried() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/channels/123"))
+                .respond_with(ResponseTemplate::new(500))
+                .up_to_n_times(1)
+                .with_priority(1),
+        )
+        .await;
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/channels/123"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({
+                        "id": "123",
+                        "type": 0,
+                    }),
+                )),
+        )
+        .await;
+
+    GetChannel::execute(&client, Snowflake::new(123))
+        .await
+        .expect("error getting channel");
+
+    let requests = server.received_requests().await;
+    let attempts = requests
+        .iter()
+        .filter(|req| req.path() == "/channels/123")
+        .count();
+    assert_eq!(attempts, 2);
+}
+
+#[tokio::test]
+async fn non_idempotent_routes_are_not_retried() {
+    let (server, client) = discord_server(Snowflake::new(1)).await;
+    server
+        .mock(
+            Mock::given(method("POST"))
+                .and(path("/channels/123/messages"))
+                .respond_with(ResponseTemplate::new(500)),
+        )
+        .await;
+
+    let error = CreateMessage::execute(
+        &client,
+        Snowflake::new(123),
+        CreateMessageModel {
+            content: Some("hello".to_owned()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect_err("request should fail");
+    assert_eq!(error.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+
+    // Panics if the request was sent more than once
+    server
+        .single_request(Method::POST, "/channels/123/messages")
+        .await;
+}
+
+#[derive(Debug, Default)]
+struct RecordingMetrics {
+    started: Mutex<Vec<&'static str>>,
+    ended: Mutex<Vec<(&'static str, Option<StatusCode>)>>,
+}
+
+impl Metrics for RecordingMetrics {
+    fn on_request_start(&self, bucket: &RateLimitBucket) {
+        self.started.lock().unwrap().push(bucket.route);
+    }
+
+    fn on_request_end(
+        &self,
+        bucket: &RateLimitBucket,
+        status: Option<StatusCode>,
+        _duration: Duration,
+    ) {
+        self.ended.lock().unwrap().push((bucket.route, status));
+    }
+}
+
+#[tokio::test]
+async fn metrics_are_reported() {
+    let (server, _) = discord_server(Snowflake::new(1)).await;
+    let metrics = Arc::new(RecordingMetrics::default());
+    let client = DiscordRestClient::new_with_metrics(
+        Client::new(),
+        server.uri(),
+        Snowflake::new(1),
+        Arc::new("secret".to_owned().into()),
+        DiscordClientConfig::default(),
+        metrics.clone(),
+    );
+    server
+        .mock(
+            Mock::given(method("GET"))
+                .and(path("/guilds/456/channels"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(json!([])),
+                ),
+        )
+        .await;
+
+    GetGuildChannels::execute(&client, Snowflake::new(456))
+        .await
+        .expect("error getting guild channels");
+
+    let route = "/guilds/{guild_id}/channels";
+    let started = metrics.started.lock().unwrap();
+    assert_eq!(started.iter().filter(|&&r| r == route).count(), 1);
+    let ended = metrics.ended.lock().unwrap();
+    assert!(ended.contains(&(route, Some(StatusCode::OK))));
+}
@@ -0,0 +1,36 @@
+use derive_more::{Display, Error};
+use futures::{Stream, StreamExt};
+
+/// Reads a body stream chunk by chunk, failing as soon as more than `max`
+/// bytes have been received.
+pub async fn read_body_limited<S, B, E>(
+    body: S,
+    max: usize,
+) -> Result<Vec<u8>, ReadBodyError<E>>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+{
+    futures::pin_mut!(body);
+    let mut buffer = Vec::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(ReadBodyError::Stream)?;
+        let chunk = chunk.as_ref();
+        if buffer.len() + chunk.len() > max {
+            return Err(ReadBodyError::TooLarge { limit: max });
+        }
+
+        buffer.extend_from_slice(chunk);
+    }
+
+    Ok(buffer)
+}
+
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum ReadBodyError<E> {
+    #[display(fmt = "body is larger than the limit of {} bytes", limit)]
+    TooLarge { limit: usize },
+    #[display(fmt = "error reading body: {}", _0)]
+    Stream(E),
+}
@@ -0,0 +1,37 @@
+use crate::RequestError;
+use reqwest::{header::ETAG, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The response to a conditional request, such as one sent with an
+/// `If-None-Match` header.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Conditional<T> {
+    /// The resource was returned. `etag` can be sent with later requests to
+    /// check if the resource changed.
+    Modified { value: T, etag: Option<String> },
+    /// The resource hasn't changed since the last request.
+    NotModified,
+}
+
+impl<T> Conditional<T>
+where
+    T: DeserializeOwned,
+{
+    /// Parses a JSON response, treating `304 Not Modified` as
+    /// [`Conditional::NotModified`].
+    pub async fn from_json_response(
+        response: Response,
+    ) -> Result<Self, RequestError> {
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Conditional::NotModified);
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(ToOwned::to_owned);
+        let value = response.json().await?;
+        Ok(Conditional::Modified { value, etag })
+    }
+}
@@ -1,10 +1,112 @@
+use crate::{read_body_limited, ReadBodyError};
 use derive_more::{Display, Error, From};
+use reqwest::{Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::error::Error as _;
+
+/// The maximum number of bytes read from the body of an unsuccessful
+/// response.
+pub const MAX_ERROR_BODY_SIZE: usize = 64 * 1024;
 
 #[derive(Debug, Display, Error, From)]
 #[non_exhaustive]
 pub enum RequestError {
     #[display(fmt = "{}", _0)]
+    #[from(ignore)]
     ReqwestError(reqwest::Error),
+    /// The response body was compressed, but couldn't be decompressed.
+    #[display(fmt = "error decompressing response body: {}", _0)]
+    #[from(ignore)]
+    Decompression(reqwest::Error),
+    #[display(fmt = "API error ({}): {}", status, message)]
+    #[from(ignore)]
+    ApiError {
+        status: StatusCode,
+        code: Option<i32>,
+        message: String,
+    },
     #[display(fmt = "{}", _0)]
     Custom(#[error(ignore)] anyhow::Error),
 }
+
+impl RequestError {
+    /// Creates an [`RequestError::ApiError`] from an unsuccessful response.
+    /// If the response body is a JSON error object, its `code` and `message`
+    /// are preserved, otherwise the raw body is used as the message.
+    pub async fn from_response(response: Response) -> Self {
+        #[derive(Deserialize)]
+        struct ErrorBody {
+            code: Option<i64>,
+            message: Option<String>,
+        }
+
+        let status = response.status();
+        let body = match read_error_body(response).await {
+            Ok(body) => body,
+            Err(ReadBodyError::Stream(error)) => return error.into(),
+            Err(error) => error.to_string().into_bytes(),
+        };
+
+        let json: Option<ErrorBody> = serde_json::from_slice(&body).ok();
+        let code = json
+            .as_ref()
+            .and_then(|json| json.code)
+            .and_then(|code| code.try_into().ok());
+        let message = json
+            .and_then(|json| json.message)
+            .unwrap_or_else(|| String::from_utf8_lossy(&body).into_owned());
+
+        RequestError::ApiError {
+            status,
+            code,
+            message,
+        }
+    }
+
+    /// The HTTP status code of the response, if the API returned an error.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            RequestError::ReqwestError(error)
+            | RequestError::Decompression(error) => error.status(),
+            RequestError::ApiError { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(error: reqwest::Error) -> Self {
+        // Errors from the body decoder wrap an I/O error, while errors from
+        // parsing the decompressed body wrap the parser's error
+        let decompression_failed = error.is_decode()
+            && error
+                .source()
+                .filter(|source| source.is::<std::io::Error>())
+                .is_some();
+        if decompression_failed {
+            RequestError::Decompression(error)
+        } else {
+            RequestError::ReqwestError(error)
+        }
+    }
+}
+
+/// Reads the JSON body of an unsuccessful response as `E`. Returns [`None`]
+/// if the response was successful, or if the body is larger than
+/// [`MAX_ERROR_BODY_SIZE`] or isn't a valid `E`.
+pub async fn parse_error_body<E: DeserializeOwned>(
+    response: Response,
+) -> Option<E> {
+    if response.status().is_success() {
+        return None;
+    }
+
+    let body = read_error_body(response).await.ok()?;
+    serde_json::from_slice(&body).ok()
+}
+
+async fn read_error_body(
+    response: Response,
+) -> Result<Vec<u8>, ReadBodyError<reqwest::Error>> {
+    read_body_limited(response.bytes_stream(), MAX_ERROR_BODY_SIZE).await
+}
@@ -1,14 +1,26 @@
 pub mod middleware;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
+mod body;
+mod conditional;
 mod error;
 mod macros;
+mod multipart;
+mod path_segment;
 mod rest_client;
 mod routes;
+mod transport;
 
+pub use body::*;
+pub use conditional::*;
 pub use error::*;
 pub use macros::*;
+pub use multipart::*;
+pub use path_segment::*;
 pub use rest_client::*;
 pub use routes::*;
+pub use transport::*;
 
 #[doc(hidden)]
 pub use async_trait;
@@ -1,11 +1,23 @@
 #[macro_export]
 macro_rules! routes {
+    (@req_body $builder:expr, $body:expr, [multipart]) => {
+        $crate::MultipartForm::from($body).apply($builder)
+    };
     (@req_body $builder:expr, $body:expr, [$body_type:ident]) => {
         $builder.$body_type($body)
     };
     (@req_body $builder:expr,) => {
         $builder
     };
+    (@res_body $res:expr, [empty]) => {
+        async move {
+            ::std::mem::drop($res);
+            ::std::result::Result::<(), $crate::RequestError>::Ok(())
+        }
+    };
+    (@res_body $res:expr, [conditional_json]) => {
+        $crate::Conditional::from_json_response($res)
+    };
     (@res_body $res:expr, [$body_type:ident]) => {
         $res.$body_type()
     };
@@ -15,12 +27,23 @@ macro_rules! routes {
     (@query $builder:expr,) => {
         $builder
     };
+    (@idempotent $self:expr, $method:ident, |$route:pat_param| $idempotent:expr) => {{
+        let $route = $self;
+        $idempotent
+    }};
+    (@idempotent $self:expr, POST,) => {
+        false
+    };
+    (@idempotent $self:expr, $method:ident,) => {
+        true
+    };
     (@info_type $info_type:ty, $($_:tt)*) => {
         $info_type
     };
     {
         $(
             (
+                $(#[$route_meta:meta])*
                 $route_ty:ident {
                     $($route_field:ident : $route_field_type:ty),*
                     $(,)?
@@ -29,7 +52,11 @@ macro_rules! routes {
                 $(, body = [$body_type:ident] $body:expr)?
                 , method = $method:ident $route:literal
                 $(, info = |$info_method:pat_param, $info_route:pat_param| -> $info_type:ty $info:block)?
+                $(, validate = |$validate_route:pat_param| $validate:expr)?
+                $(, idempotent = |$idempotent_route:pat_param| $idempotent:expr)?
+                $(, timeout = $timeout:expr)?
                 $(, query = $query:expr)?
+                $(, if_none_match = $if_none_match:expr)?
                 $(, processor = |$req:pat_param| $processor:expr)?
                 , response = [$res_body_type:ident] $response:ty
                 $(,)?
@@ -38,6 +65,7 @@ macro_rules! routes {
         $(,)?
     } => {
         $(
+            $(#[$route_meta])*
             #[derive(Clone, Debug)]
             pub struct $route_ty $(<$($generics)*>)? {
                 $(pub $route_field: $route_field_type),*
@@ -90,6 +118,31 @@ macro_rules! routes {
                     )?
                 }
 
+                $(
+                    fn validate(
+                        &self,
+                    ) -> ::std::result::Result<(), $crate::RequestError> {
+                        let $validate_route = self;
+                        $validate
+                    }
+                )?
+
+                #[inline]
+                fn is_idempotent(&self) -> bool {
+                    $crate::routes!(
+                        @idempotent self,
+                        $method,
+                        $(|$idempotent_route| $idempotent)?
+                    )
+                }
+
+                $(
+                    #[inline]
+                    fn timeout(&self) -> ::std::option::Option<::std::time::Duration> {
+                        ::std::option::Option::Some($timeout)
+                    }
+                )?
+
                 fn create_request<F>(
                     &self,
                     request_factory: F
@@ -123,6 +176,17 @@ macro_rules! routes {
                     // Query string
                     let request = $crate::routes!(@query request, $($query)?);
 
+                    // Conditional headers
+                    $(
+                        let request = match $if_none_match {
+                            ::std::option::Option::Some(etag) => request.header(
+                                $crate::reqwest::header::IF_NONE_MATCH,
+                                etag,
+                            ),
+                            ::std::option::Option::None => request,
+                        };
+                    )?
+
                     // Processor
                     $(
                         let $req = request;
@@ -1,17 +1,34 @@
+use crate::{ReqwestTransport, Transport};
 use downcast_rs::{impl_downcast, DowncastSync};
 use dyn_clone::{clone_trait_object, DynClone};
-use futures::{future::BoxFuture, FutureExt};
+use futures::future::BoxFuture;
 use reqwest::{RequestBuilder, Response};
 use std::{
     any::TypeId,
     collections::HashMap,
     fmt::Debug,
+    sync::Arc,
     task::{Context, Poll},
 };
 use tower::Service;
 
-#[derive(Clone, Debug, Default)]
-pub struct ExecuteRequestService;
+/// Sends requests using a [`Transport`].
+#[derive(Clone, Debug)]
+pub struct ExecuteRequestService {
+    transport: Arc<dyn Transport>,
+}
+
+impl ExecuteRequestService {
+    pub fn new(transport: Arc<dyn Transport>) -> Self {
+        ExecuteRequestService { transport }
+    }
+}
+
+impl Default for ExecuteRequestService {
+    fn default() -> Self {
+        Self::new(Arc::new(ReqwestTransport))
+    }
+}
 
 impl Service<RequestBuilder> for ExecuteRequestService {
     type Response = Response;
@@ -26,7 +43,7 @@ impl Service<RequestBuilder> for ExecuteRequestService {
     }
 
     fn call(&mut self, req: RequestBuilder) -> Self::Future {
-        req.send().boxed()
+        self.transport.send(req)
     }
 }
 
@@ -1,20 +1,54 @@
-use anyhow::anyhow;
+use crate::middleware::RestRequestBuilder;
 use futures::future::BoxFuture;
-use reqwest::{Response, StatusCode};
-use std::task::{Context, Poll};
+use reqwest::{header::RETRY_AFTER, Response, StatusCode};
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
 use tower::{Layer, Service};
 use tracing::warn;
 
-/// Retries a request indefinitely, halting only if the inner service errors or
-/// returns a fatal response.
+/// How failed requests are retried.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryConfig {
+    /// The most times a request is retried before its last response is
+    /// returned.
+    pub max_retries: usize,
+    /// The base delay for exponential backoff, in milliseconds.
+    pub backoff_base_millis: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_retries: 3,
+            backoff_base_millis: 20,
+        }
+    }
+}
+
+/// Retries a request until the inner service errors or returns a successful
+/// or fatal response. Fatal responses are returned as-is so the caller can
+/// inspect them. By default, requests are retried indefinitely.
 #[derive(Clone, Debug)]
 pub struct RetryLayer<P> {
     policy: P,
+    max_retries: Option<usize>,
 }
 
 impl<P> RetryLayer<P> {
     pub fn new(policy: P) -> Self {
-        RetryLayer { policy }
+        RetryLayer {
+            policy,
+            max_retries: None,
+        }
+    }
+
+    /// Stops retrying after `max_retries` retries, returning the last
+    /// response instead.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = Some(max_retries);
+        self
     }
 }
 
@@ -27,6 +61,7 @@ where
     fn layer(&self, next: Next) -> Self::Service {
         RetryService {
             policy: self.policy.clone(),
+            max_retries: self.max_retries,
             next,
         }
     }
@@ -35,14 +70,16 @@ where
 #[derive(Clone, Debug)]
 pub struct RetryService<P, Next> {
     policy: P,
+    max_retries: Option<usize>,
     next: Next,
 }
 
 impl<P, Req, Next> Service<Req> for RetryService<P, Next>
 where
-    P: RetryPolicy<Next::Response> + Clone + Send + 'static,
+    P: RetryPolicy<Req, Next::Response> + Clone + Send + 'static,
     Req: Clone + Send + 'static,
     Next: Service<Req> + Clone + Send + 'static,
+    Next::Response: Send,
     Next::Error: From<anyhow::Error>,
     Next::Future: Send + 'static,
 {
@@ -60,27 +97,39 @@ where
     fn call(&mut self, req: Req) -> Self::Future {
         let mut next = self.next.clone();
         let policy = self.policy.clone();
+        let max_retries = self.max_retries;
 
         Box::pin(async move {
+            let mut retries = 0;
             loop {
                 // Poll next until it's ready
                 futures::future::poll_fn(|cx| next.poll_ready(cx)).await?;
 
                 // Make request
-                let req = req.clone();
-                let res = next.call(req).await?;
+                let res = next.call(req.clone()).await?;
 
                 // Process response
-                let response_kind = policy.classify(&res);
+                let response_kind = policy.classify(&req, &res);
                 match response_kind {
-                    ResponseKind::Success => return Ok(res),
-                    ResponseKind::Transient => {
-                        warn!("request failed (transient failure)")
+                    ResponseKind::Success | ResponseKind::Fatal => {
+                        return Ok(res)
                     }
-                    ResponseKind::Fatal => {
-                        return Err(anyhow!("request failed").into())
+                    ResponseKind::Transient => {
+                        warn!(retries, "request failed (transient failure)")
                     }
                 }
+
+                // Give up once the retries are used up
+                if matches!(max_retries, Some(max) if retries >= max) {
+                    warn!(retries, "request failed after retrying");
+                    return Ok(res);
+                }
+                retries += 1;
+
+                // Wait if the server asked for a delay
+                if let Some(delay) = policy.retry_after(&req, &res) {
+                    tokio::time::sleep(delay).await;
+                }
             }
         })
     }
@@ -93,23 +142,90 @@ pub enum ResponseKind {
     Fatal,
 }
 
-pub trait RetryPolicy<Res> {
-    fn classify(&self, res: &Res) -> ResponseKind;
+pub trait RetryPolicy<Req, Res> {
+    fn classify(&self, req: &Req, res: &Res) -> ResponseKind;
+
+    /// How long to wait before retrying a transient failure, in addition to
+    /// any backoff.
+    fn retry_after(&self, _req: &Req, _res: &Res) -> Option<Duration> {
+        None
+    }
 }
 
+/// Whether a request can be safely sent more than once. Requests without this
+/// value are assumed to be idempotent.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Idempotency(pub bool);
+
+/// Retries requests which failed due to a transient error. Requests that
+/// aren't idempotent are only retried if they were definitely not processed,
+/// which is the case when they are rate limited.
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
-pub struct TransientRequestRetryPolicy {}
+pub struct TransientRequestRetryPolicy {
+    honor_retry_after: bool,
+}
+
+impl TransientRequestRetryPolicy {
+    /// The longest `Retry-After` delay that will be waited for.
+    pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+    /// Waits for the delay in a response's `Retry-After` header, if any,
+    /// before retrying it. Delays are capped at
+    /// [`TransientRequestRetryPolicy::MAX_RETRY_AFTER`].
+    pub fn honoring_retry_after(mut self) -> Self {
+        self.honor_retry_after = true;
+        self
+    }
+}
 
-impl RetryPolicy<Response> for TransientRequestRetryPolicy {
-    fn classify(&self, res: &Response) -> ResponseKind {
+impl RetryPolicy<RestRequestBuilder, Response> for TransientRequestRetryPolicy {
+    fn classify(
+        &self,
+        req: &RestRequestBuilder,
+        res: &Response,
+    ) -> ResponseKind {
+        let idempotent = !matches!(req.get(), Some(Idempotency(false)));
         match res.status() {
             status if status.is_success() => ResponseKind::Success,
-            status if status.is_server_error() => ResponseKind::Transient,
-            StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS => {
-                ResponseKind::Transient
+            StatusCode::TOO_MANY_REQUESTS => ResponseKind::Transient,
+            status
+                if status.is_server_error()
+                    || status == StatusCode::REQUEST_TIMEOUT =>
+            {
+                if idempotent {
+                    ResponseKind::Transient
+                } else {
+                    ResponseKind::Fatal
+                }
             }
             _ => ResponseKind::Fatal,
         }
     }
+
+    fn retry_after(
+        &self,
+        _req: &RestRequestBuilder,
+        res: &Response,
+    ) -> Option<Duration> {
+        if !self.honor_retry_after {
+            return None;
+        }
+
+        let seconds: f64 = res
+            .headers()
+            .get(RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        if !seconds.is_finite() || seconds < 0.0 {
+            return None;
+        }
+
+        // Clamp before converting, since huge delays don't fit in a Duration
+        let seconds = seconds.min(Self::MAX_RETRY_AFTER.as_secs_f64());
+        Some(Duration::from_secs_f64(seconds))
+    }
 }
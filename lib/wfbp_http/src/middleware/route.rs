@@ -1,4 +1,7 @@
-use crate::{middleware::RestRequestBuilder, RequestError, Route};
+use crate::{
+    middleware::{Idempotency, RestRequestBuilder},
+    RequestError, Route,
+};
 use anyhow::anyhow;
 use futures::future::BoxFuture;
 use reqwest::{Client, Response};
@@ -58,11 +61,20 @@ where
     }
 
     fn call(&mut self, req: Req) -> Self::Future {
+        // Validate the route
+        if let Err(error) = req.validate() {
+            return Box::pin(async move { Err(error) });
+        }
+
         // Create HTTP request
         let http_req = req.create_request(|method, path| {
             let url = format!("{}{}", self.base_url, path);
             self.client.request(method, url)
         });
+        let http_req = match req.timeout() {
+            Some(timeout) => http_req.timeout(timeout),
+            None => http_req,
+        };
 
         // Convert to REST request builder
         let mut http_req = match RestRequestBuilder::new(&http_req) {
@@ -78,11 +90,17 @@ where
 
         // Embed route info into request builder
         http_req.insert(req.info());
+        http_req.insert(Idempotency(req.is_idempotent()));
 
         // Create the future
         let fut = self.next.call(http_req);
         Box::pin(async move {
             let http_res = fut.await?;
+            let status = http_res.status();
+            if status.is_client_error() || status.is_server_error() {
+                return Err(RequestError::from_response(http_res).await);
+            }
+
             req.map_response(http_res).await
         })
     }
@@ -0,0 +1,133 @@
+use rand::{distributions::Alphanumeric, Rng};
+use reqwest::{header::CONTENT_TYPE, RequestBuilder};
+use std::borrow::Cow;
+
+/// A `multipart/form-data` body. Unlike [`reqwest::multipart::Form`], the
+/// body is buffered so requests using it can be cloned and retried.
+#[derive(Clone, Debug)]
+pub struct MultipartForm {
+    boundary: String,
+    parts: Vec<MultipartPart>,
+}
+
+#[derive(Clone, Debug)]
+struct MultipartPart {
+    name: Cow<'static, str>,
+    file: Option<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        let boundary: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+
+        MultipartForm {
+            boundary,
+            parts: Vec::new(),
+        }
+    }
+
+    /// The boundary between each part of the body.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Adds a text field.
+    pub fn text(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.into(),
+            file: None,
+            data: value.into().into_bytes(),
+        });
+        self
+    }
+
+    /// Adds a file field.
+    pub fn file(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.into(),
+            file: Some((file_name.into(), content_type.into())),
+            data: data.into(),
+        });
+        self
+    }
+
+    /// Encodes the body.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        for part in self.parts.iter() {
+            body.extend_from_slice(b"--");
+            body.extend_from_slice(self.boundary.as_bytes());
+            body.extend_from_slice(b"\r\n");
+            match part.file {
+                Some((ref file_name, ref content_type)) => {
+                    body.extend_from_slice(
+                        format!(
+                            "Content-Disposition: form-data; name=\"{}\"; \
+                             filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                            escape(&part.name),
+                            escape(file_name),
+                            content_type,
+                        )
+                        .as_bytes(),
+                    );
+                }
+                None => {
+                    body.extend_from_slice(
+                        format!(
+                            "Content-Disposition: form-data; \
+                             name=\"{}\"\r\n\r\n",
+                            escape(&part.name),
+                        )
+                        .as_bytes(),
+                    );
+                }
+            }
+            body.extend_from_slice(&part.data);
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(b"--");
+        body.extend_from_slice(self.boundary.as_bytes());
+        body.extend_from_slice(b"--\r\n");
+        body
+    }
+
+    /// Sets the body and `Content-Type` of a request.
+    pub fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        request
+            .header(
+                CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", self.boundary),
+            )
+            .body(self.to_bytes())
+    }
+}
+
+impl Default for MultipartForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
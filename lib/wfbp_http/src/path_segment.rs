@@ -0,0 +1,38 @@
+use std::fmt::{Display, Formatter, Write};
+
+/// A single segment of a URL path. The segment is percent-encoded when
+/// displayed, so values containing reserved characters like `/` or `?`
+/// can't change the path or add a query string.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PathSegment(pub String);
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0.bytes() {
+            match byte {
+                b'A'..=b'Z'
+                | b'a'..=b'z'
+                | b'0'..=b'9'
+                | b'-'
+                | b'.'
+                | b'_'
+                | b'~' => f.write_char(byte as char)?,
+                _ => write!(f, "%{byte:02X}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<String> for PathSegment {
+    fn from(segment: String) -> Self {
+        PathSegment(segment)
+    }
+}
+
+impl From<&str> for PathSegment {
+    fn from(segment: &str) -> Self {
+        PathSegment(segment.to_owned())
+    }
+}
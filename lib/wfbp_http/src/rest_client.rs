@@ -1,15 +1,23 @@
 use crate::{
     middleware::{
         BackoffLayer, ExecuteRequestService, JitterLayer, LimitLayer,
-        RestRequestBuilder, RetryLayer, RouteLayer,
+        RestRequestBuilder, RetryConfig, RetryLayer, RouteLayer,
         TransientRequestRetryPolicy,
     },
-    RequestError, Route,
+    RequestError, ReqwestTransport, Route, Transport,
 };
 use async_trait::async_trait;
 use reqwest::{Client, RequestBuilder, Response};
-use std::borrow::Cow;
-use tower::{util::BoxLayer, ServiceBuilder, ServiceExt};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+use tower::{
+    layer::layer_fn,
+    util::{BoxCloneService, BoxLayer},
+    Layer, Service, ServiceBuilder, ServiceExt,
+};
 
 #[async_trait]
 pub trait RestClient<R>
@@ -22,28 +30,208 @@ where
 pub type RestRequestLayer =
     BoxLayer<ExecuteRequestService, RestRequestBuilder, Response, RequestError>;
 
+/// A service that sends a single attempt of a request. Layers added to a
+/// [`RestClientBuilder`] wrap this service.
+pub type RequestService =
+    BoxCloneService<RestRequestBuilder, Response, RequestError>;
+
+type BoxedRequestLayer =
+    Arc<dyn Fn(RequestService) -> RequestService + Send + Sync>;
+
+/// Builds the request pipeline shared by the REST clients. Requests are
+/// retried according to a retry policy, and each attempt is delayed with
+/// exponential backoff and jitter. Clients can add their own layers, like
+/// authentication or rate limiting, around each attempt.
+#[derive(Clone)]
+pub struct RestClientBuilder {
+    client: Client,
+    base_url: Cow<'static, str>,
+    transport: Arc<dyn Transport>,
+    retry_policy: TransientRequestRetryPolicy,
+    max_retries: Option<usize>,
+    max_tries: usize,
+    backoff_base_millis: u64,
+    layers: Vec<BoxedRequestLayer>,
+    transport_layers: Vec<BoxedRequestLayer>,
+}
+
+impl RestClientBuilder {
+    /// The most times a request can be sent, including retries.
+    pub const DEFAULT_MAX_TRIES: usize = 10;
+
+    pub fn new(client: Client, base_url: impl Into<Cow<'static, str>>) -> Self {
+        RestClientBuilder {
+            client,
+            base_url: base_url.into(),
+            transport: Arc::new(ReqwestTransport),
+            retry_policy: TransientRequestRetryPolicy::default(),
+            max_retries: None,
+            max_tries: Self::DEFAULT_MAX_TRIES,
+            backoff_base_millis: RetryConfig::default().backoff_base_millis,
+            layers: Vec::new(),
+            transport_layers: Vec::new(),
+        }
+    }
+
+    /// Sends requests using a different [`Transport`].
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Sets which responses are retried.
+    pub fn retry_policy(mut self, policy: TransientRequestRetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Bounds the number of retries and sets the backoff between them. By
+    /// default, requests are retried until they reach the maximum number of
+    /// tries.
+    pub fn retry(mut self, config: RetryConfig) -> Self {
+        self.max_retries = Some(config.max_retries);
+        self.backoff_base_millis = config.backoff_base_millis;
+        self
+    }
+
+    /// Adds a layer around each attempt, outside of the backoff delay.
+    /// Layers are applied in the order they're added, with the first layer
+    /// being the outermost.
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<RequestService> + Send + Sync + 'static,
+        L::Service: Service<
+                RestRequestBuilder,
+                Response = Response,
+                Error = RequestError,
+            > + Clone
+            + Send
+            + 'static,
+        <L::Service as Service<RestRequestBuilder>>::Future: Send + 'static,
+    {
+        self.layers.push(box_layer(layer));
+        self
+    }
+
+    /// Adds a layer around each attempt, directly around the transport.
+    /// These layers see every request exactly as it's sent, after any delays.
+    pub fn transport_layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<RequestService> + Send + Sync + 'static,
+        L::Service: Service<
+                RestRequestBuilder,
+                Response = Response,
+                Error = RequestError,
+            > + Clone
+            + Send
+            + 'static,
+        <L::Service as Service<RestRequestBuilder>>::Future: Send + 'static,
+    {
+        self.transport_layers.push(box_layer(layer));
+        self
+    }
+
+    /// Builds the layer which turns routes into requests.
+    pub fn build_route_layer(&self) -> RouteLayer {
+        RouteLayer::new(self.client.clone(), self.base_url.clone())
+    }
+
+    /// Builds the layer which sends requests, retrying them as needed.
+    pub fn build_request_layer(&self) -> RestRequestLayer {
+        let layers = self.layers.clone();
+        let transport_layers = self.transport_layers.clone();
+        let backoff_base_millis = self.backoff_base_millis;
+        let max_tries = self.max_tries;
+        let retry_layer = match self.max_retries {
+            Some(max_retries) => RetryLayer::new(self.retry_policy.clone())
+                .with_max_retries(max_retries),
+            None => RetryLayer::new(self.retry_policy.clone()),
+        };
+
+        BoxLayer::new(layer_fn(move |transport: ExecuteRequestService| {
+            let mut service = BoxCloneService::new(
+                ServiceBuilder::new()
+                    .map_request(RequestBuilder::from)
+                    .map_err(RequestError::from)
+                    .service(transport),
+            );
+            for layer in transport_layers.iter().rev() {
+                service = layer(service);
+            }
+            service = BoxCloneService::new(
+                ServiceBuilder::new()
+                    .layer(BackoffLayer::new(backoff_base_millis))
+                    .layer(JitterLayer::default())
+                    .service(service),
+            );
+            for layer in layers.iter().rev() {
+                service = layer(service);
+            }
+
+            ServiceBuilder::new()
+                .layer(retry_layer.clone())
+                .layer(LimitLayer::new(max_tries))
+                .service(service)
+        }))
+    }
+
+    /// Builds the service which executes requests with the transport.
+    pub fn build_transport(&self) -> ExecuteRequestService {
+        ExecuteRequestService::new(self.transport.clone())
+    }
+
+    pub fn build(self) -> StandardRestClient {
+        StandardRestClient {
+            route_layer: self.build_route_layer(),
+            request_layer: self.build_request_layer(),
+            transport: self.build_transport(),
+        }
+    }
+}
+
+impl Debug for RestClientBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestClientBuilder")
+            .field("base_url", &self.base_url)
+            .field("retry_policy", &self.retry_policy)
+            .field("max_retries", &self.max_retries)
+            .field("max_tries", &self.max_tries)
+            .field("backoff_base_millis", &self.backoff_base_millis)
+            .finish_non_exhaustive()
+    }
+}
+
+fn box_layer<L>(layer: L) -> BoxedRequestLayer
+where
+    L: Layer<RequestService> + Send + Sync + 'static,
+    L::Service: Service<RestRequestBuilder, Response = Response, Error = RequestError>
+        + Clone
+        + Send
+        + 'static,
+    <L::Service as Service<RestRequestBuilder>>::Future: Send + 'static,
+{
+    Arc::new(move |service| BoxCloneService::new(layer.layer(service)))
+}
+
 #[derive(Clone, Debug)]
 pub struct StandardRestClient {
     route_layer: RouteLayer,
     request_layer: RestRequestLayer,
+    transport: ExecuteRequestService,
 }
 
 impl StandardRestClient {
     pub fn new(client: Client, base_url: impl Into<Cow<'static, str>>) -> Self {
-        let base_url = base_url.into();
-        let service = ServiceBuilder::new()
-            .layer(RetryLayer::new(TransientRequestRetryPolicy::default()))
-            .layer(LimitLayer::new(10))
-            .layer(BackoffLayer::default())
-            .layer(JitterLayer::default())
-            .map_request(RequestBuilder::from)
-            .map_err(RequestError::from)
-            .check_service::<ExecuteRequestService, RestRequestBuilder, Response, RequestError>();
+        RestClientBuilder::new(client, base_url).build()
+    }
 
-        StandardRestClient::new_from_layers(
-            RouteLayer::new(client, base_url),
-            BoxLayer::new(service),
-        )
+    /// Creates a builder for a client with additional layers or different
+    /// retry behavior.
+    pub fn builder(
+        client: Client,
+        base_url: impl Into<Cow<'static, str>>,
+    ) -> RestClientBuilder {
+        RestClientBuilder::new(client, base_url)
     }
 
     pub fn new_from_layers(
@@ -53,8 +241,15 @@ impl StandardRestClient {
         StandardRestClient {
             route_layer,
             request_layer,
+            transport: ExecuteRequestService::default(),
         }
     }
+
+    /// Sends requests using a different [`Transport`].
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = ExecuteRequestService::new(transport);
+        self
+    }
 }
 
 #[async_trait]
@@ -67,7 +262,7 @@ where
             .layer(&self.route_layer)
             .layer(&self.request_layer)
             .check_service::<ExecuteRequestService, R, R::Response, RequestError>()
-            .service(ExecuteRequestService::default());
+            .service(self.transport.clone());
 
         // Execute service
         service.oneshot(route).await
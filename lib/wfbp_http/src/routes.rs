@@ -1,6 +1,7 @@
 use crate::{middleware::RestRequestValue, RequestError};
 use async_trait::async_trait;
 use reqwest::{Method, RequestBuilder, Response};
+use std::time::Duration;
 
 /// A route within the Discord REST API.
 #[async_trait]
@@ -14,6 +15,25 @@ pub trait Route: Send + Sync + 'static {
     /// Gets additional route information
     fn info(&self) -> Self::Info;
 
+    /// Checks that the route can be sent. This is called before the HTTP
+    /// request is created.
+    fn validate(&self) -> Result<(), RequestError> {
+        Ok(())
+    }
+
+    /// Whether sending this request more than once has the same effect as
+    /// sending it once. Requests which aren't idempotent are not retried
+    /// after they may have been received.
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    /// How long to wait for a response before the request times out. If this
+    /// is [`None`], the client's default timeout is used.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Creates an HTTP request to this route.
     ///
     /// `request_factory` accepts a HTTP method and URL path and creates a
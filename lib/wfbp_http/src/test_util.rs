@@ -0,0 +1,265 @@
+//! Utilities for testing routes against a local mock server or an in-memory
+//! transport.
+
+use crate::{StandardRestClient, Transport};
+use futures::{future::BoxFuture, FutureExt};
+use reqwest::{
+    header::{HeaderMap, CONTENT_TYPE},
+    Client, Method, RequestBuilder, Response, StatusCode, Url,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+use wiremock::{Mock, MockServer, Request};
+
+/// A mock HTTP server that records the requests sent to it.
+#[derive(Debug)]
+pub struct MockRestServer {
+    server: MockServer,
+}
+
+impl MockRestServer {
+    /// Starts a new mock server on a random local port.
+    pub async fn start() -> Self {
+        MockRestServer {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// The base URL of the mock server.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// The underlying [`MockServer`].
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// Creates a [`StandardRestClient`] which sends its requests to this
+    /// server.
+    pub fn client(&self) -> StandardRestClient {
+        StandardRestClient::new(Client::new(), self.uri())
+    }
+
+    /// Mounts a mock on this server.
+    pub async fn mock(&self, mock: Mock) {
+        self.server.register(mock).await;
+    }
+
+    /// Gets all the requests received by this server, in the order they were
+    /// received.
+    pub async fn received_requests(&self) -> Vec<RecordedRequest> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(RecordedRequest)
+            .collect()
+    }
+
+    /// Gets the only request received by this server for the given method
+    /// and path. Panics if there isn't exactly one.
+    pub async fn single_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> RecordedRequest {
+        let mut requests: Vec<_> = self
+            .received_requests()
+            .await
+            .into_iter()
+            .filter(|req| req.method() == method.as_str())
+            .filter(|req| req.path() == path)
+            .collect();
+        assert_eq!(
+            requests.len(),
+            1,
+            "expected exactly one {} request to {}",
+            method,
+            path
+        );
+        requests.remove(0)
+    }
+}
+
+/// A request received by a [`MockRestServer`].
+#[derive(Debug)]
+pub struct RecordedRequest(pub Request);
+
+impl RecordedRequest {
+    /// The HTTP method of the request.
+    pub fn method(&self) -> String {
+        self.0.method.to_string()
+    }
+
+    /// The URL path of the request.
+    pub fn path(&self) -> &str {
+        self.0.url.path()
+    }
+
+    /// Gets the last value of a header, if the header was sent.
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.0
+            .headers
+            .iter()
+            .find(|(key, _)| key.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, values)| values.last().as_str().to_owned())
+    }
+
+    /// Parses the body of the request as JSON.
+    pub fn json_body(&self) -> serde_json::Value {
+        self.0.body_json().expect("request body is not valid JSON")
+    }
+
+    /// Asserts the HTTP method of the request.
+    pub fn assert_method(&self, method: Method) -> &Self {
+        assert_eq!(self.method(), method.as_str(), "unexpected method");
+        self
+    }
+
+    /// Asserts the URL path of the request.
+    pub fn assert_path(&self, path: &str) -> &Self {
+        assert_eq!(self.path(), path, "unexpected path");
+        self
+    }
+
+    /// Asserts that a header was sent with the given value.
+    pub fn assert_header(&self, name: &str, value: &str) -> &Self {
+        assert_eq!(
+            self.header(name).as_deref(),
+            Some(value),
+            "unexpected value for header {}",
+            name
+        );
+        self
+    }
+
+    /// Asserts that the request body is equivalent to the given JSON.
+    pub fn assert_json_body(&self, body: serde_json::Value) -> &Self {
+        assert_eq!(self.json_body(), body, "unexpected body");
+        self
+    }
+}
+
+type MockRoute = (Method, String);
+type MockResponse = (StatusCode, String);
+
+/// A [`Transport`] which responds to requests with canned responses instead
+/// of sending them. Requests without a response get a `404 Not Found`.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<HashMap<MockRoute, MockResponse>>,
+    queued: Mutex<HashMap<MockRoute, VecDeque<MockResponse>>>,
+    requests: Mutex<Vec<(Method, Url)>>,
+    headers: Mutex<Vec<HeaderMap>>,
+    delay: Mutex<Duration>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Responds to requests with the given method and URL path.
+    pub fn respond(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        status: StatusCode,
+        body: impl Into<String>,
+    ) {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert((method, path.into()), (status, body.into()));
+    }
+
+    /// Responds to the next request with the given method and URL path,
+    /// before falling back to the response set with
+    /// [`MockTransport::respond`]. Queued responses are used in order.
+    pub fn respond_once(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        status: StatusCode,
+        body: impl Into<String>,
+    ) {
+        self.queued
+            .lock()
+            .unwrap()
+            .entry((method, path.into()))
+            .or_default()
+            .push_back((status, body.into()));
+    }
+
+    /// Responds to requests with the given method and URL path with JSON.
+    pub fn respond_json(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        body: serde_json::Value,
+    ) {
+        self.respond(method, path, StatusCode::OK, body.to_string());
+    }
+
+    /// Waits before responding to each request, so concurrent requests are
+    /// in flight at the same time.
+    pub fn delay(&self, delay: Duration) {
+        *self.delay.lock().unwrap() = delay;
+    }
+
+    /// Gets the method and URL of each request, in the order they were sent.
+    pub fn requests(&self) -> Vec<(Method, Url)> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Gets the headers of each request, in the order they were sent.
+    pub fn request_headers(&self) -> Vec<HeaderMap> {
+        self.headers.lock().unwrap().clone()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(
+        &self,
+        request: RequestBuilder,
+    ) -> BoxFuture<'static, Result<Response, reqwest::Error>> {
+        let request = match request.build() {
+            Ok(request) => request,
+            Err(error) => return async move { Err(error) }.boxed(),
+        };
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let key = (method.clone(), url.path().to_owned());
+        let queued = self
+            .queued
+            .lock()
+            .unwrap()
+            .get_mut(&key)
+            .and_then(VecDeque::pop_front);
+        let (status, body) = queued
+            .or_else(|| self.responses.lock().unwrap().get(&key).cloned())
+            .unwrap_or((StatusCode::NOT_FOUND, String::new()));
+        self.requests.lock().unwrap().push((method, url));
+        self.headers.lock().unwrap().push(request.headers().clone());
+
+        let response = http::Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .expect("error creating response");
+        let delay = *self.delay.lock().unwrap();
+        async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
+            Ok(Response::from(response))
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,25 @@
+use futures::{future::BoxFuture, FutureExt};
+use reqwest::{RequestBuilder, Response};
+use std::fmt::Debug;
+
+/// Sends HTTP requests. Clients use [`ReqwestTransport`] by default, but a
+/// different transport can be used to avoid the network in tests.
+pub trait Transport: Debug + Send + Sync + 'static {
+    fn send(
+        &self,
+        request: RequestBuilder,
+    ) -> BoxFuture<'static, Result<Response, reqwest::Error>>;
+}
+
+/// Sends requests with [`reqwest`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReqwestTransport;
+
+impl Transport for ReqwestTransport {
+    fn send(
+        &self,
+        request: RequestBuilder,
+    ) -> BoxFuture<'static, Result<Response, reqwest::Error>> {
+        request.send().boxed()
+    }
+}
@@ -0,0 +1,30 @@
+use futures::{executor::block_on, stream};
+use std::convert::Infallible;
+use wfbp_http::{read_body_limited, ReadBodyError};
+
+fn chunks(
+    chunks: Vec<&'static [u8]>,
+) -> impl futures::Stream<Item = Result<&'static [u8], Infallible>> {
+    stream::iter(chunks.into_iter().map(Ok))
+}
+
+#[test]
+fn body_under_limit_is_read() {
+    let body = chunks(vec![b"hello ", b"world"]);
+    let result = block_on(read_body_limited(body, 11)).unwrap();
+    assert_eq!(result, b"hello world");
+}
+
+#[test]
+fn body_over_limit_is_rejected() {
+    let body = chunks(vec![b"hello ", b"world"]);
+    let result = block_on(read_body_limited(body, 10));
+    assert!(matches!(result, Err(ReadBodyError::TooLarge { limit: 10 })));
+}
+
+#[test]
+fn stream_errors_are_returned() {
+    let body = stream::iter(vec![Ok(&b"hello"[..]), Err("broken pipe")]);
+    let result = block_on(read_body_limited(body, 100));
+    assert!(matches!(result, Err(ReadBodyError::Stream("broken pipe"))));
+}
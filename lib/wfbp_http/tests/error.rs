@@ -0,0 +1,97 @@
+use futures::executor::block_on;
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+use wfbp_http::{parse_error_body, RequestError, MAX_ERROR_BODY_SIZE};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct DiscordError {
+    code: i32,
+    message: String,
+}
+
+fn response(status: StatusCode, body: impl Into<String>) -> Response {
+    http::Response::builder()
+        .status(status)
+        .body(body.into())
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn error_body_is_parsed() {
+    let response = response(
+        StatusCode::NOT_FOUND,
+        r#"{"code": 10003, "message": "Unknown Channel"}"#,
+    );
+    let error: Option<DiscordError> = block_on(parse_error_body(response));
+    assert_eq!(
+        error,
+        Some(DiscordError {
+            code: 10003,
+            message: "Unknown Channel".into(),
+        })
+    );
+}
+
+#[test]
+fn successful_and_invalid_bodies_are_ignored() {
+    let body = r#"{"code": 0, "message": "ok"}"#;
+    let ok: Option<DiscordError> =
+        block_on(parse_error_body(response(StatusCode::OK, body)));
+    assert_eq!(ok, None);
+
+    let html: Option<DiscordError> = block_on(parse_error_body(response(
+        StatusCode::BAD_GATEWAY,
+        "<html>Bad Gateway</html>",
+    )));
+    assert_eq!(html, None);
+}
+
+#[test]
+fn large_error_bodies_are_not_read() {
+    let message = "a".repeat(MAX_ERROR_BODY_SIZE);
+    let body = format!(r#"{{"code": 1, "message": "{}"}}"#, message);
+    let error: Option<DiscordError> =
+        block_on(parse_error_body(response(StatusCode::BAD_REQUEST, &*body)));
+    assert_eq!(error, None);
+
+    let error = block_on(RequestError::from_response(response(
+        StatusCode::BAD_REQUEST,
+        body,
+    )));
+    match error {
+        RequestError::ApiError {
+            status,
+            code,
+            message,
+        } => {
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(code, None);
+            assert!(message.len() < MAX_ERROR_BODY_SIZE, "{}", message);
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn api_error_keeps_code_and_message() {
+    let error = block_on(RequestError::from_response(response(
+        StatusCode::FORBIDDEN,
+        r#"{"code": 50013, "message": "Missing Permissions"}"#,
+    )));
+    assert!(matches!(
+        error,
+        RequestError::ApiError { status: StatusCode::FORBIDDEN, code: Some(50013), ref message }
+            if message == "Missing Permissions"
+    ));
+
+    let error = block_on(RequestError::from_response(response(
+        StatusCode::BAD_GATEWAY,
+        "Bad Gateway",
+    )));
+    assert!(matches!(
+        error,
+        RequestError::ApiError { code: None, ref message, .. }
+            if message == "Bad Gateway"
+    ));
+}
@@ -0,0 +1,35 @@
+use wfbp_http::MultipartForm;
+
+#[test]
+fn multipart_form_is_encoded() {
+    let form = MultipartForm::new().text("name", "plat").file(
+        "file",
+        "plat.png",
+        "image/png",
+        b"data".to_vec(),
+    );
+    let boundary = form.boundary().to_owned();
+    let body = String::from_utf8(form.to_bytes()).unwrap();
+
+    assert_eq!(
+        body,
+        format!(
+            "--{b}\r\n\
+             Content-Disposition: form-data; name=\"name\"\r\n\r\n\
+             plat\r\n\
+             --{b}\r\n\
+             Content-Disposition: form-data; name=\"file\"; \
+             filename=\"plat.png\"\r\n\
+             Content-Type: image/png\r\n\r\n\
+             data\r\n\
+             --{b}--\r\n",
+            b = boundary
+        )
+    );
+}
+
+#[test]
+fn multipart_form_is_cloneable() {
+    let form = MultipartForm::new().text("name", "plat");
+    assert_eq!(form.clone().to_bytes(), form.to_bytes());
+}
@@ -0,0 +1,33 @@
+use reqwest::{header::RETRY_AFTER, Client, Response};
+use std::time::Duration;
+use wfbp_http::middleware::{
+    RestRequestBuilder, RetryPolicy, TransientRequestRetryPolicy,
+};
+
+fn retry_after(value: &str) -> Option<Duration> {
+    let builder = Client::new().get("http://localhost/");
+    let request = RestRequestBuilder::new(&builder).unwrap();
+    let response = http::Response::builder()
+        .status(429)
+        .header(RETRY_AFTER, value)
+        .body("")
+        .unwrap();
+    TransientRequestRetryPolicy::default()
+        .honoring_retry_after()
+        .retry_after(&request, &Response::from(response))
+}
+
+#[test]
+fn retry_after_is_read() {
+    assert_eq!(retry_after("1.5"), Some(Duration::from_millis(1500)));
+    assert_eq!(retry_after("-1"), None);
+    assert_eq!(retry_after("soon"), None);
+}
+
+#[test]
+fn huge_retry_after_is_capped() {
+    let max = TransientRequestRetryPolicy::MAX_RETRY_AFTER;
+    assert_eq!(retry_after("60"), Some(max));
+    assert_eq!(retry_after("1e20"), Some(max));
+    assert_eq!(retry_after("1e400"), None);
+}
@@ -0,0 +1,164 @@
+use png::{BitDepth, ColorType, Encoder};
+
+/// The fewest prices needed to render a chart.
+pub const MIN_CHART_PRICES: usize = 3;
+
+const WIDTH: u32 = 480;
+const HEIGHT: u32 = 240;
+const MARGIN: u32 = 16;
+const MAX_BINS: u32 = 20;
+
+/// Each glyph is 3x5 dots, drawn this many pixels wide.
+const GLYPH_SCALE: u32 = 2;
+const GLYPH_WIDTH: u32 = 3 * GLYPH_SCALE;
+const GLYPH_HEIGHT: u32 = 5 * GLYPH_SCALE;
+const GLYPH_ADVANCE: u32 = GLYPH_WIDTH + GLYPH_SCALE;
+/// Space between the axes and their labels.
+const LABEL_GAP: u32 = 4;
+/// The most digits an order count label needs room for.
+const MAX_COUNT_DIGITS: u32 = 5;
+
+const BACKGROUND: [u8; 3] = [0x2f, 0x31, 0x36];
+const AXIS: [u8; 3] = [0x8e, 0x92, 0x97];
+const BAR: [u8; 3] = [0x58, 0x65, 0xf2];
+
+/// Renders a histogram of prices as a PNG image, or [`None`] if there are too
+/// few prices to be useful. The lowest and highest prices are labeled under
+/// the bars, and the tallest bar's order count next to the vertical axis.
+pub fn price_histogram(prices: &[u32]) -> Option<Vec<u8>> {
+    if prices.len() < MIN_CHART_PRICES {
+        return None;
+    }
+
+    // Bucket prices
+    let min = *prices.iter().min()?;
+    let max = *prices.iter().max()?;
+    let span = (max - min).saturating_add(1);
+    let bin_count = span.min(MAX_BINS);
+    let bin_width = span as f64 / bin_count as f64;
+    let mut bins = vec![0u32; bin_count as usize];
+    for &price in prices {
+        let bin = ((price - min) as f64 / bin_width) as usize;
+        bins[bin.min(bin_count as usize - 1)] += 1;
+    }
+    let tallest = *bins.iter().max()?;
+
+    // Draw
+    let mut canvas = Canvas::new(WIDTH, HEIGHT, BACKGROUND);
+    let left = MARGIN + MAX_COUNT_DIGITS * GLYPH_ADVANCE + LABEL_GAP;
+    let bottom = HEIGHT - MARGIN - GLYPH_HEIGHT - LABEL_GAP;
+    let plot_width = WIDTH - MARGIN - left;
+    let plot_height = bottom - MARGIN;
+    let bar_width = plot_width / bin_count;
+    for (index, &count) in bins.iter().enumerate() {
+        let height = count * plot_height / tallest;
+        let bar_left = left + index as u32 * bar_width;
+        canvas.fill(bar_left + 1, bottom - height, bar_width - 1, height, BAR);
+    }
+    canvas.fill(left, MARGIN, 1, plot_height, AXIS);
+    canvas.fill(left, bottom, plot_width, 1, AXIS);
+
+    // Label the price range and order counts
+    let labels_top = bottom + 1 + LABEL_GAP;
+    let plot_right = left + bin_count * bar_width;
+    let max_label = format!("{max}p");
+    canvas.text(left, labels_top, &format!("{min}p"), AXIS);
+    if max != min {
+        let max_left = plot_right.saturating_sub(text_width(&max_label));
+        canvas.text(max_left, labels_top, &max_label, AXIS);
+    }
+    let count_label = tallest.to_string();
+    let count_left =
+        (left - LABEL_GAP).saturating_sub(text_width(&count_label));
+    canvas.text(count_left, MARGIN, &count_label, AXIS);
+
+    Some(canvas.to_png())
+}
+
+/// How wide some text is when drawn.
+fn text_width(text: &str) -> u32 {
+    (text.chars().count() as u32 * GLYPH_ADVANCE).saturating_sub(GLYPH_SCALE)
+}
+
+/// The dots in each row of a character, with the leftmost dot in the highest
+/// bit. Only the characters needed for price labels are supported.
+fn glyph(c: char) -> Option<[u8; 5]> {
+    let rows = match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'p' => [0b111, 0b101, 0b111, 0b100, 0b100],
+        _ => return None,
+    };
+
+    Some(rows)
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: [u8; 3]) -> Self {
+        let pixels = background
+            .iter()
+            .copied()
+            .cycle()
+            .take((width * height * 3) as usize)
+            .collect();
+        Canvas {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, rgb: [u8; 3]) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                let offset = ((row * self.width + column) * 3) as usize;
+                self.pixels[offset..offset + 3].copy_from_slice(&rgb);
+            }
+        }
+    }
+
+    fn text(&mut self, x: u32, y: u32, text: &str, rgb: [u8; 3]) {
+        for (index, c) in text.chars().enumerate() {
+            let left = x + index as u32 * GLYPH_ADVANCE;
+            for (row, dots) in glyph(c).into_iter().flatten().enumerate() {
+                for column in 0..3 {
+                    if dots & (0b100 >> column) != 0 {
+                        self.fill(
+                            left + column * GLYPH_SCALE,
+                            y + row as u32 * GLYPH_SCALE,
+                            GLYPH_SCALE,
+                            GLYPH_SCALE,
+                            rgb,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn to_png(&self) -> Vec<u8> {
+        let mut png = Vec::new();
+        let mut encoder = Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .expect("error encoding image");
+        png
+    }
+}
@@ -1,5 +1,16 @@
 mod admin;
+mod context;
+mod help;
 mod pc;
 
 pub use admin::*;
-pub use pc::*;
+pub use context::*;
+pub use help::*;
+pub use pc::pc_command;
+
+/// Internals of `/pc` that are exposed for integration tests.
+#[cfg(feature = "test-util")]
+pub use pc::{
+    error_details_response, item_price_message, item_url, lookup_price_message,
+    seller_orders_message, whisper_message,
+};
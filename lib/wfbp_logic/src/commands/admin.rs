@@ -73,10 +73,7 @@ async fn reset_commands(
         &discord_client,
         app_id,
         interaction_data.token.clone(),
-        CreateWebhookMessage {
-            content: Some("Done!".into()),
-            ..Default::default()
-        },
+        CreateWebhookMessage::builder().content("Done!").build(),
     )
     .await
     .context("error creating response")?;
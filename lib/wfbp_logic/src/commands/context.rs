@@ -0,0 +1,16 @@
+use crate::services::{PlatinumEmojiService, WarframeItemService};
+use wfbp_discord::{models::Snowflake, DiscordRestClient};
+use wfbp_wm::WmRestClient;
+
+/// Dependencies shared by command callbacks.
+#[derive(Clone, Debug)]
+pub struct CommandContext {
+    pub discord_client: DiscordRestClient,
+    pub wm_client: WmRestClient,
+    pub item_service: WarframeItemService,
+    pub platinum_emoji: PlatinumEmojiService,
+    pub app_id: Snowflake,
+    /// The user who can run admin commands anywhere, in addition to members
+    /// with the Manage Server permission.
+    pub owner_id: Option<Snowflake>,
+}
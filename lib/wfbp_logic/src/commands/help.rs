@@ -0,0 +1,88 @@
+use anyhow::bail;
+use async_trait::async_trait;
+use std::sync::{Arc, Weak};
+use tokio::sync::RwLock;
+use wfbp_commands::{
+    AutoDeferCallback, CommandBuilder, CommandOptionRegistry, CommandRegistry,
+    HandleInteractionError, InteractionData, ResponseBudget, ResponseCallback,
+    SlashCommand, SlashCommandData,
+};
+use wfbp_discord::{
+    models::{
+        AllowedMentions, CreateWebhookMessage, CreateWebhookMessageBuilder,
+    },
+    DiscordRestClient,
+};
+
+pub fn help_command(
+    discord_client: DiscordRestClient,
+    command_registry: Arc<RwLock<Option<Weak<CommandRegistry>>>>,
+) -> SlashCommand {
+    // The dispatcher defers /help before it reaches the processor
+    let callback = AutoDeferCallback::new(
+        HelpCallback { command_registry },
+        discord_client,
+        ResponseBudget::new(),
+    )
+    .already_deferred();
+
+    CommandBuilder::new()
+        .name("help")
+        .description("Shows detailed help for the bot's commands")
+        .default_permission(true)
+        .optional_string("command", "The command to show help for")
+        .example("/help")
+        .example("/help command: pc")
+        .callback(callback)
+        .build()
+}
+
+struct HelpCallback {
+    command_registry: Arc<RwLock<Option<Weak<CommandRegistry>>>>,
+}
+
+#[async_trait]
+impl ResponseCallback for HelpCallback {
+    async fn respond<'a>(
+        &self,
+        _interaction_data: Arc<InteractionData>,
+        _invoke_data: &'a SlashCommandData,
+        options: CommandOptionRegistry<'a>,
+    ) -> Result<CreateWebhookMessageBuilder, HandleInteractionError> {
+        let message = help(options, &self.command_registry).await?;
+        Ok(message)
+    }
+}
+
+async fn help<'opts>(
+    options: CommandOptionRegistry<'opts>,
+    command_registry: &RwLock<Option<Weak<CommandRegistry>>>,
+) -> anyhow::Result<CreateWebhookMessageBuilder> {
+    let command: Option<&str> = options.get_optional_option("command")?;
+    let command_registry = command_registry.read().await;
+    let command_registry = match command_registry.as_ref() {
+        None => bail!("command registry not set"),
+        Some(command_registry) => command_registry,
+    };
+    let command_registry = match command_registry.upgrade() {
+        None => bail!("command registry is already dropped"),
+        Some(command_registry) => command_registry,
+    };
+
+    let message = match command {
+        None => CreateWebhookMessage::builder()
+            .embed(command_registry.help_overview().await),
+        Some(name) => {
+            let name = name.trim().trim_start_matches('/').to_lowercase();
+            match command_registry.help_embed(&name).await {
+                Some(embed) => CreateWebhookMessage::builder().embed(embed),
+                None => CreateWebhookMessage::builder()
+                    .content(format!("There is no command named `{}`.", name)),
+            }
+        }
+    };
+
+    Ok(message
+        .allowed_mentions(AllowedMentions::none())
+        .ephemeral())
+}
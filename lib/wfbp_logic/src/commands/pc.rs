@@ -1,96 +1,108 @@
-use crate::services::WarframeItemService;
+use crate::{
+    commands::CommandContext,
+    localization::{Language, Text},
+    services::WarframeItemService,
+};
 use anyhow::{bail, Context};
-use std::{borrow::Cow, fmt::Write, str::FromStr, sync::Arc};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Write},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{error, warn};
 use wfbp_commands::{
-    create_callback, Choice, CommandBuilder, CommandOptionRegistry,
+    create_callback, BoundedI64, Choice, CommaSeparated, CommandBuilder,
+    CommandCallback, CommandOptionRegistry, CooldownCallback, CooldownTracker,
     InteractionData, SlashCommand,
 };
 use wfbp_discord::{
     models::{
-        AllowedMentions, CreateWebhookMessage, Embed, EmbedField,
-        EmbedThumbnail, MessageFlags, Snowflake,
+        AllowedMentions, CreateWebhookMessage, Embed, EmbedField, EmbedFooter,
+        EmbedThumbnail, Permissions, Snowflake, Timestamp,
     },
     routes::CreateFollowupMessage,
-    DiscordRestClient,
 };
+#[cfg(feature = "chart")]
+use wfbp_discord::{
+    models::{AttachmentFile, CreateWebhookMessageWithFiles, EmbedImage},
+    routes::CreateFollowupMessageWithFiles,
+};
+use wfbp_http::{PathSegment, RequestError};
 use wfbp_wm::{
     models::{
-        ItemFull, ItemOrder, ItemOrdersPayload, ItemPayload, ItemRank,
-        OrderType, PayloadResponse, Platform, RelicRefinement, UserStatus,
+        ItemFull, ItemOrder, ItemRank, ItemType, OrderType, Platform,
+        RelicRefinement, UserStatus,
     },
-    routes::GetItemOrders,
+    routes::GetProfileOrders,
+    FetchedItemOrders, ItemOrders, WmErrorKind, WmRequestErrorExt,
     WmRestClient,
 };
 
 const WM_BASE_URL: &'static str = "https://warframe.market";
+/// The rank to get mod and arcane prices for when no rank is given.
+const DEFAULT_MOD_RANK: u8 = 0;
 const WM_ASSETS_ROOT: &'static str = "http://warframe.market/static/assets/";
-const PLAT: &'static str = "<:WFPlatinum:380292389798936579>";
-
-pub fn pc_command(
-    discord_client: DiscordRestClient,
-    wm_client: WmRestClient,
-    item_service: WarframeItemService,
-    app_id: Snowflake,
-) -> SlashCommand {
-    let pc_items_callback = create_callback! {
-        capture: {
-            discord_client: DiscordRestClient = discord_client.clone(),
-            wm_client: WmRestClient = wm_client.clone(),
-            item_service: WarframeItemService = item_service.clone(),
-            app_id: Snowflake = app_id,
-        },
-        handler: async |interaction_data, _, options| {
-            pc_items(interaction_data, options, discord_client, wm_client, item_service, app_id).await
-        },
-    };
-    let pc_mod_callback = create_callback! {
-        capture: {
-            discord_client: DiscordRestClient = discord_client.clone(),
-            wm_client: WmRestClient = wm_client.clone(),
-            item_service: WarframeItemService = item_service.clone(),
-            app_id: Snowflake = app_id,
-        },
-        handler: async |interaction_data, _, options| {
-            pc_mod_or_arcane(interaction_data, options, discord_client, wm_client, item_service, app_id).await
-        },
-    };
-    let pc_arcane_callback = create_callback! {
-        capture: {
-            discord_client: DiscordRestClient = discord_client.clone(),
-            wm_client: WmRestClient = wm_client.clone(),
-            item_service: WarframeItemService = item_service.clone(),
-            app_id: Snowflake = app_id,
-        },
-        handler: async |interaction_data, _, options| {
-            pc_mod_or_arcane(interaction_data, options, discord_client, wm_client, item_service, app_id).await
-        },
-    };
-    let pc_relic_callback = create_callback! {
-        capture: {
-            discord_client: DiscordRestClient = discord_client.clone(),
-            wm_client: WmRestClient = wm_client.clone(),
-            item_service: WarframeItemService = item_service.clone(),
-            app_id: Snowflake = app_id,
-        },
-        handler: async |interaction_data, _, options| {
-            pc_relic(interaction_data, options, discord_client, wm_client, item_service, app_id).await
+const MAX_COMPARE_ITEMS: usize = 5;
+const MAX_SELLER_ORDERS: usize = 20;
+/// Appended to error details which were too long to show in full.
+const TRUNCATED_MARKER: &str = "…(truncated)";
+
+pub fn pc_command(ctx: CommandContext, cooldown: Duration) -> SlashCommand {
+    let cooldown = CooldownTracker::new(cooldown);
+
+    // Every subcommand except refreshing items shares the same cooldown
+    macro_rules! cooldown_callback {
+        ($handler:ident) => {
+            with_cooldown(
+                &ctx,
+                &cooldown,
+                create_callback! {
+                    context: ctx: CommandContext = ctx.clone(),
+                    handler: async |interaction_data, _, options| {
+                        $handler(interaction_data, options, ctx).await
+                    },
+                },
+            )
+        };
+    }
+
+    let pc_items_callback = cooldown_callback!(pc_items);
+    let pc_mod_callback = cooldown_callback!(pc_mod_or_arcane);
+    let pc_arcane_callback = cooldown_callback!(pc_mod_or_arcane);
+    let pc_relic_callback = cooldown_callback!(pc_relic);
+    let pc_lookup_callback = cooldown_callback!(pc_lookup);
+    let pc_seller_callback = cooldown_callback!(pc_seller);
+    let pc_compare_callback = cooldown_callback!(pc_compare);
+    let pc_refresh_items_callback = create_callback! {
+        context: ctx: CommandContext = ctx.clone(),
+        handler: async |interaction_data, _, _| {
+            pc_refresh_items(interaction_data, ctx).await
         },
     };
+    #[cfg(feature = "chart")]
+    let pc_chart_callback = cooldown_callback!(pc_chart);
 
-    CommandBuilder::new()
+    let builder = CommandBuilder::new()
         .name("pc")
         .description("Checks warframe.market for the price of an item")
+        .long_help(
+            "Checks warframe.market for the lowest sell orders of an item. \
+            Use the subcommand for the kind of item to filter orders by mod \
+            rank or relic refinement, or `compare` to check several items at \
+            once.",
+        )
+        .example("/pc item name: ash prime set")
+        .example("/pc mod name: primed continuity rank: 10")
+        .example("/pc relic name: lith a1 refinement: radiant")
+        .example("/pc compare items: soma prime set, braton prime set")
         .default_permission(true)
         .subcommand_option(|builder| {
             builder
                 .name("item")
                 .description("Searches for the price of a normal item (blueprint, part, etc.)")
-                .string_option(|builder| {
-                    builder
-                        .name("name")
-                        .description("The name of the item to get the price of")
-                        .required(true)
-                })
+                .required_string("name", "The name of the item to get the price of")
                 .string_option(|builder| {
                     builder.name("platform")
                         .description("The platform")
@@ -102,11 +114,7 @@ pub fn pc_command(
         .subcommand_option(|builder| {
             builder.name("mod")
                 .description("Searches for the price of a mod")
-                .string_option(|builder| {
-                    builder.name("name")
-                        .description("The name of the mod to get the price of")
-                        .required(true)
-                })
+                .required_string("name", "The name of the mod to get the price of")
                 .string_option(|builder| {
                     builder.name("platform")
                         .description("The platform")
@@ -115,7 +123,7 @@ pub fn pc_command(
                 })
                 .integer_option(|builder| {
                     builder.name("rank")
-                        .description("The rank of the mod")
+                        .description("The rank of the mod (default: 0)")
                         .required(false)
                 })
                 .callback(pc_mod_callback)
@@ -123,11 +131,7 @@ pub fn pc_command(
         .subcommand_option(|builder| {
             builder.name("arcane")
                 .description("Searches for the price of an arcane")
-                .string_option(|builder| {
-                    builder.name("name")
-                        .description("The name of the arcane to get the price of")
-                        .required(true)
-                })
+                .required_string("name", "The name of the arcane to get the price of")
                 .string_option(|builder| {
                     builder.name("platform")
                         .description("The platform")
@@ -136,7 +140,7 @@ pub fn pc_command(
                 })
                 .integer_option(|builder| {
                     builder.name("rank")
-                        .description("The rank of the arcane")
+                        .description("The rank of the arcane (default: 0)")
                         .required(false)
                 })
                 .callback(pc_arcane_callback)
@@ -144,11 +148,7 @@ pub fn pc_command(
         .subcommand_option(|builder| {
             builder.name("relic")
                 .description("Searches for the price of a relic")
-                .string_option(|builder| {
-                    builder.name("name")
-                        .description("The name of the relic to get the price of")
-                        .required(true)
-                })
+                .required_string("name", "The name of the relic to get the price of")
                 .string_option(|builder| {
                     builder.name("platform")
                         .description("The platform")
@@ -158,12 +158,73 @@ pub fn pc_command(
                 .string_option(|builder| {
                     builder.name("refinement")
                         .description("The refinement level of the relic")
-                        .choices(RelicRefinementChoice::choices().into_iter().collect())
+                        .choices(refinement_choices())
                         .required(false)
                 })
                 .callback(pc_relic_callback)
         })
-        .build()
+        .subcommand_option(|builder| {
+            builder.name("lookup")
+                .description("Searches for the price of any item, mod, arcane or relic")
+                .required_string("name", "The name of the item to get the price of")
+                .string_option(|builder| {
+                    builder.name("platform")
+                        .description("The platform")
+                        .choices(PlatformChoice::choices().into_iter().collect())
+                        .required(false)
+                })
+                .callback(pc_lookup_callback)
+        })
+        .subcommand_option(|builder| {
+            builder.name("seller")
+                .description("Lists what a trader is selling")
+                .required_string("name", "The in-game name of the trader")
+                .string_option(|builder| {
+                    builder.name("platform")
+                        .description("The platform")
+                        .choices(PlatformChoice::choices().into_iter().collect())
+                        .required(false)
+                })
+                .callback(pc_seller_callback)
+        })
+        .subcommand_option(|builder| {
+            builder.name("compare")
+                .description("Compares the prices of several items")
+                .required_string("items", format!("Comma-separated names of up to {MAX_COMPARE_ITEMS} items to compare"))
+                .string_option(|builder| {
+                    builder.name("platform")
+                        .description("The platform")
+                        .choices(PlatformChoice::choices().into_iter().collect())
+                        .required(false)
+                })
+                .callback(pc_compare_callback)
+        })
+        .subcommand_option(|builder| {
+            builder.name("refresh-items")
+                .description("Refreshes the list of tradeable items (admin only)")
+                .callback(pc_refresh_items_callback)
+        });
+
+    #[cfg(feature = "chart")]
+    let builder = builder.subcommand_option(|builder| {
+        builder
+            .name("chart")
+            .description("Charts the distribution of prices for an item")
+            .required_string(
+                "name",
+                "The name of the item to chart the prices of",
+            )
+            .string_option(|builder| {
+                builder
+                    .name("platform")
+                    .description("The platform")
+                    .choices(PlatformChoice::choices().into_iter().collect())
+                    .required(false)
+            })
+            .callback(pc_chart_callback)
+    });
+
+    builder.build()
 }
 
 macro_rules! enum_choice {
@@ -215,25 +276,46 @@ macro_rules! enum_choice {
     };
 }
 
-enum_choice! {
-    #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-    enum RelicRefinementChoice {
-        Intact = "intact",
-        Exceptional = "exceptional",
-        Flawless = "flawless",
-        Radiant = "radiant",
-    }
+/// Wraps a callback so users must wait between uses of the command.
+fn with_cooldown<C: CommandCallback>(
+    ctx: &CommandContext,
+    cooldown: &CooldownTracker,
+    callback: C,
+) -> CooldownCallback<C> {
+    CooldownCallback::new(
+        callback,
+        cooldown.clone(),
+        ctx.discord_client.clone(),
+        ctx.app_id,
+    )
+    .notice(cooldown_notice)
 }
 
-impl From<RelicRefinementChoice> for RelicRefinement {
-    fn from(refinement: RelicRefinementChoice) -> Self {
-        match refinement {
-            RelicRefinementChoice::Intact => RelicRefinement::Intact,
-            RelicRefinementChoice::Exceptional => RelicRefinement::Exceptional,
-            RelicRefinementChoice::Flawless => RelicRefinement::Flawless,
-            RelicRefinementChoice::Radiant => RelicRefinement::Radiant,
-        }
-    }
+/// Tells a user on cooldown how long to wait, in their own language.
+fn cooldown_notice(
+    interaction_data: &InteractionData,
+    remaining: Duration,
+) -> String {
+    let lang = Language::from_locale(interaction_data.locale.as_deref());
+    let seconds = remaining.as_secs_f32().ceil().to_string();
+    lang.format(Text::CommandOnCooldown, &[("seconds", &seconds)])
+}
+
+/// The relic refinements users can choose from. The values are the names
+/// warframe.market uses.
+fn refinement_choices() -> Vec<Choice<Cow<'static, str>>> {
+    RelicRefinement::ALL
+        .into_iter()
+        .map(|refinement| {
+            let value = refinement.name();
+            let mut name = value[..1].to_uppercase();
+            name.push_str(&value[1..]);
+            Choice {
+                name: name.into(),
+                value: value.into(),
+            }
+        })
+        .collect()
 }
 
 enum_choice! {
@@ -260,10 +342,7 @@ impl From<PlatformChoice> for Platform {
 async fn pc_items<'opts>(
     interaction_data: Arc<InteractionData>,
     options: CommandOptionRegistry<'opts>,
-    discord_client: &DiscordRestClient,
-    wm_client: &WmRestClient,
-    item_service: &WarframeItemService,
-    app_id: &Snowflake,
+    ctx: &CommandContext,
 ) -> anyhow::Result<()> {
     // Get options
     let item_name: &str = options.get_option("name")?;
@@ -278,10 +357,7 @@ async fn pc_items<'opts>(
 
     pc_filtered(
         interaction_data,
-        discord_client,
-        wm_client,
-        item_service,
-        app_id,
+        ctx,
         &item_name,
         OrderFilters {
             platform,
@@ -294,15 +370,14 @@ async fn pc_items<'opts>(
 async fn pc_mod_or_arcane<'opts>(
     interaction_data: Arc<InteractionData>,
     options: CommandOptionRegistry<'opts>,
-    discord_client: &DiscordRestClient,
-    wm_client: &WmRestClient,
-    item_service: &WarframeItemService,
-    app_id: &Snowflake,
+    ctx: &CommandContext,
 ) -> anyhow::Result<()> {
     // Get options
     let item_name: &str = options.get_option("name")?;
     let item_name = item_name.to_lowercase();
-    let rank = options.get_optional_option("rank")?;
+    let rank = options
+        .get_optional_option("rank")?
+        .map_or(DEFAULT_MOD_RANK, |rank: BoundedI64<0, 10>| rank.get() as u8);
     let platform = options
         .get_optional_option("platform")
         .context("error getting platform")?
@@ -313,10 +388,7 @@ async fn pc_mod_or_arcane<'opts>(
 
     pc_filtered(
         interaction_data,
-        discord_client,
-        wm_client,
-        item_service,
-        app_id,
+        ctx,
         &item_name,
         OrderFilters {
             platform,
@@ -329,10 +401,7 @@ async fn pc_mod_or_arcane<'opts>(
 async fn pc_relic<'opts>(
     interaction_data: Arc<InteractionData>,
     options: CommandOptionRegistry<'opts>,
-    discord_client: &DiscordRestClient,
-    wm_client: &WmRestClient,
-    item_service: &WarframeItemService,
-    app_id: &Snowflake,
+    ctx: &CommandContext,
 ) -> anyhow::Result<()> {
     // Get options
     let item_name: &str = options.get_option("name")?;
@@ -340,10 +409,9 @@ async fn pc_relic<'opts>(
     let refinement = options
         .get_optional_option("refinement")
         .context("error getting refinement")?
-        .map(|refinement: &str| refinement.parse())
+        .map(|refinement: &str| refinement.parse::<RelicRefinement>())
         .transpose()
-        .context("error parsing refinement")?
-        .map(RelicRefinementChoice::into);
+        .context("error parsing refinement")?;
     let platform = options
         .get_optional_option("platform")
         .context("error getting platform")?
@@ -354,10 +422,7 @@ async fn pc_relic<'opts>(
 
     pc_filtered(
         interaction_data,
-        discord_client,
-        wm_client,
-        item_service,
-        app_id,
+        ctx,
         &item_name,
         OrderFilters {
             platform,
@@ -367,28 +432,554 @@ async fn pc_relic<'opts>(
     .await
 }
 
-async fn pc_filtered<'opts>(
+async fn pc_lookup<'opts>(
     interaction_data: Arc<InteractionData>,
-    discord_client: &DiscordRestClient,
+    options: CommandOptionRegistry<'opts>,
+    ctx: &CommandContext,
+) -> anyhow::Result<()> {
+    // Get options
+    let item_name: &str = options.get_option("name")?;
+    let item_name = item_name.to_lowercase();
+    let platform = options
+        .get_optional_option("platform")
+        .context("error getting platform")?
+        .map(|platform: &str| platform.parse())
+        .transpose()
+        .context("error parsing platform")?
+        .map(PlatformChoice::into);
+
+    pc_filtered(
+        interaction_data,
+        ctx,
+        &item_name,
+        OrderFilters {
+            platform,
+            rank: RankFilter::Infer,
+        },
+    )
+    .await
+}
+
+async fn pc_seller<'opts>(
+    interaction_data: Arc<InteractionData>,
+    options: CommandOptionRegistry<'opts>,
+    ctx: &CommandContext,
+) -> anyhow::Result<()> {
+    // Get options
+    let ingame_name: &str = options.get_option("name")?;
+    let platform = options
+        .get_optional_option("platform")
+        .context("error getting platform")?
+        .map(|platform: &str| platform.parse())
+        .transpose()
+        .context("error parsing platform")?
+        .map(PlatformChoice::into);
+
+    // Get message
+    let lang = Language::from_locale(interaction_data.locale.as_deref());
+    let plat = ctx.platinum_emoji.resolve(interaction_data.guild_id);
+    let message = seller_orders_message(
+        &ctx.wm_client,
+        ingame_name,
+        platform,
+        &plat,
+        lang,
+    )
+    .await
+    .unwrap_or_else(|error| error_details_response(lang, &error));
+
+    // Send response
+    CreateFollowupMessage::execute(
+        &ctx.discord_client,
+        ctx.app_id,
+        interaction_data.token.clone(),
+        checked_message(lang, message),
+    )
+    .await
+    .context("error creating response")?;
+
+    Ok(())
+}
+
+async fn pc_refresh_items(
+    interaction_data: Arc<InteractionData>,
+    ctx: &CommandContext,
+) -> anyhow::Result<()> {
+    let lang = Language::from_locale(interaction_data.locale.as_deref());
+    let message = if can_refresh_items(&interaction_data, ctx.owner_id) {
+        let start = Instant::now();
+        match ctx.item_service.refresh(&ctx.wm_client).await {
+            Ok(()) => {
+                let count = ctx.item_service.item_count().to_string();
+                let duration = format!("{:.2?}", start.elapsed());
+                CreateWebhookMessage::builder()
+                    .content(lang.format(
+                        Text::ItemsIndexed,
+                        &[("count", &count), ("duration", &duration)],
+                    ))
+                    .ephemeral()
+                    .build()
+            }
+            Err(error) => error_details_response(lang, &error),
+        }
+    } else {
+        error_response(lang, lang.text(Text::RefreshItemsForbidden))
+    };
+
+    // Send response
+    CreateFollowupMessage::execute(
+        &ctx.discord_client,
+        ctx.app_id,
+        interaction_data.token.clone(),
+        checked_message(lang, message),
+    )
+    .await
+    .context("error creating response")?;
+
+    Ok(())
+}
+
+/// Whether the user who sent an interaction can refresh the item index. The
+/// owner can refresh it anywhere, and members can refresh it if they can
+/// manage the guild.
+fn can_refresh_items(
+    interaction_data: &InteractionData,
+    owner_id: Option<Snowflake>,
+) -> bool {
+    let member = interaction_data.member.as_ref();
+    let user = member
+        .and_then(|member| member.user())
+        .or(interaction_data.user.as_ref());
+    let is_owner = owner_id
+        .zip(user)
+        .filter(|(owner_id, user)| user.id() == *owner_id)
+        .is_some();
+    let can_manage_guild = member
+        .and_then(|member| member.permissions())
+        .filter(|permissions| permissions.contains(Permissions::MANAGE_GUILD))
+        .is_some();
+
+    is_owner || can_manage_guild
+}
+
+/// Gets the response listing a trader's sell orders, as if it were requested
+/// with `/pc seller`.
+pub async fn seller_orders_message(
     wm_client: &WmRestClient,
-    item_service: &WarframeItemService,
-    app_id: &Snowflake,
-    item_name: &str,
-    order_filters: OrderFilters,
+    ingame_name: &str,
+    platform: Option<Platform>,
+    plat: &str,
+    lang: Language,
+) -> anyhow::Result<CreateWebhookMessage> {
+    // Get orders
+    let response =
+        GetProfileOrders::execute(wm_client, ingame_name.into()).await;
+    let response = match response {
+        Err(error) if error.is_not_found() => {
+            return Ok(error_response(
+                lang,
+                lang.format(Text::NoUserNamed, &[("name", ingame_name)]),
+            ));
+        }
+        Err(error) => match market_error_text(&error) {
+            Some(text) => return Ok(error_response(lang, lang.text(text))),
+            None => return Err(error).context("error getting profile orders"),
+        },
+        Ok(response) => response,
+    };
+
+    let mut orders: Vec<_> = response
+        .payload
+        .sell_orders
+        .iter()
+        .filter(|order| platform.is_none() || platform == Some(order.platform))
+        .collect();
+    if orders.is_empty() {
+        return Ok(error_response(lang, lang.text(Text::NoOrdersFound)));
+    }
+    orders.sort_by(|a, b| {
+        a.item
+            .en
+            .item_name
+            .cmp(&b.item.en.item_name)
+            .then_with(|| a.platinum.cmp(&b.platinum))
+    });
+
+    // Build response
+    let mut description = String::new();
+    for order in orders.iter().take(MAX_SELLER_ORDERS) {
+        let rank = match order.rank {
+            ItemRank::ModOrArcane { mod_rank, .. } => {
+                format!(" (rank {mod_rank})")
+            }
+            ItemRank::Relic { refinement, .. } => {
+                format!(" ({refinement})")
+            }
+            ItemRank::Item {} => String::new(),
+        };
+        writeln!(
+            description,
+            "**{item}**{rank}: {cost}{plat} × {quantity}",
+            item = order.item.en.item_name,
+            cost = order.platinum,
+            quantity = order.quantity,
+        )
+        .unwrap();
+    }
+    if orders.len() > MAX_SELLER_ORDERS {
+        let more = (orders.len() - MAX_SELLER_ORDERS).to_string();
+        writeln!(
+            description,
+            "{}",
+            lang.format(Text::MoreOrders, &[("count", &more)])
+        )
+        .unwrap();
+    }
+
+    let message = CreateWebhookMessage::builder()
+        .embed(Embed {
+            title: Some(
+                lang.format(Text::SellOrdersBy, &[("name", ingame_name)]),
+            ),
+            url: Some(format!(
+                "{WM_BASE_URL}/profile/{}",
+                PathSegment::from(ingame_name)
+            )),
+            description: Some(description),
+            footer: Some(market_footer(lang)),
+            timestamp: Some(Timestamp::now()),
+            ..Default::default()
+        })
+        .allowed_mentions(AllowedMentions::none())
+        .build();
+    Ok(message)
+}
+
+async fn pc_compare<'opts>(
+    interaction_data: Arc<InteractionData>,
+    options: CommandOptionRegistry<'opts>,
+    ctx: &CommandContext,
 ) -> anyhow::Result<()> {
+    // Get options
+    let CommaSeparated(item_names): CommaSeparated<String> =
+        options.get_option("items")?;
+    let platform = options
+        .get_optional_option("platform")
+        .context("error getting platform")?
+        .map(|platform: &str| platform.parse())
+        .transpose()
+        .context("error parsing platform")?
+        .map(PlatformChoice::into);
+
     // Get message
-    let message = process(wm_client, item_service, &item_name, order_filters)
+    let lang = Language::from_locale(interaction_data.locale.as_deref());
+    let plat = ctx.platinum_emoji.resolve(interaction_data.guild_id);
+    let message = if item_names.is_empty() {
+        error_response(lang, lang.text(Text::NoItemsToCompare))
+    } else if item_names.len() > MAX_COMPARE_ITEMS {
+        let max = MAX_COMPARE_ITEMS.to_string();
+        error_response(lang, lang.format(Text::TooManyItems, &[("max", &max)]))
+    } else {
+        // Requests are limited by the client, so these can run concurrently
+        let order_filters = OrderFilters {
+            platform,
+            rank: RankFilter::Infer,
+        };
+        let rows = futures::future::join_all(
+            item_names
+                .iter()
+                .map(|name| compare_item(ctx, name, &order_filters, lang)),
+        )
+        .await;
+        create_compare_response(&item_names, rows, &plat, lang)
+    };
+
+    // Send response
+    CreateFollowupMessage::execute(
+        &ctx.discord_client,
+        ctx.app_id,
+        interaction_data.token.clone(),
+        checked_message(lang, message),
+    )
+    .await
+    .context("error creating response")?;
+
+    Ok(())
+}
+
+#[cfg(feature = "chart")]
+async fn pc_chart<'opts>(
+    interaction_data: Arc<InteractionData>,
+    options: CommandOptionRegistry<'opts>,
+    ctx: &CommandContext,
+) -> anyhow::Result<()> {
+    // Get options
+    let item_name: &str = options.get_option("name")?;
+    let item_name = item_name.to_lowercase();
+    let platform = options
+        .get_optional_option("platform")
+        .context("error getting platform")?
+        .map(|platform: &str| platform.parse())
+        .transpose()
+        .context("error parsing platform")?
+        .map(PlatformChoice::into);
+
+    // Get message
+    let lang = Language::from_locale(interaction_data.locale.as_deref());
+    let plat = ctx.platinum_emoji.resolve(interaction_data.guild_id);
+    let order_filters = OrderFilters {
+        platform,
+        rank: RankFilter::Infer,
+    };
+    let (message, chart) =
+        process_chart(ctx, &item_name, order_filters, &plat, lang)
+            .await
+            .unwrap_or_else(|error| {
+                let message = error_details_response(lang, &error);
+                (message, None)
+            });
+
+    // Send response, falling back to the text embed if there is no chart
+    let message = checked_message(lang, message);
+    match chart {
+        Some(chart) => CreateFollowupMessageWithFiles::execute(
+            &ctx.discord_client,
+            ctx.app_id,
+            interaction_data.token.clone(),
+            CreateWebhookMessageWithFiles {
+                message,
+                files: vec![chart],
+            },
+        )
+        .await
+        .context("error creating response")?,
+        None => CreateFollowupMessage::execute(
+            &ctx.discord_client,
+            ctx.app_id,
+            interaction_data.token.clone(),
+            message,
+        )
         .await
-        .unwrap_or_else(|error| {
-            error_response(format!("```\n{:#?}\n```", error))
+        .context("error creating response")?,
+    };
+
+    Ok(())
+}
+
+#[cfg(feature = "chart")]
+async fn process_chart(
+    ctx: &CommandContext,
+    item_name: &str,
+    order_filters: OrderFilters,
+    plat: &str,
+    lang: Language,
+) -> anyhow::Result<(CreateWebhookMessage, Option<AttachmentFile>)> {
+    // Look up item name
+    let url_name = match ctx.item_service.get_url_name(item_name) {
+        Some(url_name) => url_name,
+        None => bail!(lang.format(Text::NoItemNamed, &[("name", item_name)])),
+    };
+
+    // Get orders
+    let response = ctx
+        .wm_client
+        .fetch_item_orders(
+            url_name.as_ref().to_owned(),
+            order_filters.platform,
+            false,
+        )
+        .await;
+    let response = match response {
+        Err(error) if error.is_not_found() => {
+            let message = error_response(
+                lang,
+                lang.format(Text::NoItemNamed, &[("name", item_name)]),
+            );
+            return Ok((message, None));
+        }
+        Err(error) => match market_error_text(&error) {
+            Some(text) => {
+                return Ok((error_response(lang, lang.text(text)), None))
+            }
+            None => return Err(error).context("error getting item orders"),
+        },
+        Ok(response) => response,
+    };
+
+    // Render chart
+    let prices: Vec<_> = match item_details(&response.orders) {
+        Some(item) => {
+            let order_filters = order_filters.clone().resolve(item);
+            sell_orders(&response.orders.payload.orders, &order_filters)
+                .iter()
+                .map(|order| order.platinum)
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    let mut message =
+        create_response(response, order_filters, url_name.as_ref(), plat, lang);
+    let chart = match crate::chart::price_histogram(&prices) {
+        Some(chart) => AttachmentFile {
+            file_name: "prices.png".into(),
+            content_type: "image/png".into(),
+            data: chart,
+        },
+        None => return Ok((message, None)),
+    };
+
+    // Show the chart in the main embed
+    let embed = message
+        .embeds
+        .as_mut()
+        .and_then(|embeds| embeds.first_mut());
+    if let Some(embed) = embed {
+        embed.image = Some(EmbedImage {
+            url: Some(chart.attachment_url()),
+            ..Default::default()
         });
+        let footer = embed.footer.get_or_insert_with(|| market_footer(lang));
+        footer.text = format!(
+            "{} • {}",
+            lang.text(Text::PriceDistribution),
+            footer.text,
+        );
+    }
+
+    Ok((message, Some(chart)))
+}
+
+/// Finds the details of the item that orders were requested for.
+fn item_details(orders: &ItemOrders) -> Option<&ItemFull> {
+    let item = &orders.include.as_ref()?.item;
+    item.items_in_set
+        .iter()
+        .find(|details| details.id == item.id)
+}
+
+async fn compare_item(
+    ctx: &CommandContext,
+    item_name: &str,
+    order_filters: &OrderFilters,
+    lang: Language,
+) -> anyhow::Result<(String, Option<OrderStats>)> {
+    // Look up item name
+    let url_name = match ctx
+        .item_service
+        .get_url_name(&item_name.to_lowercase())
+    {
+        Some(url_name) => url_name,
+        None => bail!(lang.format(Text::NoItemNamed, &[("name", item_name)])),
+    };
+
+    // Get orders
+    let response = ctx
+        .wm_client
+        .get_item_orders(
+            url_name.as_ref().to_owned(),
+            order_filters.platform,
+            false,
+        )
+        .await;
+    let response = match response {
+        Err(error) if error.is_not_found() => {
+            bail!(lang.format(Text::NoItemNamed, &[("name", item_name)]))
+        }
+        Err(error) => match market_error_text(&error) {
+            Some(text) => bail!(lang.text(text)),
+            None => return Err(error).context("error getting item orders"),
+        },
+        Ok(response) => response,
+    };
+
+    // Calculate stats
+    let item = match item_details(&response) {
+        Some(item) => item,
+        None => bail!(lang.text(Text::MissingItemDetails)),
+    };
+    let order_filters = order_filters.clone().resolve(item);
+    let orders = sell_orders(&response.payload.orders, &order_filters);
+    Ok((item.en.item_name.clone(), OrderStats::new(&orders)))
+}
+
+fn create_compare_response(
+    item_names: &[String],
+    rows: Vec<anyhow::Result<(String, Option<OrderStats>)>>,
+    plat: &str,
+    lang: Language,
+) -> CreateWebhookMessage {
+    let mut table = String::new();
+    let mut errors = String::new();
+    writeln!(
+        table,
+        "{:<30} {:>7} {:>9} {:>9} {:>7}",
+        lang.text(Text::Item),
+        lang.text(Text::Min),
+        lang.text(Text::Median),
+        lang.text(Text::Mean),
+        lang.text(Text::Sellers)
+    )
+    .unwrap();
+    for (item_name, row) in item_names.iter().zip(rows) {
+        match row {
+            Ok((name, Some(stats))) => writeln!(
+                table,
+                "{name:<30.30} {min:>7} {median:>9.1} {mean:>9.2} {count:>7}",
+                min = stats.min,
+                median = stats.median,
+                mean = stats.mean,
+                count = stats.count,
+            )
+            .unwrap(),
+            Ok((name, None)) => writeln!(
+                table,
+                "{name:<30.30} {:>7} {:>9} {:>9} {:>7}",
+                "-", "-", "-", 0
+            )
+            .unwrap(),
+            Err(error) => writeln!(errors, "**{item_name}**: {error}").unwrap(),
+        }
+    }
+
+    CreateWebhookMessage::builder()
+        .embed(Embed {
+            title: Some(lang.text(Text::PriceComparison).into()),
+            description: Some(format!(
+                "{prices} {plat}\n```\n{table}```\n{errors}",
+                prices = lang.text(Text::PricesInPlatinum),
+            )),
+            ..Default::default()
+        })
+        .allowed_mentions(AllowedMentions::none())
+        .build()
+}
+
+async fn pc_filtered<'opts>(
+    interaction_data: Arc<InteractionData>,
+    ctx: &CommandContext,
+    item_name: &str,
+    order_filters: OrderFilters,
+) -> anyhow::Result<()> {
+    // Get message
+    let lang = Language::from_locale(interaction_data.locale.as_deref());
+    let plat = ctx.platinum_emoji.resolve(interaction_data.guild_id);
+    let message = process(
+        &ctx.wm_client,
+        &ctx.item_service,
+        &item_name,
+        order_filters,
+        &plat,
+        lang,
+    )
+    .await
+    .unwrap_or_else(|error| error_details_response(lang, &error));
 
     // Send response
     CreateFollowupMessage::execute(
-        discord_client,
-        *app_id,
+        &ctx.discord_client,
+        ctx.app_id,
         interaction_data.token.clone(),
-        message,
+        checked_message(lang, message),
     )
     .await
     .context("error creating response")?;
@@ -396,6 +987,54 @@ async fn pc_filtered<'opts>(
     Ok(())
 }
 
+/// Gets the price check response for an item, as if it were requested with
+/// `/pc items`.
+pub async fn item_price_message(
+    wm_client: &WmRestClient,
+    item_service: &WarframeItemService,
+    item_name: &str,
+    platform: Option<Platform>,
+    plat: &str,
+    lang: Language,
+) -> anyhow::Result<CreateWebhookMessage> {
+    process(
+        wm_client,
+        item_service,
+        &item_name.to_lowercase(),
+        OrderFilters {
+            platform,
+            rank: RankFilter::Item,
+        },
+        plat,
+        lang,
+    )
+    .await
+}
+
+/// Gets the price check response for an item, mod, arcane or relic, as if it
+/// were requested with `/pc lookup`.
+pub async fn lookup_price_message(
+    wm_client: &WmRestClient,
+    item_service: &WarframeItemService,
+    item_name: &str,
+    platform: Option<Platform>,
+    plat: &str,
+    lang: Language,
+) -> anyhow::Result<CreateWebhookMessage> {
+    process(
+        wm_client,
+        item_service,
+        &item_name.to_lowercase(),
+        OrderFilters {
+            platform,
+            rank: RankFilter::Infer,
+        },
+        plat,
+        lang,
+    )
+    .await
+}
+
 #[derive(Clone, Debug)]
 struct OrderFilters {
     pub platform: Option<Platform>,
@@ -403,6 +1042,14 @@ struct OrderFilters {
 }
 
 impl OrderFilters {
+    /// Resolves [`RankFilter::Infer`] for the item the orders are for.
+    fn resolve(self, item: &ItemFull) -> Self {
+        OrderFilters {
+            rank: self.rank.resolve(item),
+            ..self
+        }
+    }
+
     pub fn matches(&self, order: &ItemOrder) -> bool {
         // Platform
         if let Some(platform) = self.platform {
@@ -413,13 +1060,11 @@ impl OrderFilters {
 
         // Item rank/refinement
         match self.rank {
+            RankFilter::Infer => true,
             RankFilter::Item => matches!(order.rank, ItemRank::Item {}),
-            RankFilter::ModOrArcane { rank: rank_filter } => match order.rank {
-                ItemRank::ModOrArcane { mod_rank } => {
-                    rank_filter.map_or(true, |filter| mod_rank == filter)
-                }
-                _ => false,
-            },
+            RankFilter::ModOrArcane { rank } => {
+                order.rank == ItemRank::ModOrArcane { mod_rank: rank }
+            }
             RankFilter::Relic {
                 refinement: refinement_filter,
             } => match order.rank {
@@ -433,9 +1078,37 @@ impl OrderFilters {
 
 #[derive(Clone, Debug)]
 enum RankFilter {
-    ModOrArcane { rank: Option<u8> },
-    Relic { refinement: Option<RelicRefinement> },
+    /// Only orders for mods or arcanes at a specific rank. Prices differ
+    /// greatly between ranks, so orders for different ranks are never mixed.
+    ModOrArcane {
+        rank: u8,
+    },
+    Relic {
+        refinement: Option<RelicRefinement>,
+    },
     Item,
+    /// Picks the filter based on the item's type.
+    Infer,
+}
+
+impl RankFilter {
+    /// Replaces [`RankFilter::Infer`] with the filter for an item's type.
+    /// Mods and arcanes are filtered to [`DEFAULT_MOD_RANK`], and items which
+    /// aren't mods, arcanes or relics are treated as normal items.
+    fn resolve(self, item: &ItemFull) -> Self {
+        match self {
+            RankFilter::Infer => match item.item_type {
+                ItemType::ModOrArcane { .. } => RankFilter::ModOrArcane {
+                    rank: DEFAULT_MOD_RANK,
+                },
+                ItemType::Relic { .. } => {
+                    RankFilter::Relic { refinement: None }
+                }
+                ItemType::Item {} => RankFilter::Item,
+            },
+            filter => filter,
+        }
+    }
 }
 
 async fn process(
@@ -443,90 +1116,130 @@ async fn process(
     item_service: &WarframeItemService,
     item_name: &str,
     order_filters: OrderFilters,
+    plat: &str,
+    lang: Language,
 ) -> anyhow::Result<CreateWebhookMessage> {
     // Look up item name
     let url_name = item_service.get_url_name(&item_name);
     let url_name = match url_name {
         Some(url_name) => url_name,
-        None => bail!("No item with the name '{item_name}' found"),
+        None => bail!(lang.format(Text::NoItemNamed, &[("name", item_name)])),
     };
 
     // Get orders
-    let response = GetItemOrders::execute(
-        wm_client,
-        url_name.as_ref().to_owned(),
-        order_filters.platform,
-    )
-    .await
-    .context("error getting item orders")?;
+    let response = wm_client
+        .fetch_item_orders(
+            url_name.as_ref().to_owned(),
+            order_filters.platform,
+            false,
+        )
+        .await;
+    let response = match response {
+        Err(error) if error.is_not_found() => {
+            return Ok(error_response(
+                lang,
+                lang.format(Text::NoItemNamed, &[("name", item_name)]),
+            ));
+        }
+        Err(error) => match market_error_text(&error) {
+            Some(text) => return Ok(error_response(lang, lang.text(text))),
+            None => return Err(error).context("error getting item orders"),
+        },
+        Ok(response) => response,
+    };
 
     // Build response
-    let message = create_response(response, order_filters, url_name.as_ref());
+    let message =
+        create_response(response, order_filters, url_name.as_ref(), plat, lang);
     Ok(message)
 }
 
 fn create_response(
-    wm_res: PayloadResponse<ItemOrdersPayload, ItemPayload>,
+    fetched: FetchedItemOrders,
     order_filters: OrderFilters,
     url_name: &str,
+    plat: &str,
+    lang: Language,
 ) -> CreateWebhookMessage {
-    // Get item details
-    let item_details = match wm_res.include.as_ref() {
-        None => return error_response("Missing item details"),
-        Some(item_payload) => {
-            let item = item_payload
-                .item
-                .items_in_set
-                .iter()
-                .find(|item| &item.id == &item_payload.item.id);
+    let FetchedItemOrders {
+        orders: wm_res,
+        fetched_at,
+        cached,
+    } = fetched;
+    let fetched_at = Timestamp::from(fetched_at);
 
-            match item {
-                None => return error_response("Missing correct item details"),
-                Some(item) => item,
-            }
+    // Get item details
+    let item_details = match item_details(&wm_res) {
+        Some(item) => item,
+        None => {
+            return error_response(lang, lang.text(Text::MissingItemDetails))
         }
     };
 
     // Get orders
-    let mut orders: Vec<_> = wm_res
-        .payload
-        .orders
-        .iter()
-        .filter(|order| {
-            // Only show sell orders by people current ingame
-            order.order_type == OrderType::Sell
-                && order.user.status == UserStatus::InGame
-        })
-        .filter(|order| order_filters.matches(order))
-        .collect();
-
-    // Check if no orders
-    if orders.is_empty() {
-        return partial_error_response("No orders found", item_details);
-    }
-
-    orders.sort_unstable_by_key(|order| order.platinum);
-    let count = orders.len();
-    let sum: u32 = orders.iter().map(|order| order.platinum).sum();
-    let mean = sum as f64 / count as f64;
-    let variance = orders
-        .iter()
-        .map(|order| (order.platinum as f64 - mean).powi(2))
-        .sum::<f64>()
-        / (count - 1) as f64;
-    let deviation = variance.sqrt();
-    let range =
-        orders.first().unwrap().platinum..=orders.last().unwrap().platinum;
-    let median = if count % 2 == 0 {
-        orders[count / 2 - 1].platinum as f64 / 2.0
-            + orders[count / 2].platinum as f64 / 2.0
-    } else {
-        orders[count / 2].platinum as f64
+    let order_filters = order_filters.resolve(item_details);
+    let status_counts =
+        StatusCounts::new(&wm_res.payload.orders, &order_filters);
+    let orders = sell_orders(&wm_res.payload.orders, &order_filters);
+    let stats = match OrderStats::new(&orders) {
+        Some(stats) => stats,
+        None => {
+            return partial_error_response(
+                lang,
+                lang.text(Text::NoOrdersFound),
+                item_details,
+            )
+        }
     };
+    let OrderStats {
+        min,
+        max,
+        mean,
+        median,
+        deviation,
+        ..
+    } = stats;
 
+    let stats_fields = [
+        EmbedField {
+            name: lang.text(Text::PriceRange).to_string(),
+            value: format!("{min}{plat} - {max}{plat}"),
+            inline: Some(true),
+        },
+        EmbedField {
+            name: lang.text(Text::Mean).to_string(),
+            value: format!("{mean:0.2}{plat}"),
+            inline: Some(true),
+        },
+        EmbedField {
+            name: lang.text(Text::Median).to_string(),
+            value: format!("{median:.1}{plat}"),
+            inline: Some(true),
+        },
+        EmbedField {
+            name: lang.text(Text::StandardDeviation).to_string(),
+            value: match deviation {
+                Some(deviation) => format!("{deviation:.2}"),
+                None => lang.text(Text::NotApplicable).into(),
+            },
+            inline: Some(true),
+        },
+        EmbedField {
+            name: lang.text(Text::Orders).to_string(),
+            value: lang.format(
+                Text::OrderStatusSummary,
+                &[
+                    ("ingame", &status_counts.ingame.to_string()),
+                    ("online", &status_counts.online.to_string()),
+                    ("total", &status_counts.total.to_string()),
+                ],
+            ),
+            inline: Some(false),
+        },
+    ];
     let main_embed = Embed {
         title: Some(item_details.en.item_name.clone()),
-        url: Some(format!("{WM_BASE_URL}/items/{url_name}")),
+        url: Some(item_url(url_name)),
         description: Some(item_details.en.description.clone()),
         thumbnail: Some(EmbedThumbnail {
             url: Some(format!(
@@ -535,32 +1248,12 @@ fn create_response(
             )),
             ..Default::default()
         }),
-        fields: Some(vec![
-            EmbedField {
-                name: "Price range".to_string(),
-                value: format!(
-                    "{start}{PLAT} - {end}{PLAT}",
-                    start = range.start(),
-                    end = range.end(),
-                ),
-                inline: Some(true),
-            },
-            EmbedField {
-                name: "Mean (x̄)".to_string(),
-                value: format!("{mean:0.2}{PLAT}"),
-                inline: Some(true),
-            },
-            EmbedField {
-                name: "Median".to_string(),
-                value: format!("{median:.1}{PLAT}"),
-                inline: Some(true),
-            },
-            EmbedField {
-                name: "Standard deviation (s)".to_string(),
-                value: format!("{deviation:.2}"),
-                inline: Some(true),
-            },
-        ]),
+        footer: Some(if cached {
+            cached_market_footer(lang, fetched_at)
+        } else {
+            market_footer(lang)
+        }),
+        timestamp: Some(fetched_at),
         ..Default::default()
     };
     let offers_description =
@@ -570,88 +1263,238 @@ fn create_response(
             .fold(String::new(), |mut offers, order| {
                 writeln!(
                     offers,
-                    "**{seller}** ({rep:+}): {cost}{PLAT}, {quantity} remaining ```",
+                    "**{seller}** ({rep:+}): {cost}{plat}, {quantity} remaining ```",
                     seller = order.user.ingame_name,
                     rep = order.user.reputation,
                     cost = order.platinum,
                     quantity = order.quantity,
                 ).unwrap();
-                match order.rank {
-                    ItemRank::ModOrArcane { mod_rank: rank, .. } => writeln!(
-                        offers,
-                        "/w {seller} Hi! I want to buy: {item} (rank {rank}) for {cost} platinum. (warframe.market)",
-                        seller = order.user.ingame_name,
-                        item = item_details.en.item_name,
-                        cost = order.platinum,
-                    )
-                    .unwrap(),
-                    ItemRank::Relic { refinement, .. } => writeln!(
-                        offers,
-                        "/w {seller} Hi! I want to buy: {item} ({refinement}) for {cost} platinum. (warframe.market)",
-                        seller = order.user.ingame_name,
-                        item = item_details.en.item_name,
-                        refinement = match refinement {
-                            RelicRefinement::Intact => "intact",
-                            RelicRefinement::Exceptional => "exceptional",
-                            RelicRefinement::Flawless => "flawless",
-                            RelicRefinement::Radiant => "radiant",
-                        },
-                        cost = order.platinum,
-                    )
-                    .unwrap(),
-                    ItemRank::Item { .. } => writeln!(
-                        offers,
-                        "/w {seller} Hi! I want to buy: {item} for {cost} platinum. (warframe.market)",
-                        seller = order.user.ingame_name,
-                        item = item_details.en.item_name,
-                        cost = order.platinum,
-                    )
-                    .unwrap(),
-                }
+                writeln!(
+                    offers,
+                    "{}",
+                    whisper_message(order, &item_details.en.item_name)
+                )
+                .unwrap();
                 writeln!(offers, "```").unwrap();
 
                 offers
             });
 
-    let offers_embed = Embed {
-        title: Some(format!("Best Offers ({} sellers)", orders.len())),
-        description: Some(offers_description),
-        ..Default::default()
+    let offers_embed =
+        Embed {
+            title: Some(lang.format(
+                Text::BestOffers,
+                &[("count", &orders.len().to_string())],
+            )),
+            description: Some(offers_description),
+            ..Default::default()
+        };
+
+    CreateWebhookMessage::builder()
+        .field_embeds(main_embed, stats_fields)
+        .embed(offers_embed)
+        .allowed_mentions(AllowedMentions::none())
+        .build()
+}
+
+/// The warframe.market page for an item. The URL name is percent-encoded so
+/// the link is valid even if the name contains reserved characters.
+pub fn item_url(url_name: &str) -> String {
+    format!("{WM_BASE_URL}/items/{}", PathSegment::from(url_name))
+}
+
+/// The message to whisper a seller in game to buy the item from their
+/// order.
+pub fn whisper_message(order: &ItemOrder, item_name: &str) -> String {
+    let item = match order.rank {
+        ItemRank::ModOrArcane { mod_rank, .. } => {
+            format!("{item_name} (rank {mod_rank})")
+        }
+        ItemRank::Relic { refinement, .. } => {
+            format!("{item_name} ({refinement})")
+        }
+        ItemRank::Item { .. } => item_name.to_owned(),
     };
 
-    CreateWebhookMessage {
-        embeds: Some(vec![main_embed, offers_embed]),
-        allowed_mentions: Some(AllowedMentions {
-            parse: Some(vec![]),
-            ..Default::default()
-        }),
-        ..Default::default()
+    format!(
+        "/w {seller} Hi! I want to buy: {item} for {cost} platinum. (warframe.market)",
+        seller = order.user.ingame_name,
+        cost = order.platinum,
+    )
+}
+
+/// Gets the sell orders from players currently in game which match the
+/// filters, sorted by price. Orders with the same price are sorted by the
+/// seller's reputation (highest first), then by their name.
+fn sell_orders<'a>(
+    orders: &'a [ItemOrder],
+    order_filters: &OrderFilters,
+) -> Vec<&'a ItemOrder> {
+    let mut orders: Vec<_> = orders
+        .iter()
+        .filter(|order| {
+            // Only show sell orders by people current ingame
+            order.order_type == OrderType::Sell
+                && order.user.status == UserStatus::InGame
+        })
+        .filter(|order| order_filters.matches(order))
+        .collect();
+    orders.sort_by(|a, b| {
+        a.platinum
+            .cmp(&b.platinum)
+            .then_with(|| b.user.reputation.cmp(&a.user.reputation))
+            .then_with(|| a.user.ingame_name.cmp(&b.user.ingame_name))
+    });
+    orders
+}
+
+/// How many sell orders matching the filters are from sellers with each
+/// status, before only in-game sellers are kept.
+#[derive(Clone, Copy, Debug, Default)]
+struct StatusCounts {
+    ingame: usize,
+    online: usize,
+    total: usize,
+}
+
+impl StatusCounts {
+    fn new(orders: &[ItemOrder], order_filters: &OrderFilters) -> Self {
+        orders
+            .iter()
+            .filter(|order| order.order_type == OrderType::Sell)
+            .filter(|order| order_filters.matches(order))
+            .fold(StatusCounts::default(), |mut counts, order| {
+                match order.user.status {
+                    UserStatus::InGame => counts.ingame += 1,
+                    UserStatus::Online => counts.online += 1,
+                    UserStatus::Offline => {}
+                }
+                counts.total += 1;
+                counts
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct OrderStats {
+    count: usize,
+    min: u32,
+    max: u32,
+    mean: f64,
+    median: f64,
+    /// The sample standard deviation, or [`None`] if there is only one order.
+    deviation: Option<f64>,
+}
+
+impl OrderStats {
+    /// Calculates statistics for orders sorted by price, or [`None`] if there
+    /// are no orders.
+    fn new(orders: &[&ItemOrder]) -> Option<Self> {
+        let min = orders.first()?.platinum;
+        let max = orders.last()?.platinum;
+        let count = orders.len();
+        let sum: u64 =
+            orders.iter().map(|order| u64::from(order.platinum)).sum();
+        let mean = sum as f64 / count as f64;
+        let deviation = if count > 1 {
+            let variance = orders
+                .iter()
+                .map(|order| (order.platinum as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (count - 1) as f64;
+            Some(variance.sqrt())
+        } else {
+            None
+        };
+        let median = if count % 2 == 1 {
+            orders[count / 2].platinum as f64
+        } else {
+            orders[count / 2 - 1].platinum as f64 / 2.0
+                + orders[count / 2].platinum as f64 / 2.0
+        };
+
+        Some(OrderStats {
+            count,
+            min,
+            max,
+            mean,
+            median,
+            deviation,
+        })
     }
 }
 
-fn error_response(content: impl Into<String>) -> CreateWebhookMessage {
-    CreateWebhookMessage {
-        embeds: Some(vec![Embed {
-            title: Some("Error".into()),
+fn error_response(
+    lang: Language,
+    content: impl Into<String>,
+) -> CreateWebhookMessage {
+    CreateWebhookMessage::builder()
+        .embed(Embed {
+            title: Some(lang.text(Text::Error).into()),
             description: Some(content.into()),
             ..Default::default()
-        }]),
-        allowed_mentions: Some(AllowedMentions {
-            parse: Some(vec![]),
-            ..Default::default()
-        }),
-        flags: Some(MessageFlags::EPHEMERAL),
-        ..Default::default()
+        })
+        .allowed_mentions(AllowedMentions::none())
+        .ephemeral()
+        .build()
+}
+
+/// Creates an error response showing an error's debug output. The full
+/// error is logged, and the output is truncated to fit in the embed.
+pub fn error_details_response(
+    lang: Language,
+    error: &impl Debug,
+) -> CreateWebhookMessage {
+    error!(?error, "error handling command");
+
+    // Leave room for the code block
+    let max_length = Embed::MAX_DESCRIPTION_LENGTH - "```\n\n```".len();
+    let mut details = format!("{:#?}", error);
+    if details.chars().count() > max_length {
+        let kept = max_length - TRUNCATED_MARKER.chars().count();
+        details = details.chars().take(kept).collect();
+        details.push_str(TRUNCATED_MARKER);
+    }
+
+    error_response(lang, format!("```\n{}\n```", details))
+}
+
+/// Replaces a message Discord would reject with an error message, so the
+/// user still gets a response.
+fn checked_message(
+    lang: Language,
+    message: CreateWebhookMessage,
+) -> CreateWebhookMessage {
+    match message.validate_content() {
+        Ok(()) => message,
+        Err(error) => {
+            warn!(%error, "response message is invalid");
+            error_response(lang, error.to_string())
+        }
+    }
+}
+
+/// Gets the text to show when warframe.market couldn't handle a request.
+/// Other errors aren't shown to the user.
+fn market_error_text(error: &RequestError) -> Option<Text> {
+    match error.kind()? {
+        WmErrorKind::Unavailable => Some(Text::MarketUnavailable),
+        WmErrorKind::RateLimited => Some(Text::MarketRateLimited),
+        WmErrorKind::NotFound | WmErrorKind::Parse => None,
     }
 }
 
 fn partial_error_response(
+    lang: Language,
     content: impl Into<String>,
     item_details: &ItemFull,
 ) -> CreateWebhookMessage {
-    CreateWebhookMessage {
-        embeds: Some(vec![Embed {
-            title: Some(format!("Error ({})", item_details.en.item_name)),
+    CreateWebhookMessage::builder()
+        .embed(Embed {
+            title: Some(lang.format(
+                Text::ErrorFor,
+                &[("item", &item_details.en.item_name)],
+            )),
             description: Some(content.into()),
             thumbnail: Some(EmbedThumbnail {
                 url: Some(format!(
@@ -663,12 +1506,35 @@ fn partial_error_response(
                 )),
                 ..Default::default()
             }),
+            footer: Some(market_footer(lang)),
+            timestamp: Some(Timestamp::now()),
             ..Default::default()
-        }]),
-        allowed_mentions: Some(AllowedMentions {
-            parse: Some(vec![]),
-            ..Default::default()
-        }),
-        ..Default::default()
+        })
+        .allowed_mentions(AllowedMentions::none())
+        .build()
+}
+
+/// Credits warframe.market for the data in an embed. Discord shows the embed's
+/// timestamp next to the footer.
+fn market_footer(lang: Language) -> EmbedFooter {
+    EmbedFooter {
+        text: lang.text(Text::DataFromMarket).into(),
+        icon_url: None,
+        proxy_icon_url: None,
+    }
+}
+
+/// Like [`market_footer`], but also notes when cached data was fetched.
+/// Footers can't contain timestamp markdown, so the time is shown in UTC.
+fn cached_market_footer(lang: Language, fetched_at: Timestamp) -> EmbedFooter {
+    let time = fetched_at.0.format("%H:%M").to_string();
+    EmbedFooter {
+        text: format!(
+            "{} • {}",
+            lang.text(Text::DataFromMarket),
+            lang.format(Text::PricesAsOfCached, &[("time", &time)]),
+        ),
+        icon_url: None,
+        proxy_icon_url: None,
     }
 }
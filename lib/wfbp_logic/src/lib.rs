@@ -1,2 +1,5 @@
+#[cfg(feature = "chart")]
+pub mod chart;
 pub mod commands;
+pub mod localization;
 pub mod services;
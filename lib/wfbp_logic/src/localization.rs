@@ -0,0 +1,173 @@
+/// A language that responses can be written in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Language {
+    English,
+    German,
+}
+
+impl Language {
+    /// Picks the language for a Discord locale like `en-US` or `de`. Unknown
+    /// locales fall back to English.
+    pub fn from_locale(locale: Option<&str>) -> Self {
+        let language = locale
+            .and_then(|locale| locale.split('-').next())
+            .unwrap_or_default();
+        match language {
+            "de" => Language::German,
+            _ => Language::English,
+        }
+    }
+
+    /// Gets the translation of some text. Text which hasn't been translated
+    /// yet is shown in English.
+    pub fn text(self, text: Text) -> &'static str {
+        let translated = match self {
+            Language::English => None,
+            Language::German => german(text),
+        };
+
+        translated.unwrap_or_else(|| english(text))
+    }
+
+    /// Gets the translation of some text, replacing each `{name}` with its
+    /// argument.
+    pub fn format(self, text: Text, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(self.text(text).to_owned(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+}
+
+/// Text shown in command responses.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[non_exhaustive]
+pub enum Text {
+    Error,
+    ErrorFor,
+    PriceRange,
+    Mean,
+    Median,
+    StandardDeviation,
+    BestOffers,
+    NoOrdersFound,
+    MissingItemDetails,
+    NoItemNamed,
+    MarketUnavailable,
+    MarketRateLimited,
+    PriceComparison,
+    PricesInPlatinum,
+    Item,
+    Min,
+    Sellers,
+    NoItemsToCompare,
+    TooManyItems,
+    PriceDistribution,
+    DataFromMarket,
+    PricesAsOfCached,
+    NotApplicable,
+    Orders,
+    OrderStatusSummary,
+    SellOrdersBy,
+    NoUserNamed,
+    MoreOrders,
+    ItemsIndexed,
+    RefreshItemsForbidden,
+    CommandOnCooldown,
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::Error => "Error",
+        Text::ErrorFor => "Error ({item})",
+        Text::PriceRange => "Price range",
+        Text::Mean => "Mean (x̄)",
+        Text::Median => "Median",
+        Text::StandardDeviation => "Standard deviation (s)",
+        Text::BestOffers => "Best Offers ({count} sellers)",
+        Text::NoOrdersFound => "No orders found",
+        Text::MissingItemDetails => "Missing item details",
+        Text::NoItemNamed => "No item with the name '{name}' found",
+        Text::MarketUnavailable => {
+            "warframe.market appears to be down, try again shortly."
+        }
+        Text::MarketRateLimited => {
+            "warframe.market is receiving too many requests, try again in a few seconds."
+        }
+        Text::PriceComparison => "Price comparison",
+        Text::PricesInPlatinum => "Prices are in platinum",
+        Text::Item => "Item",
+        Text::Min => "Min",
+        Text::Sellers => "Sellers",
+        Text::NoItemsToCompare => "No items to compare",
+        Text::TooManyItems => "At most {max} items can be compared at once",
+        Text::PriceDistribution => "Price distribution",
+        Text::DataFromMarket => "Data from warframe.market",
+        Text::PricesAsOfCached => "prices as of {time} UTC (cached)",
+        Text::NotApplicable => "n/a",
+        Text::Orders => "Orders",
+        Text::OrderStatusSummary => {
+            "{ingame} in-game, {online} online, {total} total"
+        }
+        Text::SellOrdersBy => "Sell orders by {name}",
+        Text::NoUserNamed => "No user with the name '{name}' found",
+        Text::MoreOrders => "...and {count} more",
+        Text::ItemsIndexed => "Indexed {count} items in {duration}",
+        Text::RefreshItemsForbidden => {
+            "You need the Manage Server permission to refresh items"
+        }
+        Text::CommandOnCooldown => {
+            "You're using this command too quickly, try again in {seconds} seconds."
+        }
+    }
+}
+
+fn german(text: Text) -> Option<&'static str> {
+    let translated = match text {
+        Text::Error => "Fehler",
+        Text::ErrorFor => "Fehler ({item})",
+        Text::PriceRange => "Preisspanne",
+        Text::Mean => "Mittelwert (x̄)",
+        Text::Median => "Median",
+        Text::StandardDeviation => "Standardabweichung (s)",
+        Text::BestOffers => "Beste Angebote ({count} Verkäufer)",
+        Text::NoOrdersFound => "Keine Angebote gefunden",
+        Text::MissingItemDetails => "Gegenstandsdetails fehlen",
+        Text::NoItemNamed => "Kein Gegenstand mit dem Namen '{name}' gefunden",
+        Text::MarketUnavailable => {
+            "warframe.market scheint nicht erreichbar zu sein, versuche es gleich noch einmal."
+        }
+        Text::MarketRateLimited => {
+            "warframe.market erhält zu viele Anfragen, versuche es in ein paar Sekunden noch einmal."
+        }
+        Text::PriceComparison => "Preisvergleich",
+        Text::PricesInPlatinum => "Preise in Platin",
+        Text::Item => "Gegenstand",
+        Text::Min => "Min",
+        Text::Sellers => "Verkäufer",
+        Text::NoItemsToCompare => "Keine Gegenstände zum Vergleichen",
+        Text::TooManyItems => {
+            "Es können höchstens {max} Gegenstände gleichzeitig verglichen werden"
+        }
+        Text::PriceDistribution => "Preisverteilung",
+        Text::DataFromMarket => "Daten von warframe.market",
+        Text::PricesAsOfCached => "Preise von {time} UTC (zwischengespeichert)",
+        Text::NotApplicable => "k. A.",
+        Text::Orders => "Angebote",
+        Text::OrderStatusSummary => {
+            "{ingame} im Spiel, {online} online, {total} insgesamt"
+        }
+        Text::SellOrdersBy => "Verkaufsangebote von {name}",
+        Text::NoUserNamed => "Kein Benutzer mit dem Namen '{name}' gefunden",
+        Text::MoreOrders => "...und {count} weitere",
+        Text::ItemsIndexed => "{count} Gegenstände in {duration} indexiert",
+        Text::RefreshItemsForbidden => {
+            "Du brauchst die Berechtigung \"Server verwalten\", um Gegenstände zu aktualisieren"
+        }
+        Text::CommandOnCooldown => {
+            "Du benutzt diesen Befehl zu schnell, versuche es in {seconds} Sekunden erneut."
+        }
+    };
+
+    Some(translated)
+}
@@ -1,3 +1,5 @@
+mod platinum_emoji_service;
 mod wf_item_service;
 
+pub use platinum_emoji_service::*;
 pub use wf_item_service::*;
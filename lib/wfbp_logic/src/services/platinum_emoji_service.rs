@@ -0,0 +1,43 @@
+use std::{borrow::Cow, collections::HashSet, sync::Arc};
+use wfbp_discord::models::Snowflake;
+
+/// Resolves the emoji used to display platinum in responses.
+#[derive(Clone, Debug, Default)]
+pub struct PlatinumEmojiService {
+    emoji_id: Option<Snowflake>,
+    guild_ids: Arc<HashSet<Snowflake>>,
+}
+
+impl PlatinumEmojiService {
+    /// Used when no custom emoji is available in the guild.
+    pub const FALLBACK: &'static str = "💎";
+
+    /// Creates a new service. If `guild_ids` is empty, the custom emoji is
+    /// used everywhere. Otherwise, it is only used within those guilds.
+    pub fn new(
+        emoji_id: Option<Snowflake>,
+        guild_ids: impl IntoIterator<Item = Snowflake>,
+    ) -> Self {
+        PlatinumEmojiService {
+            emoji_id,
+            guild_ids: Arc::new(guild_ids.into_iter().collect()),
+        }
+    }
+
+    /// Gets the platinum emoji to use for an interaction in the given guild.
+    pub fn resolve(&self, guild_id: Option<Snowflake>) -> Cow<'static, str> {
+        let emoji_id = match self.emoji_id {
+            Some(emoji_id) => emoji_id,
+            None => return Self::FALLBACK.into(),
+        };
+
+        let usable = self.guild_ids.is_empty()
+            || guild_id
+                .is_some_and(|guild_id| self.guild_ids.contains(&guild_id));
+        if usable {
+            format!("<:WFPlatinum:{emoji_id}>").into()
+        } else {
+            Self::FALLBACK.into()
+        }
+    }
+}
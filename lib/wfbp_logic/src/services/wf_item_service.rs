@@ -1,41 +1,130 @@
-use anyhow::Context;
+use anyhow::{anyhow, bail, Context};
 use qp_trie::{wrapper::BString, Trie};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 use tracing::{debug, instrument, warn};
-use wfbp_wm::{models::ItemShort, routes::GetItems, WmRestClient};
+use wfbp_http::Conditional;
+use wfbp_wm::{
+    models::{ItemShort, Language},
+    routes::GetItems,
+    SingleFlight, WmRestClient,
+};
+
+type RefreshResult = Result<(), Arc<anyhow::Error>>;
 
 #[derive(Debug, Clone)]
 pub struct WarframeItemService {
-    lookup: Arc<Trie<BString, Arc<str>>>,
+    lookup: Arc<RwLock<ItemLookup>>,
+    refreshing: SingleFlight<(), RefreshResult>,
+}
+
+#[derive(Debug)]
+struct ItemLookup {
+    etag: Option<String>,
+    items: usize,
+    trie: Trie<BString, Arc<str>>,
 }
 
 impl WarframeItemService {
     #[instrument(skip(wm_client))]
     pub async fn new(wm_client: WmRestClient) -> anyhow::Result<Self> {
-        let lookup = build_lookup(&wm_client)
-            .await
-            .context("error building lookup table")?;
-        debug!(entries=?lookup.count(), "created lookup trie for item queries");
+        let (items, etag) = match get_items(&wm_client, None).await? {
+            Conditional::Modified { value, etag } => (value, etag),
+            Conditional::NotModified => {
+                bail!("unconditional request for items was not modified")
+            }
+        };
+        let item_count = items.len();
+        let trie = build_lookup(items);
+        debug!(entries=?trie.count(), "created lookup trie for item queries");
 
         Ok(WarframeItemService {
-            lookup: Arc::new(lookup),
+            lookup: Arc::new(RwLock::new(ItemLookup {
+                etag,
+                items: item_count,
+                trie,
+            })),
+            refreshing: SingleFlight::new(),
         })
     }
 
     pub fn get_url_name(&self, query: &str) -> Option<Arc<str>> {
-        self.lookup.get_str(query).cloned()
+        self.lookup.read().unwrap().trie.get_str(query).cloned()
+    }
+
+    /// The number of tradeable items that can be looked up.
+    pub fn item_count(&self) -> usize {
+        self.lookup.read().unwrap().items
+    }
+
+    /// Rebuilds the lookup table if the list of items changed since it was
+    /// last fetched. Concurrent refreshes share a single request.
+    #[instrument(skip(self, wm_client))]
+    pub async fn refresh(
+        &self,
+        wm_client: &WmRestClient,
+    ) -> anyhow::Result<()> {
+        let service = self.clone();
+        let wm_client = wm_client.clone();
+        let result = self
+            .refreshing
+            .run((), move || async move {
+                service.refresh_lookup(&wm_client).await.map_err(Arc::new)
+            })
+            .await;
+        result.map_err(|error| {
+            Arc::try_unwrap(error).unwrap_or_else(|error| anyhow!("{error:#}"))
+        })
+    }
+
+    async fn refresh_lookup(
+        &self,
+        wm_client: &WmRestClient,
+    ) -> anyhow::Result<()> {
+        let etag = self.lookup.read().unwrap().etag.clone();
+        let (items, etag) = match get_items(wm_client, etag).await? {
+            Conditional::Modified { value, etag } => (value, etag),
+            Conditional::NotModified => {
+                debug!("item list not modified");
+                return Ok(());
+            }
+        };
+
+        let item_count = items.len();
+        let trie = build_lookup(items);
+        debug!(entries=?trie.count(), "refreshed lookup trie for item queries");
+        *self.lookup.write().unwrap() = ItemLookup {
+            etag,
+            items: item_count,
+            trie,
+        };
+        Ok(())
     }
 }
 
-#[instrument(skip(wm_client))]
-async fn build_lookup(
+async fn get_items(
     wm_client: &WmRestClient,
-) -> anyhow::Result<Trie<BString, Arc<str>>> {
-    // Get searchable items from w.m
-    let items = GetItems::execute(wm_client)
+    etag: Option<String>,
+) -> anyhow::Result<Conditional<Vec<ItemShort>>> {
+    // Item names are always indexed in English
+    let response = GetItems::execute(wm_client, etag, Some(Language::En))
         .await
         .context("error getting tradeable items from warframe.market")?;
-    debug!(items = ?items.payload.items.len(), "got items");
+    let response = match response {
+        Conditional::Modified { value, etag } => Conditional::Modified {
+            value: value.payload.items,
+            etag,
+        },
+        Conditional::NotModified => Conditional::NotModified,
+    };
+
+    Ok(response)
+}
+
+fn build_lookup(items: Vec<ItemShort>) -> Trie<BString, Arc<str>> {
+    debug!(items = ?items.len(), "got items");
 
     // Build abbreviation table
     let mut abbrvs = HashMap::new();
@@ -51,9 +140,7 @@ async fn build_lookup(
     abbrvs.insert("relic", vec![""]);
 
     // Create trie
-    let trie = items
-        .payload
-        .items
+    items
         .into_iter()
         .flat_map(|item| {
             let ItemShort {
@@ -89,9 +176,7 @@ async fn build_lookup(
                 warn!("duplicate key {key:?} for {prev:?} in trie");
             }
             trie
-        });
-
-    Ok(trie)
+        })
 }
 
 fn dfs_build_phrases(
@@ -0,0 +1,75 @@
+#![cfg(feature = "chart")]
+
+use png::{BitDepth, ColorType, Decoder};
+use wfbp_logic::chart::{price_histogram, MIN_CHART_PRICES};
+
+/// A decoded RGB image.
+struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn decode(png: &[u8]) -> Self {
+        let mut reader = Decoder::new(png).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(info.color_type, ColorType::Rgb);
+        assert_eq!(info.bit_depth, BitDepth::Eight);
+        pixels.truncate(info.buffer_size());
+
+        Image {
+            width: info.width,
+            height: info.height,
+            pixels,
+        }
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        let offset = ((y * self.width + x) * 3) as usize;
+        self.pixels[offset..offset + 3].try_into().unwrap()
+    }
+}
+
+#[test]
+fn too_few_prices_are_not_charted() {
+    let prices = vec![10; MIN_CHART_PRICES - 1];
+    assert!(price_histogram(&prices).is_none());
+}
+
+#[test]
+fn histogram_is_a_valid_png() {
+    let png = price_histogram(&[10, 12, 12, 15, 40, 41]).unwrap();
+    let image = Image::decode(&png);
+    assert_eq!(
+        image.pixels.len(),
+        (image.width * image.height * 3) as usize
+    );
+}
+
+#[test]
+fn price_range_is_labeled() {
+    // Both charts have the same bars, so only their price labels differ
+    let low = Image::decode(&price_histogram(&[10, 12, 15]).unwrap());
+    let high = Image::decode(&price_histogram(&[20, 22, 25]).unwrap());
+    let changed_rows: Vec<_> = (0..low.height)
+        .filter(|&y| {
+            (0..low.width).any(|x| low.pixel(x, y) != high.pixel(x, y))
+        })
+        .collect();
+
+    // The labels are drawn under the bars
+    assert!(!changed_rows.is_empty());
+    assert!(changed_rows.iter().all(|&y| y > low.height * 3 / 4));
+}
+
+#[test]
+fn identical_prices_are_charted() {
+    assert!(price_histogram(&[5, 5, 5]).is_some());
+}
+
+#[test]
+fn full_price_range_is_charted() {
+    assert!(price_histogram(&[0, 1, u32::MAX]).is_some());
+}
@@ -0,0 +1,82 @@
+use reqwest::{Client, Method};
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+use wfbp_http::test_util::MockTransport;
+use wfbp_logic::services::WarframeItemService;
+use wfbp_wm::{NoopMetrics, WmClientConfig, WmRestClient};
+
+fn item(url_name: &str, item_name: &str) -> serde_json::Value {
+    json!({
+        "id": url_name,
+        "url_name": url_name,
+        "thumb": format!("items/images/en/thumbs/{url_name}.png"),
+        "item_name": item_name,
+    })
+}
+
+fn respond_items(transport: &MockTransport, items: Vec<serde_json::Value>) {
+    transport.respond_json(
+        Method::GET,
+        "/v1/items",
+        json!({ "payload": { "items": items } }),
+    );
+}
+
+fn wm_client(transport: Arc<MockTransport>) -> WmRestClient {
+    WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport,
+    )
+}
+
+#[tokio::test]
+async fn refresh_indexes_new_items() {
+    let transport = Arc::new(MockTransport::new());
+    respond_items(&transport, vec![item("ash_prime_set", "Ash Prime Set")]);
+    let wm_client = wm_client(transport.clone());
+    let item_service = WarframeItemService::new(wm_client.clone())
+        .await
+        .expect("error creating item service");
+    assert_eq!(item_service.item_count(), 1);
+    assert!(item_service.get_url_name("volt prime set").is_none());
+
+    respond_items(
+        &transport,
+        vec![
+            item("ash_prime_set", "Ash Prime Set"),
+            item("volt_prime_set", "Volt Prime Set"),
+        ],
+    );
+    item_service
+        .refresh(&wm_client)
+        .await
+        .expect("error refreshing items");
+
+    assert_eq!(item_service.item_count(), 2);
+    assert_eq!(
+        item_service.get_url_name("volt prime set").as_deref(),
+        Some("volt_prime_set")
+    );
+}
+
+#[tokio::test]
+async fn concurrent_refreshes_share_one_request() {
+    let transport = Arc::new(MockTransport::new());
+    respond_items(&transport, vec![item("ash_prime_set", "Ash Prime Set")]);
+    let wm_client = wm_client(transport.clone());
+    let item_service = WarframeItemService::new(wm_client.clone())
+        .await
+        .expect("error creating item service");
+
+    transport.delay(Duration::from_millis(50));
+    let (first, second) = tokio::join!(
+        item_service.refresh(&wm_client),
+        item_service.refresh(&wm_client),
+    );
+    first.expect("error refreshing items");
+    second.expect("error refreshing items");
+
+    assert_eq!(transport.requests().len(), 2);
+}
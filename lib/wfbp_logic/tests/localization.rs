@@ -0,0 +1,38 @@
+use wfbp_logic::localization::{Language, Text};
+
+#[test]
+fn picks_language_from_locale() {
+    assert_eq!(Language::from_locale(Some("de")), Language::German);
+    assert_eq!(Language::from_locale(Some("en-US")), Language::English);
+    assert_eq!(Language::from_locale(Some("en-GB")), Language::English);
+}
+
+#[test]
+fn unknown_locales_fall_back_to_english() {
+    assert_eq!(Language::from_locale(Some("pt-BR")), Language::English);
+    assert_eq!(Language::from_locale(Some("")), Language::English);
+    assert_eq!(Language::from_locale(None), Language::English);
+}
+
+#[test]
+fn formats_translated_text() {
+    assert_eq!(Language::English.text(Text::PriceRange), "Price range");
+    assert_eq!(Language::German.text(Text::PriceRange), "Preisspanne");
+    assert_eq!(
+        Language::German.format(Text::BestOffers, &[("count", "3")]),
+        "Beste Angebote (3 Verkäufer)",
+    );
+}
+
+#[test]
+fn formats_refresh_messages() {
+    let args = [("count", "1234"), ("duration", "1.50s")];
+    assert_eq!(
+        Language::English.format(Text::ItemsIndexed, &args),
+        "Indexed 1234 items in 1.50s",
+    );
+    assert_eq!(
+        Language::German.format(Text::ItemsIndexed, &args),
+        "1234 Gegenstände in 1.50s indexiert",
+    );
+}
@@ -0,0 +1,465 @@
+use reqwest::{Client, Method};
+use serde_json::json;
+use std::sync::Arc;
+use wfbp_discord::models::{CreateWebhookMessage, Embed};
+use wfbp_http::test_util::MockTransport;
+use wfbp_logic::{
+    commands::{
+        error_details_response, item_price_message, item_url,
+        lookup_price_message, whisper_message,
+    },
+    localization::Language,
+    services::WarframeItemService,
+};
+use wfbp_wm::{
+    models::ItemOrder, ItemOrdersCacheConfig, NoopMetrics, WmClientConfig,
+    WmRestClient,
+};
+
+fn order(id: &str, platinum: u32, status: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "platinum": platinum,
+        "quantity": 1,
+        "order_type": "sell",
+        "platform": "pc",
+        "creation_date": "2021-01-01T00:00:00.000+00:00",
+        "last_update": "2021-01-01T00:00:00.000+00:00",
+        "user": {
+            "id": format!("user-{id}"),
+            "ingame_name": format!("Seller{id}"),
+            "status": status,
+            "region": "en",
+            "reputation": 10,
+            "avatar": null,
+            "last_seen": null,
+        },
+    })
+}
+
+fn mock_market(orders: Vec<serde_json::Value>) -> Arc<MockTransport> {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond_json(
+        Method::GET,
+        "/v1/items",
+        json!({
+            "payload": {
+                "items": [{
+                    "id": "item",
+                    "url_name": "ash_prime_set",
+                    "thumb": "items/images/en/thumbs/ash_prime_set.png",
+                    "item_name": "Ash Prime Set",
+                }],
+            },
+        }),
+    );
+    transport.respond_json(
+        Method::GET,
+        "/v1/items/ash_prime_set/orders",
+        json!({
+            "payload": {
+                "orders": orders,
+            },
+            "include": {
+                "item": {
+                    "id": "item",
+                    "items_in_set": [{
+                        "id": "item",
+                        "url_name": "ash_prime_set",
+                        "icon": "items/images/en/ash_prime_set.png",
+                        "thumb": "items/images/en/thumbs/ash_prime_set.png",
+                        "tags": ["prime", "set", "warframe"],
+                        "en": {
+                            "item_name": "Ash Prime Set",
+                            "description": "A set of parts.",
+                        },
+                    }],
+                },
+            },
+        }),
+    );
+    transport
+}
+
+async fn price_message(transport: Arc<MockTransport>) -> CreateWebhookMessage {
+    let wm_client = WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport,
+    );
+    let item_service = WarframeItemService::new(wm_client.clone())
+        .await
+        .expect("error creating item service");
+
+    item_price_message(
+        &wm_client,
+        &item_service,
+        "Ash Prime Set",
+        None,
+        "p",
+        Language::English,
+    )
+    .await
+    .expect("error creating message")
+}
+
+fn stats_values(message: CreateWebhookMessage) -> Vec<String> {
+    let embeds = message.embeds.expect("missing embeds");
+    embeds[0]
+        .fields
+        .iter()
+        .flatten()
+        .map(|field| field.value.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn price_message_summarizes_ingame_sell_orders() {
+    let transport = mock_market(vec![
+        order("1", 60, "ingame"),
+        order("2", 45, "ingame"),
+        order("3", 30, "offline"),
+        order("4", 50, "ingame"),
+    ]);
+    let message = price_message(transport.clone()).await;
+
+    let embeds = message.embeds.expect("missing embeds");
+    let main_embed = &embeds[0];
+    assert_eq!(main_embed.title.as_deref(), Some("Ash Prime Set"));
+    assert_eq!(
+        main_embed.url.as_deref(),
+        Some("https://warframe.market/items/ash_prime_set")
+    );
+    let fields = main_embed.fields.as_ref().expect("missing fields");
+    assert_eq!(fields[0].name, "Price range");
+    assert_eq!(fields[0].value, "45p - 60p");
+    let footer = main_embed.footer.as_ref().expect("missing footer");
+    assert_eq!(footer.text, "Data from warframe.market");
+    assert!(main_embed.timestamp.is_some());
+    assert_eq!(embeds[1].title.as_deref(), Some("Best Offers (3 sellers)"));
+
+    let paths: Vec<_> = transport
+        .requests()
+        .into_iter()
+        .map(|(_, url)| url.path().to_owned())
+        .collect();
+    assert_eq!(paths, ["/v1/items", "/v1/items/ash_prime_set/orders"]);
+}
+
+#[tokio::test]
+async fn price_message_notes_when_prices_are_cached() {
+    let transport = mock_market(vec![order("1", 60, "ingame")]);
+    let wm_client = WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig {
+            item_orders_cache: Some(ItemOrdersCacheConfig::default()),
+            ..Default::default()
+        },
+        Arc::new(NoopMetrics),
+        transport.clone(),
+    );
+    let item_service = WarframeItemService::new(wm_client.clone())
+        .await
+        .expect("error creating item service");
+    let footer = || async {
+        let message = item_price_message(
+            &wm_client,
+            &item_service,
+            "Ash Prime Set",
+            None,
+            "p",
+            Language::English,
+        )
+        .await
+        .expect("error creating message");
+        let embeds = message.embeds.expect("missing embeds");
+        let footer = embeds[0].footer.clone().expect("missing footer");
+        (footer.text, embeds[0].timestamp.expect("missing timestamp"))
+    };
+
+    let (fresh, fresh_timestamp) = footer().await;
+    assert_eq!(fresh, "Data from warframe.market");
+
+    let (cached, cached_timestamp) = footer().await;
+    let time = fresh_timestamp.0.format("%H:%M");
+    assert_eq!(
+        cached,
+        format!("Data from warframe.market • prices as of {time} UTC (cached)")
+    );
+    assert_eq!(cached_timestamp, fresh_timestamp);
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn price_message_breaks_price_ties_deterministically() {
+    let with_reputation = |id: &str, reputation: i32| {
+        let mut order = order(id, 40, "ingame");
+        order["user"]["reputation"] = json!(reputation);
+        order
+    };
+    let transport = mock_market(vec![
+        with_reputation("D", 5),
+        with_reputation("B", 20),
+        with_reputation("C", 5),
+        with_reputation("A", 5),
+    ]);
+    let embeds = price_message(transport)
+        .await
+        .embeds
+        .expect("missing embeds");
+    let offers = embeds[1].description.as_deref().expect("missing offers");
+    let sellers: Vec<_> = offers
+        .lines()
+        .filter_map(|line| line.strip_prefix("**"))
+        .filter_map(|line| line.split("**").next())
+        .collect();
+    assert_eq!(sellers, ["SellerB", "SellerA", "SellerC"]);
+}
+
+#[tokio::test]
+async fn price_message_handles_a_single_order() {
+    let transport = mock_market(vec![order("1", 40, "ingame")]);
+    let values = stats_values(price_message(transport).await);
+    assert_eq!(values[..4], ["40p - 40p", "40.00p", "40.0p", "n/a"]);
+}
+
+#[tokio::test]
+async fn price_message_handles_huge_prices() {
+    let transport = mock_market(vec![
+        order("1", u32::MAX, "ingame"),
+        order("2", u32::MAX, "ingame"),
+    ]);
+    let values = stats_values(price_message(transport).await);
+    assert_eq!(values[1], "4294967295.00p");
+}
+
+#[tokio::test]
+async fn price_message_handles_two_orders() {
+    let transport =
+        mock_market(vec![order("1", 40, "ingame"), order("2", 50, "ingame")]);
+    let values = stats_values(price_message(transport).await);
+    assert_eq!(values[..4], ["40p - 50p", "45.00p", "45.0p", "7.07"]);
+}
+
+fn mock_mod_market(orders: Vec<serde_json::Value>) -> Arc<MockTransport> {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond_json(
+        Method::GET,
+        "/v1/items",
+        json!({
+            "payload": {
+                "items": [{
+                    "id": "mod",
+                    "url_name": "primed_flow",
+                    "thumb": "items/images/en/thumbs/primed_flow.png",
+                    "item_name": "Primed Flow",
+                }],
+            },
+        }),
+    );
+    transport.respond_json(
+        Method::GET,
+        "/v1/items/primed_flow/orders",
+        json!({
+            "payload": {
+                "orders": orders,
+            },
+            "include": {
+                "item": {
+                    "id": "mod",
+                    "items_in_set": [{
+                        "id": "mod",
+                        "url_name": "primed_flow",
+                        "icon": "items/images/en/primed_flow.png",
+                        "thumb": "items/images/en/thumbs/primed_flow.png",
+                        "tags": ["mod", "legendary", "warframe"],
+                        "mod_max_rank": 10,
+                        "en": {
+                            "item_name": "Primed Flow",
+                            "description": "Increases maximum energy.",
+                        },
+                    }],
+                },
+            },
+        }),
+    );
+    transport
+}
+
+#[tokio::test]
+async fn lookup_infers_mod_ranks() {
+    let ranked = |id: &str, platinum: u32, rank: u8| {
+        let mut order = order(id, platinum, "ingame");
+        order["mod_rank"] = json!(rank);
+        order
+    };
+    let transport = mock_mod_market(vec![
+        ranked("1", 20, 0),
+        ranked("2", 90, 10),
+        ranked("3", 25, 0),
+    ]);
+    let wm_client = WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport,
+    );
+    let item_service = WarframeItemService::new(wm_client.clone())
+        .await
+        .expect("error creating item service");
+
+    // Ranked orders aren't normal items
+    let message = item_price_message(
+        &wm_client,
+        &item_service,
+        "Primed Flow",
+        None,
+        "p",
+        Language::English,
+    )
+    .await
+    .expect("error creating message");
+    let embeds = message.embeds.expect("missing embeds");
+    assert_eq!(embeds[0].description.as_deref(), Some("No orders found"));
+
+    let message = lookup_price_message(
+        &wm_client,
+        &item_service,
+        "Primed Flow",
+        None,
+        "p",
+        Language::English,
+    )
+    .await
+    .expect("error creating message");
+    let values = stats_values(message);
+    assert_eq!(values[0], "20p - 25p");
+}
+
+#[tokio::test]
+async fn mixed_rank_orders_default_to_unranked() {
+    let ranked = |id: &str, platinum: u32, rank: u8| {
+        let mut order = order(id, platinum, "ingame");
+        order["mod_rank"] = json!(rank);
+        order
+    };
+    let transport = mock_mod_market(vec![
+        ranked("1", 20, 0),
+        ranked("2", 90, 10),
+        ranked("3", 30, 0),
+        ranked("4", 100, 10),
+        ranked("5", 50, 5),
+    ]);
+    let wm_client = WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport,
+    );
+    let item_service = WarframeItemService::new(wm_client.clone())
+        .await
+        .expect("error creating item service");
+
+    let message = lookup_price_message(
+        &wm_client,
+        &item_service,
+        "Primed Flow",
+        None,
+        "p",
+        Language::English,
+    )
+    .await
+    .expect("error creating message");
+    let values = stats_values(message);
+    assert_eq!(values[0], "20p - 30p");
+    assert_eq!(values[2], "25.0p");
+    assert_eq!(values[4], "2 in-game, 0 online, 2 total");
+}
+
+#[tokio::test]
+async fn price_message_counts_orders_by_status() {
+    let transport = mock_market(vec![
+        order("1", 40, "ingame"),
+        order("2", 45, "online"),
+        order("3", 50, "offline"),
+        order("4", 55, "online"),
+        order("5", 60, "ingame"),
+    ]);
+    let values = stats_values(price_message(transport).await);
+    assert_eq!(values[0], "40p - 60p");
+    assert_eq!(values[4], "2 in-game, 2 online, 5 total");
+}
+
+fn item_order(rank: serde_json::Value) -> ItemOrder {
+    let mut order = order("1", 25, "ingame");
+    order
+        .as_object_mut()
+        .unwrap()
+        .extend(rank.as_object().unwrap().clone());
+    serde_json::from_value(order).unwrap()
+}
+
+#[test]
+fn whisper_message_for_item() {
+    let order = item_order(json!({}));
+    assert_eq!(
+        whisper_message(&order, "Ash Prime Set"),
+        "/w Seller1 Hi! I want to buy: Ash Prime Set for 25 platinum. (warframe.market)"
+    );
+}
+
+#[test]
+fn whisper_message_for_ranked_mod() {
+    let order = item_order(json!({ "mod_rank": 10 }));
+    assert_eq!(
+        whisper_message(&order, "Primed Continuity"),
+        "/w Seller1 Hi! I want to buy: Primed Continuity (rank 10) for 25 platinum. (warframe.market)"
+    );
+}
+
+#[test]
+fn whisper_message_for_relic() {
+    let order = item_order(json!({ "subtype": "radiant" }));
+    assert_eq!(
+        whisper_message(&order, "Lith A1 Relic"),
+        "/w Seller1 Hi! I want to buy: Lith A1 Relic (radiant) for 25 platinum. (warframe.market)"
+    );
+}
+
+#[test]
+fn item_url_links_to_market_page() {
+    assert_eq!(
+        item_url("primed_continuity"),
+        "https://warframe.market/items/primed_continuity"
+    );
+    assert_eq!(
+        item_url("odd name/?#"),
+        "https://warframe.market/items/odd%20name%2F%3F%23"
+    );
+}
+
+fn error_description(message: &CreateWebhookMessage) -> &str {
+    message.embeds.as_ref().unwrap()[0]
+        .description
+        .as_deref()
+        .unwrap()
+}
+
+#[test]
+fn long_errors_are_truncated() {
+    let error = anyhow::anyhow!("{}", "a".repeat(10_000));
+    let message = error_details_response(Language::English, &error);
+    let description = error_description(&message);
+    assert_eq!(description.chars().count(), Embed::MAX_DESCRIPTION_LENGTH);
+    assert!(description.starts_with("```\n\"aaa"));
+    assert!(description.ends_with("…(truncated)\n```"));
+    assert!(message.validate_content().is_ok());
+}
+
+#[test]
+fn short_errors_are_not_truncated() {
+    let error = anyhow::anyhow!("not found");
+    let message = error_details_response(Language::English, &error);
+    assert_eq!(error_description(&message), "```\n\"not found\"\n```");
+}
@@ -0,0 +1,158 @@
+use reqwest::{Client, Method, StatusCode};
+use serde_json::json;
+use std::sync::Arc;
+use wfbp_http::test_util::MockTransport;
+use wfbp_logic::{commands::seller_orders_message, localization::Language};
+use wfbp_wm::{models::Platform, NoopMetrics, WmClientConfig, WmRestClient};
+
+fn profile_order(
+    item_name: &str,
+    platinum: u32,
+    platform: &str,
+    rank: serde_json::Value,
+) -> serde_json::Value {
+    let url_name = item_name.to_lowercase().replace(' ', "_");
+    let mut order = json!({
+        "id": format!("order-{url_name}"),
+        "platinum": platinum,
+        "quantity": 2,
+        "order_type": "sell",
+        "platform": platform,
+        "creation_date": "2021-01-01T00:00:00.000+00:00",
+        "last_update": "2021-01-01T00:00:00.000+00:00",
+        "visible": true,
+        "item": {
+            "id": format!("item-{url_name}"),
+            "url_name": url_name,
+            "thumb": format!("items/images/en/thumbs/{url_name}.png"),
+            "en": { "item_name": item_name },
+        },
+    });
+    if let (Some(order), Some(rank)) = (order.as_object_mut(), rank.as_object())
+    {
+        order.extend(rank.clone());
+    }
+    order
+}
+
+fn client(transport: Arc<MockTransport>) -> WmRestClient {
+    WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport,
+    )
+}
+
+#[tokio::test]
+async fn seller_lists_sell_orders() {
+    let sell_orders = vec![
+        profile_order("Primed Flow", 90, "pc", json!({ "mod_rank": 10 })),
+        profile_order("Axi A1 Relic", 5, "pc", json!({ "subtype": "radiant" })),
+        profile_order("Ash Prime Set", 60, "pc", json!({})),
+        profile_order("Ash Prime Set", 55, "xbox", json!({})),
+    ];
+    let transport = Arc::new(MockTransport::new());
+    transport.respond_json(
+        Method::GET,
+        "/v1/profile/Trader/orders",
+        json!({
+            "payload": {
+                "sell_orders": sell_orders,
+                "buy_orders": [],
+            },
+        }),
+    );
+
+    let message = seller_orders_message(
+        &client(transport),
+        "Trader",
+        Some(Platform::PC),
+        "p",
+        Language::English,
+    )
+    .await
+    .expect("error creating message");
+
+    let embeds = message.embeds.expect("missing embeds");
+    assert_eq!(embeds[0].title.as_deref(), Some("Sell orders by Trader"));
+    let lines: Vec<_> = embeds[0]
+        .description
+        .as_deref()
+        .expect("missing description")
+        .lines()
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "**Ash Prime Set**: 60p × 2",
+            "**Axi A1 Relic** (radiant): 5p × 2",
+            "**Primed Flow** (rank 10): 90p × 2",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn seller_reports_unknown_users() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(
+        Method::GET,
+        "/v1/profile/Ghost/orders",
+        StatusCode::NOT_FOUND,
+        json!({ "error": { "request": ["app.profile.user_not_found"] } })
+            .to_string(),
+    );
+
+    let message = seller_orders_message(
+        &client(transport),
+        "Ghost",
+        None,
+        "p",
+        Language::English,
+    )
+    .await
+    .expect("error creating message");
+
+    let embeds = message.embeds.expect("missing embeds");
+    assert_eq!(
+        embeds[0].description.as_deref(),
+        Some("No user with the name 'Ghost' found")
+    );
+}
+
+#[tokio::test]
+async fn seller_names_are_percent_encoded() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond_json(
+        Method::GET,
+        "/v1/profile/a%2Fb%3F%20c/orders",
+        json!({
+            "payload": {
+                "sell_orders": [
+                    profile_order("Ash Prime Set", 60, "pc", json!({})),
+                ],
+                "buy_orders": [],
+            },
+        }),
+    );
+
+    let message = seller_orders_message(
+        &client(transport.clone()),
+        "a/b? c",
+        None,
+        "p",
+        Language::English,
+    )
+    .await
+    .expect("error creating message");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].1.path(), "/v1/profile/a%2Fb%3F%20c/orders");
+    assert_eq!(requests[0].1.query(), None);
+    let embeds = message.embeds.expect("missing embeds");
+    assert_eq!(
+        embeds[0].url.as_deref(),
+        Some("https://warframe.market/profile/a%2Fb%3F%20c")
+    );
+}
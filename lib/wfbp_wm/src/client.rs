@@ -1,48 +1,228 @@
 use crate::{
-    middleware::{CacheLayer, LocalCacheStorage},
-    routes::WmRouteInfo,
+    middleware::{
+        CacheLayer, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerLayer,
+        LocalCacheStorage, RateLimitConfig, RateLimitLayer, TokenBucket,
+    },
+    models::{Language, Platform},
+    routes::{GetItemOrders, WmRouteInfo},
+    FetchedItemOrders, ItemOrders, ItemOrdersCache, ItemOrdersCacheConfig,
+    MarketUnavailable, Metrics, NoopMetrics, SingleFlight,
 };
 use async_trait::async_trait;
-use reqwest::{Client, RequestBuilder, Response};
+use chrono::Utc;
+use reqwest::Client;
 use serde::{de::DeserializeOwned, Serialize};
-use std::fmt::Debug;
-use tower::{util::BoxLayer, ServiceBuilder, ServiceExt};
+use std::{fmt::Debug, sync::Arc};
+use tower::{ServiceBuilder, ServiceExt};
 use wfbp_http::{
     middleware::{
-        BackoffLayer, ExecuteRequestService, JitterLayer, LimitLayer,
-        RestRequestBuilder, RetryLayer, RouteLayer,
+        ExecuteRequestService, RetryConfig, RouteLayer,
         TransientRequestRetryPolicy,
     },
-    RequestError, RestClient, RestRequestLayer, Route,
+    RequestError, ReqwestTransport, RestClient, RestRequestLayer, Route,
+    StandardRestClient, Transport,
 };
 
+type OrdersKey = (String, Option<Platform>);
+type OrdersResult = Result<FetchedItemOrders, Arc<RequestError>>;
+
+/// Configuration for a [`WmRestClient`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WmClientConfig {
+    /// When to stop sending requests because warframe.market appears to be
+    /// down.
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// How item orders are cached, or [`None`] to always request them.
+    pub item_orders_cache: Option<ItemOrdersCacheConfig>,
+    /// How quickly requests can be sent to warframe.market.
+    pub rate_limit: RateLimitConfig,
+    /// The platform to get orders for when a request doesn't specify one.
+    pub default_platform: Platform,
+    /// The language to request localized item details in.
+    pub default_language: Language,
+    /// How failed requests are retried.
+    pub retry: RetryConfig,
+}
+
 #[derive(Clone, Debug)]
 pub struct WmRestClient {
     cache_layer: CacheLayer<LocalCacheStorage>,
     route_layer: RouteLayer,
     request_layer: RestRequestLayer,
+    item_orders_cache: Option<ItemOrdersCache>,
+    item_orders_in_flight: SingleFlight<OrdersKey, OrdersResult>,
+    default_platform: Platform,
+    default_language: Language,
+    transport: ExecuteRequestService,
 }
 
 impl WmRestClient {
     pub const BASE_URL: &'static str = "https://api.warframe.market/v1";
 
     pub fn new(client: Client) -> Self {
+        Self::new_with_config(client, WmClientConfig::default())
+    }
+
+    pub fn new_with_config(client: Client, config: WmClientConfig) -> Self {
+        Self::new_with_metrics(client, config, Arc::new(NoopMetrics))
+    }
+
+    /// Creates a client which reports its requests to a [`Metrics`]
+    /// implementation.
+    pub fn new_with_metrics(
+        client: Client,
+        config: WmClientConfig,
+        metrics: Arc<dyn Metrics>,
+    ) -> Self {
+        Self::new_with_transport(
+            client,
+            config,
+            metrics,
+            Arc::new(ReqwestTransport),
+        )
+    }
+
+    /// Creates a client which sends its requests using a different
+    /// [`Transport`].
+    pub fn new_with_transport(
+        client: Client,
+        config: WmClientConfig,
+        metrics: Arc<dyn Metrics>,
+        transport: Arc<dyn Transport>,
+    ) -> Self {
         let cache_layer = CacheLayer::new(LocalCacheStorage::default());
-        let route_layer = RouteLayer::new(client, Self::BASE_URL.into());
-        let request_layer = ServiceBuilder::new()
-            .layer(RetryLayer::new(TransientRequestRetryPolicy::default()))
-            .layer(LimitLayer::new(10))
-            .layer(BackoffLayer::default())
-            .layer(JitterLayer::default())
-            .map_request(RequestBuilder::from)
-            .map_err(RequestError::from)
-            .check_service::<ExecuteRequestService, RestRequestBuilder, Response, RequestError>();
+        let builder = StandardRestClient::builder(client, Self::BASE_URL)
+            .transport(transport)
+            .retry_policy(
+                TransientRequestRetryPolicy::default().honoring_retry_after(),
+            )
+            .retry(config.retry)
+            .layer(CircuitBreakerLayer::new(CircuitBreaker::new(
+                config.circuit_breaker,
+            )))
+            .layer(RateLimitLayer::new(
+                TokenBucket::new(config.rate_limit),
+                metrics,
+            ));
 
         Self {
             cache_layer,
-            route_layer,
-            request_layer: BoxLayer::new(request_layer),
+            route_layer: builder.build_route_layer(),
+            request_layer: builder.build_request_layer(),
+            item_orders_cache: config
+                .item_orders_cache
+                .map(ItemOrdersCache::new),
+            item_orders_in_flight: SingleFlight::new(),
+            default_platform: config.default_platform,
+            default_language: config.default_language,
+            transport: builder.build_transport(),
+        }
+    }
+
+    /// The platform to get orders for when a request doesn't specify one.
+    pub fn default_platform(&self) -> Platform {
+        self.default_platform
+    }
+
+    /// The language item details are requested in by default.
+    pub fn default_language(&self) -> Language {
+        self.default_language
+    }
+
+    /// Gets the orders for an item, reusing a recent response if one is
+    /// cached. Set `bypass_cache` to always request fresh orders. If no
+    /// platform is given, the client's default platform is used. Item details
+    /// are requested in the client's default language.
+    pub async fn get_item_orders(
+        &self,
+        url_name: String,
+        platform: Option<Platform>,
+        bypass_cache: bool,
+    ) -> Result<ItemOrders, RequestError> {
+        self.fetch_item_orders(url_name, platform, bypass_cache)
+            .await
+            .map(|fetched| fetched.orders)
+    }
+
+    /// Gets the orders for an item like
+    /// [`get_item_orders`](WmRestClient::get_item_orders), along with when
+    /// they were fetched and whether they came from the cache.
+    pub async fn fetch_item_orders(
+        &self,
+        url_name: String,
+        platform: Option<Platform>,
+        bypass_cache: bool,
+    ) -> Result<FetchedItemOrders, RequestError> {
+        let platform = Some(platform.unwrap_or(self.default_platform));
+        if !bypass_cache {
+            let cached = self
+                .item_orders_cache
+                .as_ref()
+                .and_then(|cache| cache.get(&url_name, platform));
+            if let Some(orders) = cached {
+                return Ok(orders);
+            }
+        }
+
+        // Concurrent lookups for the same orders share one request
+        let client = self.clone();
+        let key = (url_name.clone(), platform);
+        let orders = self
+            .item_orders_in_flight
+            .run(key, move || async move {
+                let language = Some(client.default_language);
+                let orders = GetItemOrders::execute(
+                    &client,
+                    url_name.clone(),
+                    platform,
+                    language,
+                )
+                .await
+                .map_err(Arc::new)?;
+                let fetched_at = Utc::now();
+                if let Some(cache) = client.item_orders_cache.as_ref() {
+                    cache.insert_fetched(
+                        url_name,
+                        platform,
+                        orders.clone(),
+                        fetched_at,
+                    );
+                }
+
+                Ok(FetchedItemOrders {
+                    orders,
+                    fetched_at,
+                    cached: false,
+                })
+            })
+            .await;
+        orders
+            .map_err(|error| Arc::try_unwrap(error).unwrap_or_else(clone_error))
+    }
+}
+
+/// Copies an error shared between coalesced requests. API errors and open
+/// circuits are copied as-is. Other errors are shared through
+/// [`RequestError::Custom`], which [`crate::WmRequestErrorExt`] looks
+/// through.
+fn clone_error(error: Arc<RequestError>) -> RequestError {
+    match &*error {
+        RequestError::ApiError {
+            status,
+            code,
+            message,
+        } => RequestError::ApiError {
+            status: *status,
+            code: *code,
+            message: message.clone(),
+        },
+        RequestError::Custom(inner) => {
+            match inner.downcast_ref::<MarketUnavailable>() {
+                Some(&unavailable) => unavailable.into(),
+                None => RequestError::Custom(error.into()),
+            }
         }
+        _ => RequestError::Custom(error.into()),
     }
 }
 
@@ -59,7 +239,7 @@ where
             .layer(&self.route_layer)
             .layer(&self.request_layer)
             .check_service::<ExecuteRequestService, R, R::Response, RequestError>()
-            .service(ExecuteRequestService::default());
+            .service(self.transport.clone());
 
         // Execute service
         service.oneshot(route).await
@@ -0,0 +1,115 @@
+use derive_more::{Display, Error};
+use reqwest::StatusCode;
+use std::{sync::Arc, time::Duration};
+use wfbp_http::RequestError;
+
+/// warframe.market is failing too often, so requests are not being sent.
+#[derive(Clone, Copy, Debug, Display, Error)]
+#[display(fmt = "warframe.market is unavailable")]
+pub struct MarketUnavailable {
+    /// How long until the next request will be attempted.
+    pub retry_after: Duration,
+}
+
+impl From<MarketUnavailable> for RequestError {
+    fn from(error: MarketUnavailable) -> Self {
+        RequestError::Custom(error.into())
+    }
+}
+
+/// The reason a request to warframe.market failed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Display)]
+pub enum WmErrorKind {
+    /// The requested resource, like an item or user, doesn't exist.
+    #[display(fmt = "not found")]
+    NotFound,
+    /// warframe.market is down or couldn't be reached.
+    #[display(fmt = "unavailable")]
+    Unavailable,
+    /// The request was rejected for exceeding the rate limit.
+    #[display(fmt = "rate limited")]
+    RateLimited,
+    /// The response couldn't be parsed.
+    #[display(fmt = "invalid response")]
+    Parse,
+}
+
+impl WmErrorKind {
+    /// Gets the kind of error for an unsuccessful response status, if the
+    /// status has a known meaning.
+    pub fn from_status(status: StatusCode) -> Option<Self> {
+        match status {
+            StatusCode::NOT_FOUND => Some(WmErrorKind::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Some(WmErrorKind::RateLimited),
+            status if status.is_server_error() => {
+                Some(WmErrorKind::Unavailable)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Extension methods for reading warframe.market-specific details from a
+/// [`RequestError`]. Errors shared between coalesced requests are wrapped in
+/// an [`Arc`], and these look through it.
+pub trait WmRequestErrorExt {
+    /// Gets why the request failed, if known.
+    fn kind(&self) -> Option<WmErrorKind>;
+
+    /// Whether the request was short-circuited because warframe.market
+    /// appears to be down.
+    fn is_market_unavailable(&self) -> bool;
+
+    /// Whether the requested resource, like an item or user, doesn't exist.
+    fn is_not_found(&self) -> bool;
+
+    /// Whether warframe.market rejected the request for exceeding its rate
+    /// limit.
+    fn is_rate_limited(&self) -> bool;
+}
+
+impl WmRequestErrorExt for RequestError {
+    fn kind(&self) -> Option<WmErrorKind> {
+        match self {
+            RequestError::ApiError { status, .. } => {
+                WmErrorKind::from_status(*status)
+            }
+            RequestError::ReqwestError(error) if error.is_decode() => {
+                Some(WmErrorKind::Parse)
+            }
+            RequestError::Decompression(_) => Some(WmErrorKind::Parse),
+            RequestError::ReqwestError(error)
+                if error.is_timeout() || error.is_connect() =>
+            {
+                Some(WmErrorKind::Unavailable)
+            }
+            error if error.is_market_unavailable() => {
+                Some(WmErrorKind::Unavailable)
+            }
+            RequestError::Custom(error) => {
+                error.downcast_ref::<Arc<RequestError>>()?.kind()
+            }
+            _ => None,
+        }
+    }
+
+    fn is_market_unavailable(&self) -> bool {
+        match self {
+            RequestError::Custom(error) => {
+                error.is::<MarketUnavailable>()
+                    || error
+                        .downcast_ref::<Arc<RequestError>>()
+                        .is_some_and(|error| error.is_market_unavailable())
+            }
+            _ => false,
+        }
+    }
+
+    fn is_not_found(&self) -> bool {
+        self.kind() == Some(WmErrorKind::NotFound)
+    }
+
+    fn is_rate_limited(&self) -> bool {
+        self.kind() == Some(WmErrorKind::RateLimited)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::models::{
+    ItemOrdersPayload, ItemPayload, PayloadResponse, Platform,
+};
+use chrono::{DateTime, Utc};
+use lru::LruCache;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+pub type ItemOrders = PayloadResponse<ItemOrdersPayload, ItemPayload>;
+
+type Key = (String, Option<Platform>);
+type Entry = (Instant, DateTime<Utc>, ItemOrders);
+
+/// Orders for an item, along with when they were fetched.
+#[derive(Clone, Debug)]
+pub struct FetchedItemOrders {
+    pub orders: ItemOrders,
+    /// When the orders were received from warframe.market.
+    pub fetched_at: DateTime<Utc>,
+    /// Whether the orders came from the cache instead of a new request.
+    pub cached: bool,
+}
+
+/// Configuration for an [`ItemOrdersCache`].
+#[derive(Clone, Copy, Debug)]
+pub struct ItemOrdersCacheConfig {
+    /// Maximum number of responses to keep. The least recently used response
+    /// is evicted when the cache is full.
+    pub capacity: usize,
+    /// How long a response is reused for.
+    pub ttl: Duration,
+}
+
+impl Default for ItemOrdersCacheConfig {
+    fn default() -> Self {
+        ItemOrdersCacheConfig {
+            capacity: 256,
+            ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Short-lived cache of parsed item orders, so bursts of lookups for the same
+/// item only send one request to warframe.market.
+#[derive(Clone, Debug)]
+pub struct ItemOrdersCache {
+    ttl: Duration,
+    entries: Arc<Mutex<LruCache<Key, Entry>>>,
+}
+
+impl ItemOrdersCache {
+    pub fn new(config: ItemOrdersCacheConfig) -> Self {
+        ItemOrdersCache {
+            ttl: config.ttl,
+            entries: Arc::new(Mutex::new(LruCache::new(config.capacity))),
+        }
+    }
+
+    /// Gets the cached orders for an item if they haven't expired yet.
+    pub fn get(
+        &self,
+        url_name: &str,
+        platform: Option<Platform>,
+    ) -> Option<FetchedItemOrders> {
+        let key = (url_name.to_owned(), platform);
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(&key) {
+            Some((expires, fetched_at, orders))
+                if Instant::now() < *expires =>
+            {
+                Some(FetchedItemOrders {
+                    orders: orders.clone(),
+                    fetched_at: *fetched_at,
+                    cached: true,
+                })
+            }
+            Some(_) => {
+                entries.pop(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches the orders for an item, which were just fetched.
+    pub fn insert(
+        &self,
+        url_name: String,
+        platform: Option<Platform>,
+        orders: ItemOrders,
+    ) {
+        self.insert_fetched(url_name, platform, orders, Utc::now());
+    }
+
+    /// Caches the orders for an item, which were fetched at `fetched_at`.
+    pub fn insert_fetched(
+        &self,
+        url_name: String,
+        platform: Option<Platform>,
+        orders: ItemOrders,
+        fetched_at: DateTime<Utc>,
+    ) {
+        let expires = Instant::now() + self.ttl;
+        self.entries
+            .lock()
+            .unwrap()
+            .put((url_name, platform), (expires, fetched_at, orders));
+    }
+}
@@ -3,5 +3,13 @@ pub mod models;
 pub mod routes;
 
 mod client;
+mod error;
+mod item_orders_cache;
+mod metrics;
+mod single_flight;
 
 pub use client::*;
+pub use error::*;
+pub use item_orders_cache::*;
+pub use metrics::*;
+pub use single_flight::*;
@@ -0,0 +1,13 @@
+use std::{fmt::Debug, time::Duration};
+
+/// Receives metrics about requests sent to warframe.market.
+pub trait Metrics: Debug + Send + Sync + 'static {
+    /// Called after a request was delayed due to the rate limit.
+    fn on_rate_limit_wait(&self, _duration: Duration) {}
+}
+
+/// Ignores all metrics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {}
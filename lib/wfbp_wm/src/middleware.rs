@@ -1,3 +1,7 @@
 mod cache;
+mod circuit_breaker;
+mod rate_limit;
 
 pub use cache::*;
+pub use circuit_breaker::*;
+pub use rate_limit::*;
@@ -0,0 +1,192 @@
+use crate::MarketUnavailable;
+use futures::future::BoxFuture;
+use reqwest::Response;
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use tracing::warn;
+use wfbp_http::RequestError;
+
+/// Configuration for a [`CircuitBreaker`].
+#[derive(Clone, Copy, Debug)]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive failures before the circuit opens.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial request is allowed.
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum CircuitState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests fail immediately.
+    Open,
+    /// A single trial request is allowed to check if the service recovered.
+    HalfOpen,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { trial_expires: Instant },
+}
+
+/// Tracks consecutive failures and short-circuits requests while the remote
+/// service appears to be down.
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Arc<Mutex<State>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        CircuitBreaker {
+            config,
+            state: Arc::new(Mutex::new(State::Closed { failures: 0 })),
+        }
+    }
+
+    /// The current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match *state {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { until } if Instant::now() < until => {
+                CircuitState::Open
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                CircuitState::HalfOpen
+            }
+        }
+    }
+
+    /// Checks whether a request may be sent. While half-open, only one trial
+    /// request is allowed until it completes or the cooldown elapses again.
+    pub fn try_acquire(&self) -> Result<(), MarketUnavailable> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        match *state {
+            State::Closed { .. } => Ok(()),
+            State::Open { until }
+            | State::HalfOpen {
+                trial_expires: until,
+            } if now < until => Err(MarketUnavailable {
+                retry_after: until - now,
+            }),
+            State::Open { .. } | State::HalfOpen { .. } => {
+                *state = State::HalfOpen {
+                    trial_expires: now + self.config.cooldown,
+                };
+                Ok(())
+            }
+        }
+    }
+
+    /// Records a successful request, closing the circuit.
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap() = State::Closed { failures: 0 };
+    }
+
+    /// Records a failed request, opening the circuit if the failure threshold
+    /// was reached or the trial request failed.
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            State::Open { .. } | State::HalfOpen { .. } => {
+                self.config.failure_threshold
+            }
+        };
+
+        *state = if failures >= self.config.failure_threshold {
+            warn!(failures, "circuit breaker opened");
+            State::Open {
+                until: Instant::now() + self.config.cooldown,
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+/// Fails requests immediately with [`MarketUnavailable`] after too many
+/// consecutive server errors.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerLayer {
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakerLayer {
+    pub fn new(breaker: CircuitBreaker) -> Self {
+        CircuitBreakerLayer { breaker }
+    }
+}
+
+impl<Next> Layer<Next> for CircuitBreakerLayer {
+    type Service = CircuitBreakerService<Next>;
+
+    fn layer(&self, next: Next) -> Self::Service {
+        CircuitBreakerService {
+            breaker: self.breaker.clone(),
+            next,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerService<Next> {
+    breaker: CircuitBreaker,
+    next: Next,
+}
+
+impl<Req, Next> Service<Req> for CircuitBreakerService<Next>
+where
+    Next: Service<Req, Response = Response, Error = RequestError>,
+    Next::Future: Send + 'static,
+{
+    type Response = Next::Response;
+    type Error = Next::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.next.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        if let Err(error) = self.breaker.try_acquire() {
+            return Box::pin(async move { Err(error.into()) });
+        }
+
+        let breaker = self.breaker.clone();
+        let next_fut = self.next.call(req);
+        Box::pin(async move {
+            let result = next_fut.await;
+            match &result {
+                Ok(res) if !res.status().is_server_error() => {
+                    breaker.record_success()
+                }
+                _ => breaker.record_failure(),
+            }
+
+            result
+        })
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{Metrics, NoopMetrics};
+use futures::future::BoxFuture;
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Instant;
+use tower::{Layer, Service};
+use wfbp_http::RequestError;
+
+/// Configuration for a [`TokenBucket`].
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimitConfig {
+    /// How many requests can be sent each second on average. A rate that
+    /// isn't a positive, finite number (such as 0) disables rate limiting.
+    pub requests_per_second: f64,
+    /// How many requests can be sent at once before they are spaced out.
+    pub burst: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            requests_per_second: 3.0,
+            burst: 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Spaces requests out so they don't exceed a rate limit. Each request
+/// reserves the next available slot, so waiting requests are sent in the
+/// order they were made.
+#[derive(Clone, Debug)]
+pub struct TokenBucket {
+    config: RateLimitConfig,
+    state: Arc<Mutex<State>>,
+}
+
+impl TokenBucket {
+    pub fn new(config: RateLimitConfig) -> Self {
+        TokenBucket {
+            config,
+            state: Arc::new(Mutex::new(State {
+                tokens: config.burst.max(1) as f64,
+                updated: Instant::now(),
+            })),
+        }
+    }
+
+    /// Takes a token from the bucket, returning how long to wait before the
+    /// request can be sent.
+    pub fn reserve(&self) -> Duration {
+        let rate = self.config.requests_per_second;
+        if !(rate.is_finite() && rate > 0.0) {
+            return Duration::ZERO;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(state.updated);
+        let capacity = self.config.burst.max(1) as f64;
+        state.tokens =
+            (state.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        state.updated = now;
+        state.tokens -= 1.0;
+
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / rate)
+        }
+    }
+}
+
+/// Delays requests to stay within warframe.market's rate limit.
+#[derive(Clone, Debug)]
+pub struct RateLimitLayer {
+    bucket: TokenBucket,
+    metrics: Arc<dyn Metrics>,
+}
+
+impl RateLimitLayer {
+    pub fn new(bucket: TokenBucket, metrics: Arc<dyn Metrics>) -> Self {
+        RateLimitLayer { bucket, metrics }
+    }
+}
+
+impl Default for RateLimitLayer {
+    fn default() -> Self {
+        RateLimitLayer::new(
+            TokenBucket::new(RateLimitConfig::default()),
+            Arc::new(NoopMetrics),
+        )
+    }
+}
+
+impl<Next> Layer<Next> for RateLimitLayer {
+    type Service = RateLimitService<Next>;
+
+    fn layer(&self, next: Next) -> Self::Service {
+        RateLimitService {
+            bucket: self.bucket.clone(),
+            metrics: self.metrics.clone(),
+            next,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimitService<Next> {
+    bucket: TokenBucket,
+    metrics: Arc<dyn Metrics>,
+    next: Next,
+}
+
+impl<Req, Next> Service<Req> for RateLimitService<Next>
+where
+    Req: Send + 'static,
+    Next: Service<Req, Error = RequestError> + Clone + Send + 'static,
+    Next::Future: Send + 'static,
+{
+    type Response = Next::Response;
+    type Error = Next::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.next.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let wait = self.bucket.reserve();
+        let metrics = self.metrics.clone();
+
+        // Take the service that was polled ready, and only call it once the
+        // request is allowed to be sent
+        let clone = self.next.clone();
+        let mut next = mem::replace(&mut self.next, clone);
+        Box::pin(async move {
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+                metrics.on_rate_limit_wait(wait);
+            }
+
+            next.call(req).await
+        })
+    }
+}
@@ -1,5 +1,10 @@
 use chrono::{DateTime, FixedOffset};
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PayloadResponse<T, I = ()> {
@@ -23,6 +28,12 @@ pub struct ItemOrdersPayload {
     pub orders: Vec<ItemOrder>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProfileOrdersPayload {
+    pub sell_orders: Vec<ProfileOrder>,
+    pub buy_orders: Vec<ProfileOrder>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemShort {
     pub id: String,
@@ -45,6 +56,35 @@ pub struct ItemOrder {
     pub rank: ItemRank,
 }
 
+/// An order listed on a user's profile. Unlike [`ItemOrder`], this has the
+/// item being traded instead of the user.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProfileOrder {
+    pub id: String,
+    pub platinum: u32,
+    pub quantity: u32,
+    pub order_type: OrderType,
+    pub platform: Platform,
+    pub creation_date: DateTime<FixedOffset>,
+    pub last_update: DateTime<FixedOffset>,
+    pub item: OrderItem,
+    #[serde(flatten)]
+    pub rank: ItemRank,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrderItem {
+    pub id: String,
+    pub url_name: String,
+    pub thumb: String,
+    pub en: ItemName,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ItemName {
+    pub item_name: String,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ItemRank {
@@ -67,6 +107,48 @@ pub enum RelicRefinement {
     Radiant,
 }
 
+impl RelicRefinement {
+    /// Every refinement, from least to most refined.
+    pub const ALL: [RelicRefinement; 4] = [
+        RelicRefinement::Intact,
+        RelicRefinement::Exceptional,
+        RelicRefinement::Flawless,
+        RelicRefinement::Radiant,
+    ];
+
+    /// The name warframe.market uses for the refinement.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RelicRefinement::Intact => "intact",
+            RelicRefinement::Exceptional => "exceptional",
+            RelicRefinement::Flawless => "flawless",
+            RelicRefinement::Radiant => "radiant",
+        }
+    }
+}
+
+impl Display for RelicRefinement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for RelicRefinement {
+    type Err = UnknownRelicRefinement;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RelicRefinement::ALL
+            .into_iter()
+            .find(|refinement| refinement.name() == s)
+            .ok_or_else(|| UnknownRelicRefinement(s.to_owned()))
+    }
+}
+
+/// A relic refinement name which warframe.market doesn't use.
+#[derive(Clone, Debug, Display, Error)]
+#[display(fmt = "unknown relic refinement: '{}'", _0)]
+pub struct UnknownRelicRefinement(#[error(ignore)] pub String);
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemSet {
     pub id: String,
@@ -95,7 +177,50 @@ pub struct ItemFull {
     #[serde(default)]
     pub trading_tax: Option<u32>,
     pub en: LangInItem,
-    // TODO: there are other languages too
+    #[serde(default)]
+    pub ru: Option<LangInItem>,
+    #[serde(default)]
+    pub ko: Option<LangInItem>,
+    #[serde(default)]
+    pub de: Option<LangInItem>,
+    #[serde(default)]
+    pub fr: Option<LangInItem>,
+    #[serde(default)]
+    pub pt: Option<LangInItem>,
+    #[serde(default, rename = "zh-hans")]
+    pub zh_hans: Option<LangInItem>,
+    #[serde(default, rename = "zh-hant")]
+    pub zh_hant: Option<LangInItem>,
+    #[serde(default)]
+    pub es: Option<LangInItem>,
+    #[serde(default)]
+    pub it: Option<LangInItem>,
+    #[serde(default)]
+    pub pl: Option<LangInItem>,
+    #[serde(default)]
+    pub uk: Option<LangInItem>,
+}
+
+impl ItemFull {
+    /// Gets the item's name and description in a language, falling back to
+    /// English if there is no translation.
+    pub fn lang(&self, language: Language) -> &LangInItem {
+        let translation = match language {
+            Language::En => None,
+            Language::Ru => self.ru.as_ref(),
+            Language::Ko => self.ko.as_ref(),
+            Language::De => self.de.as_ref(),
+            Language::Fr => self.fr.as_ref(),
+            Language::Pt => self.pt.as_ref(),
+            Language::ZhHans => self.zh_hans.as_ref(),
+            Language::ZhHant => self.zh_hant.as_ref(),
+            Language::Es => self.es.as_ref(),
+            Language::It => self.it.as_ref(),
+            Language::Pl => self.pl.as_ref(),
+            Language::Uk => self.uk.as_ref(),
+        };
+        translation.unwrap_or(&self.en)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -176,3 +301,43 @@ impl Default for Platform {
         Platform::PC
     }
 }
+
+/// A language supported by warframe.market.
+#[derive(
+    Clone, Copy, PartialEq, Eq, Debug, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Language {
+    #[default]
+    En,
+    Ru,
+    Ko,
+    De,
+    Fr,
+    Pt,
+    ZhHans,
+    ZhHant,
+    Es,
+    It,
+    Pl,
+    Uk,
+}
+
+impl Language {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Ru => "ru",
+            Language::Ko => "ko",
+            Language::De => "de",
+            Language::Fr => "fr",
+            Language::Pt => "pt",
+            Language::ZhHans => "zh-hans",
+            Language::ZhHant => "zh-hant",
+            Language::Es => "es",
+            Language::It => "it",
+            Language::Pl => "pl",
+            Language::Uk => "uk",
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use crate::{
     middleware::{AsCacheInfo, CacheInfo},
     models::{
-        ItemOrdersPayload, ItemPayload, ItemShort, ItemsPayload,
-        PayloadResponse, Platform,
+        ItemOrdersPayload, ItemPayload, ItemShort, ItemsPayload, Language,
+        PayloadResponse, Platform, ProfileOrdersPayload,
     },
 };
 use http::HeaderValue;
 use reqwest::Method;
 use std::time::Duration;
-use wfbp_http::routes;
+use wfbp_http::{routes, Conditional, PathSegment};
 
 #[derive(Clone, Debug, Hash)]
 pub struct WmRouteInfo {
@@ -67,22 +67,30 @@ const HOUR: u64 = MINUTE * 60;
 const DAY: u64 = HOUR * 24;
 
 pub const PLATFORM_HEADER: &'static str = "platform";
+pub const LANGUAGE_HEADER: &str = "language";
 
 routes! {
     (
-        GetItems {},
+        GetItems {
+            if_none_match: Option<String>,
+            language: Option<Language>,
+        },
         method = GET "/items",
-        info = |method, route| -> WmRouteInfo {
-            WmRouteInfo::new_cached(
-                CacheBucket {
-                    method,
-                    route,
-                    values: vec![],
-                },
-                Some(Duration::from_secs(DAY)),
-            )
+        // Refreshed by the caller using the ETag instead
+        info = |_method, _route| -> WmRouteInfo {
+            WmRouteInfo::new_uncached()
+        },
+        if_none_match = if_none_match.as_deref(),
+        processor = |req| {
+            match language {
+                Some(language) => req.header(
+                    LANGUAGE_HEADER,
+                    HeaderValue::from_static(language.name()),
+                ),
+                None => req,
+            }
         },
-        response = [json] PayloadResponse<ItemsPayload<ItemShort>>,
+        response = [conditional_json] Conditional<PayloadResponse<ItemsPayload<ItemShort>>>,
     ),
     (
         GetItem {
@@ -112,34 +120,43 @@ routes! {
         GetItemOrders {
             url_name: String,
             platform: Option<Platform>,
+            language: Option<Language>,
         },
         method = GET "/items/{url_name}/orders",
-        info = |method, route| -> WmRouteInfo {
-            WmRouteInfo::new_cached(
-                CacheBucket {
-                    method,
-                    route,
-                    values: vec![url_name.clone(), format!("{:?}", platform)],
-                },
-                Some(Duration::from_secs(HOUR)),
-            )
+        // Cached by `ItemOrdersCache` instead, since orders change frequently
+        info = |_method, _route| -> WmRouteInfo {
+            WmRouteInfo::new_uncached()
         },
         processor = |req| {
             let req = req.query(&[("include", "item")]);
             let req = match platform {
-                Some(platform) => req.header("platform", HeaderValue::from_static(match platform {
-                    Platform::XBox => "xbox",
-                    Platform::PC => "pc",
-                    Platform::PS4 => "ps4",
-                    Platform::Switch => "switch",
-                })),
+                Some(platform) => req.header(
+                    PLATFORM_HEADER,
+                    HeaderValue::from_static(platform.name()),
+                ),
                 None => req,
             };
-
-            req
+            match language {
+                Some(language) => req.header(
+                    LANGUAGE_HEADER,
+                    HeaderValue::from_static(language.name()),
+                ),
+                None => req,
+            }
         },
         response = [json] PayloadResponse<ItemOrdersPayload, ItemPayload>,
     ),
+    // Profiles
+    (
+        GetProfileOrders {
+            ingame_name: PathSegment,
+        },
+        method = GET "/profile/{ingame_name}/orders",
+        info = |_method, _route| -> WmRouteInfo {
+            WmRouteInfo::new_uncached()
+        },
+        response = [json] PayloadResponse<ProfileOrdersPayload>,
+    ),
     // Liches
     (
         GetLichWeapons {},
@@ -0,0 +1,107 @@
+use futures::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    future::Future,
+    hash::Hash,
+    panic,
+    sync::{Arc, Mutex},
+};
+
+type InFlight<K, V> = Arc<Mutex<HashMap<K, Shared<BoxFuture<'static, V>>>>>;
+
+/// Coalesces concurrent identical requests, so they share a single response
+/// instead of each being sent separately.
+pub struct SingleFlight<K, V> {
+    in_flight: InFlight<K, V>,
+}
+
+impl<K, V> SingleFlight<K, V>
+where
+    K: Clone + Eq + Hash + Send + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        SingleFlight {
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Waits for the in-flight request with the same key, or starts a new
+    /// request with `request` if there isn't one. New requests are spawned
+    /// onto the current Tokio runtime.
+    pub async fn run<F>(&self, key: K, request: impl FnOnce() -> F) -> V
+    where
+        F: Future<Output = V> + Send + 'static,
+    {
+        let shared = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(shared) => shared.clone(),
+                None => {
+                    // The request runs on its own task so it completes and
+                    // removes itself even if every caller stops waiting
+                    let request = request();
+                    let entries = self.in_flight.clone();
+                    let entry_key = key.clone();
+                    let task = tokio::spawn(async move {
+                        let value = request.await;
+                        entries.lock().unwrap().remove(&entry_key);
+                        value
+                    });
+                    let shared = task
+                        .map(|result| {
+                            result.unwrap_or_else(|error| {
+                                panic::resume_unwind(error.into_panic())
+                            })
+                        })
+                        .boxed()
+                        .shared();
+                    in_flight.insert(key, shared.clone());
+                    shared
+                }
+            }
+        };
+
+        shared.await
+    }
+
+    /// The number of requests currently in flight.
+    pub fn len(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+
+    /// Whether there are no requests in flight.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, V> Clone for SingleFlight<K, V> {
+    fn clone(&self) -> Self {
+        SingleFlight {
+            in_flight: self.in_flight.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for SingleFlight<K, V>
+where
+    K: Clone + Eq + Hash + Send + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Debug for SingleFlight<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SingleFlight")
+            .field("in_flight", &self.in_flight.lock().unwrap().len())
+            .finish()
+    }
+}
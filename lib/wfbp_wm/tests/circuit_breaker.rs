@@ -0,0 +1,82 @@
+use std::{thread::sleep, time::Duration};
+use wfbp_wm::middleware::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+
+const COOLDOWN: Duration = Duration::from_millis(50);
+
+fn breaker() -> CircuitBreaker {
+    CircuitBreaker::new(CircuitBreakerConfig {
+        failure_threshold: 3,
+        cooldown: COOLDOWN,
+    })
+}
+
+fn open(breaker: &CircuitBreaker) {
+    for _ in 0..3 {
+        breaker.try_acquire().expect("circuit should be closed");
+        breaker.record_failure();
+    }
+}
+
+#[test]
+fn opens_after_consecutive_failures() {
+    let breaker = breaker();
+    breaker.record_failure();
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitState::Closed);
+
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert!(breaker.try_acquire().is_err());
+}
+
+#[test]
+fn success_resets_failure_count() {
+    let breaker = breaker();
+    breaker.record_failure();
+    breaker.record_failure();
+    breaker.record_success();
+    breaker.record_failure();
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn half_open_allows_single_trial() {
+    let breaker = breaker();
+    open(&breaker);
+
+    sleep(COOLDOWN);
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    breaker
+        .try_acquire()
+        .expect("trial request should be allowed");
+    assert!(breaker.try_acquire().is_err());
+}
+
+#[test]
+fn half_open_closes_on_success() {
+    let breaker = breaker();
+    open(&breaker);
+
+    sleep(COOLDOWN);
+    breaker
+        .try_acquire()
+        .expect("trial request should be allowed");
+    breaker.record_success();
+    assert_eq!(breaker.state(), CircuitState::Closed);
+    assert!(breaker.try_acquire().is_ok());
+}
+
+#[test]
+fn half_open_reopens_on_failure() {
+    let breaker = breaker();
+    open(&breaker);
+
+    sleep(COOLDOWN);
+    breaker
+        .try_acquire()
+        .expect("trial request should be allowed");
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert!(breaker.try_acquire().is_err());
+}
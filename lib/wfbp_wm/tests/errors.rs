@@ -0,0 +1,104 @@
+use reqwest::{Client, Method, StatusCode};
+use std::{sync::Arc, time::Duration};
+use wfbp_http::{test_util::MockTransport, RequestError};
+use wfbp_wm::{
+    MarketUnavailable, NoopMetrics, WmClientConfig, WmErrorKind,
+    WmRequestErrorExt, WmRestClient,
+};
+
+fn api_error(status: StatusCode) -> RequestError {
+    RequestError::ApiError {
+        status,
+        code: None,
+        message: "error".into(),
+    }
+}
+
+fn client(transport: Arc<MockTransport>) -> WmRestClient {
+    WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport,
+    )
+}
+
+#[test]
+fn statuses_map_to_kinds() {
+    let cases = [
+        (StatusCode::NOT_FOUND, Some(WmErrorKind::NotFound)),
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(WmErrorKind::RateLimited),
+        ),
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Some(WmErrorKind::Unavailable),
+        ),
+        (StatusCode::BAD_GATEWAY, Some(WmErrorKind::Unavailable)),
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Some(WmErrorKind::Unavailable),
+        ),
+        (StatusCode::BAD_REQUEST, None),
+        (StatusCode::FORBIDDEN, None),
+    ];
+    for (status, kind) in cases {
+        assert_eq!(WmErrorKind::from_status(status), kind, "{status}");
+        assert_eq!(api_error(status).kind(), kind, "{status}");
+    }
+}
+
+#[test]
+fn open_circuit_is_unavailable() {
+    let error: RequestError = MarketUnavailable {
+        retry_after: Duration::from_secs(30),
+    }
+    .into();
+    assert_eq!(error.kind(), Some(WmErrorKind::Unavailable));
+    assert!(error.is_market_unavailable());
+}
+
+#[test]
+fn helpers_match_kinds() {
+    assert!(api_error(StatusCode::NOT_FOUND).is_not_found());
+    assert!(!api_error(StatusCode::NOT_FOUND).is_rate_limited());
+    assert!(api_error(StatusCode::TOO_MANY_REQUESTS).is_rate_limited());
+    assert!(!api_error(StatusCode::BAD_GATEWAY).is_market_unavailable());
+}
+
+#[tokio::test]
+async fn missing_item_is_not_found() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(
+        Method::GET,
+        "/v1/items/not_an_item/orders",
+        StatusCode::NOT_FOUND,
+        r#"{"error": {"item": "app.item.not_exist"}}"#,
+    );
+    let client = client(transport);
+
+    let error = client
+        .get_item_orders("not_an_item".into(), None, false)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(WmErrorKind::NotFound));
+}
+
+#[tokio::test]
+async fn invalid_response_is_parse_error() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(
+        Method::GET,
+        "/v1/items/soma_prime_set/orders",
+        StatusCode::OK,
+        "<html>maintenance</html>",
+    );
+    let client = client(transport);
+
+    let error = client
+        .get_item_orders("soma_prime_set".into(), None, false)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(WmErrorKind::Parse));
+}
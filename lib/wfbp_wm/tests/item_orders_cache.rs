@@ -0,0 +1,62 @@
+use chrono::{TimeZone, Utc};
+use std::{thread::sleep, time::Duration};
+use wfbp_wm::{
+    models::{ItemOrdersPayload, PayloadResponse, Platform},
+    ItemOrders, ItemOrdersCache, ItemOrdersCacheConfig,
+};
+
+fn orders() -> ItemOrders {
+    PayloadResponse {
+        payload: ItemOrdersPayload { orders: vec![] },
+        include: None,
+    }
+}
+
+#[test]
+fn returns_cached_orders() {
+    let cache = ItemOrdersCache::new(ItemOrdersCacheConfig::default());
+    cache.insert("ash_prime_set".into(), Some(Platform::PC), orders());
+
+    assert!(cache.get("ash_prime_set", Some(Platform::PC)).is_some());
+    assert!(cache.get("ash_prime_set", Some(Platform::PS4)).is_none());
+    assert!(cache.get("ash_prime_set", None).is_none());
+}
+
+#[test]
+fn cached_orders_keep_fetch_time() {
+    let cache = ItemOrdersCache::new(ItemOrdersCacheConfig::default());
+    let fetched_at = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+    cache.insert_fetched("ash_prime_set".into(), None, orders(), fetched_at);
+
+    let cached = cache.get("ash_prime_set", None).expect("missing orders");
+    assert_eq!(cached.fetched_at, fetched_at);
+    assert!(cached.cached);
+}
+
+#[test]
+fn expires_orders_after_ttl() {
+    let cache = ItemOrdersCache::new(ItemOrdersCacheConfig {
+        capacity: 8,
+        ttl: Duration::from_millis(50),
+    });
+    cache.insert("ash_prime_set".into(), None, orders());
+
+    sleep(Duration::from_millis(50));
+    assert!(cache.get("ash_prime_set", None).is_none());
+}
+
+#[test]
+fn evicts_least_recently_used_orders() {
+    let cache = ItemOrdersCache::new(ItemOrdersCacheConfig {
+        capacity: 2,
+        ..Default::default()
+    });
+    cache.insert("a".into(), None, orders());
+    cache.insert("b".into(), None, orders());
+    assert!(cache.get("a", None).is_some());
+    cache.insert("c".into(), None, orders());
+
+    assert!(cache.get("a", None).is_some());
+    assert!(cache.get("b", None).is_none());
+    assert!(cache.get("c", None).is_some());
+}
@@ -0,0 +1,60 @@
+use serde_json::json;
+use wfbp_wm::models::{ItemFull, Language, RelicRefinement};
+
+fn item() -> ItemFull {
+    serde_json::from_value(json!({
+        "id": "item",
+        "url_name": "ash_prime_set",
+        "icon": "items/images/en/ash_prime_set.png",
+        "thumb": "items/images/en/thumbs/ash_prime_set.png",
+        "tags": ["prime", "set", "warframe"],
+        "en": {
+            "item_name": "Ash Prime Set",
+            "description": "A set of parts.",
+        },
+        "de": {
+            "item_name": "Ash Prime Set (DE)",
+            "description": "Ein Satz Teile.",
+        },
+        "zh-hans": {
+            "item_name": "Ash Prime 一套",
+            "description": "",
+        },
+    }))
+    .expect("error parsing item")
+}
+
+#[test]
+fn item_uses_requested_language() {
+    let item = item();
+    assert_eq!(item.lang(Language::De).item_name, "Ash Prime Set (DE)");
+    assert_eq!(item.lang(Language::ZhHans).item_name, "Ash Prime 一套");
+}
+
+#[test]
+fn item_falls_back_to_english() {
+    let item = item();
+    assert_eq!(item.lang(Language::Fr).item_name, "Ash Prime Set");
+    assert_eq!(item.lang(Language::En).description, "A set of parts.");
+}
+
+#[test]
+fn language_names_match_serialization() {
+    for language in [Language::En, Language::ZhHans, Language::ZhHant] {
+        let serialized = serde_json::to_value(language).unwrap();
+        assert_eq!(serialized, json!(language.name()));
+    }
+}
+
+#[test]
+fn relic_refinement_names_match_warframe_market() {
+    let expected = ["intact", "exceptional", "flawless", "radiant"];
+    for (refinement, name) in RelicRefinement::ALL.into_iter().zip(expected) {
+        assert_eq!(refinement.name(), name);
+        assert_eq!(refinement.to_string(), name);
+        assert_eq!(serde_json::to_value(refinement).unwrap(), json!(name));
+        assert_eq!(name.parse::<RelicRefinement>().unwrap(), refinement);
+    }
+
+    assert!("pristine".parse::<RelicRefinement>().is_err());
+}
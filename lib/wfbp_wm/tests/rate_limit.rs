@@ -0,0 +1,97 @@
+use futures::future::join_all;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::Instant;
+use tower::{service_fn, Layer, ServiceExt};
+use wfbp_http::RequestError;
+use wfbp_wm::{
+    middleware::{RateLimitConfig, RateLimitLayer, TokenBucket},
+    Metrics,
+};
+
+const INTERVAL: Duration = Duration::from_millis(50);
+
+fn config(burst: u32) -> RateLimitConfig {
+    RateLimitConfig {
+        requests_per_second: 1.0 / INTERVAL.as_secs_f64(),
+        burst,
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecordingMetrics {
+    waits: Mutex<Vec<Duration>>,
+}
+
+impl Metrics for RecordingMetrics {
+    fn on_rate_limit_wait(&self, duration: Duration) {
+        self.waits.lock().unwrap().push(duration);
+    }
+}
+
+#[test]
+fn bucket_allows_burst_then_spaces_requests() {
+    let bucket = TokenBucket::new(config(2));
+    assert!(bucket.reserve().is_zero());
+    assert!(bucket.reserve().is_zero());
+
+    let first_wait = bucket.reserve();
+    let second_wait = bucket.reserve();
+    assert!(first_wait > INTERVAL / 2 && first_wait <= INTERVAL);
+    assert!(second_wait > first_wait + INTERVAL / 2);
+}
+
+#[test]
+fn zero_rate_disables_limit() {
+    let bucket = TokenBucket::new(RateLimitConfig {
+        requests_per_second: 0.0,
+        burst: 1,
+    });
+    for _ in 0..10 {
+        assert!(bucket.reserve().is_zero());
+    }
+}
+
+#[test]
+fn invalid_rate_disables_limit() {
+    for requests_per_second in [-1.0, f64::NAN, f64::INFINITY] {
+        let bucket = TokenBucket::new(RateLimitConfig {
+            requests_per_second,
+            burst: 1,
+        });
+        for _ in 0..10 {
+            assert!(bucket.reserve().is_zero());
+        }
+    }
+}
+
+#[tokio::test]
+async fn rapid_requests_are_spaced_to_rate() {
+    tokio::time::pause();
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let metrics = Arc::new(RecordingMetrics::default());
+    let layer =
+        RateLimitLayer::new(TokenBucket::new(config(1)), metrics.clone());
+    let service = layer.layer(service_fn({
+        let sent = sent.clone();
+        move |_: ()| {
+            sent.lock().unwrap().push(Instant::now());
+            async { Ok::<_, RequestError>(()) }
+        }
+    }));
+
+    let requests = (0..5).map(|_| service.clone().oneshot(()));
+    for result in join_all(requests).await {
+        result.expect("error sending request");
+    }
+
+    let mut sent = sent.lock().unwrap().clone();
+    sent.sort();
+    for pair in sent.windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!(gap >= INTERVAL, "gap was {gap:?}");
+    }
+    assert_eq!(metrics.waits.lock().unwrap().len(), 4);
+}
@@ -0,0 +1,149 @@
+use reqwest::{Client, Method, StatusCode};
+use serde_json::json;
+use std::sync::Arc;
+use wfbp_http::{middleware::RetryConfig, test_util::MockTransport};
+use wfbp_wm::{
+    models::{Language, Platform},
+    routes::{GetItems, LANGUAGE_HEADER, PLATFORM_HEADER},
+    NoopMetrics, WmClientConfig, WmRestClient,
+};
+
+fn client(config: WmClientConfig) -> (WmRestClient, Arc<MockTransport>) {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond_json(
+        Method::GET,
+        "/v1/items",
+        json!({ "payload": { "items": [] } }),
+    );
+    transport.respond_json(
+        Method::GET,
+        "/v1/items/ash_prime_set/orders",
+        json!({ "payload": { "orders": [] } }),
+    );
+    let client = WmRestClient::new_with_transport(
+        Client::new(),
+        config,
+        Arc::new(NoopMetrics),
+        transport.clone(),
+    );
+    (client, transport)
+}
+
+async fn sent_platform(
+    config: WmClientConfig,
+    platform: Option<Platform>,
+) -> Option<String> {
+    let (client, transport) = client(config);
+    client
+        .get_item_orders("ash_prime_set".into(), platform, true)
+        .await
+        .expect("error getting orders");
+
+    let headers = transport.request_headers();
+    assert_eq!(headers.len(), 1);
+    headers[0]
+        .get(PLATFORM_HEADER)
+        .map(|value| value.to_str().unwrap().to_owned())
+}
+
+#[tokio::test]
+async fn item_orders_send_platform_header() {
+    let platforms = [
+        (Platform::PC, "pc"),
+        (Platform::XBox, "xbox"),
+        (Platform::PS4, "ps4"),
+        (Platform::Switch, "switch"),
+    ];
+    for (platform, expected) in platforms {
+        let sent =
+            sent_platform(WmClientConfig::default(), Some(platform)).await;
+        assert_eq!(sent.as_deref(), Some(expected));
+    }
+}
+
+#[tokio::test]
+async fn item_orders_use_default_platform() {
+    let config = WmClientConfig {
+        default_platform: Platform::Switch,
+        ..Default::default()
+    };
+    assert_eq!(sent_platform(config, None).await.as_deref(), Some("switch"));
+    assert_eq!(
+        sent_platform(config, Some(Platform::PS4)).await.as_deref(),
+        Some("ps4")
+    );
+}
+
+#[tokio::test]
+async fn item_orders_send_default_language() {
+    let (client, transport) = client(WmClientConfig {
+        default_language: Language::De,
+        ..Default::default()
+    });
+    client
+        .get_item_orders("ash_prime_set".into(), None, true)
+        .await
+        .expect("error getting orders");
+
+    let headers = transport.request_headers();
+    assert_eq!(headers[0][LANGUAGE_HEADER], "de");
+}
+
+#[tokio::test]
+async fn items_send_requested_language() {
+    let (client, transport) = client(WmClientConfig::default());
+    GetItems::execute(&client, None, Some(Language::ZhHans))
+        .await
+        .expect("error getting items");
+    GetItems::execute(&client, None, None)
+        .await
+        .expect("error getting items");
+
+    let headers = transport.request_headers();
+    assert_eq!(headers[0][LANGUAGE_HEADER], "zh-hans");
+    assert!(headers[1].get(LANGUAGE_HEADER).is_none());
+}
+
+#[tokio::test]
+async fn transient_failures_are_retried() {
+    let (client, transport) = client(WmClientConfig::default());
+    for _ in 0..2 {
+        transport.respond_once(
+            Method::GET,
+            "/v1/items/ash_prime_set/orders",
+            StatusCode::SERVICE_UNAVAILABLE,
+            "",
+        );
+    }
+
+    client
+        .get_item_orders("ash_prime_set".into(), None, true)
+        .await
+        .expect("error getting orders");
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn retries_are_bounded() {
+    let (client, transport) = client(WmClientConfig {
+        retry: RetryConfig {
+            max_retries: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    for _ in 0..3 {
+        transport.respond_once(
+            Method::GET,
+            "/v1/items/ash_prime_set/orders",
+            StatusCode::SERVICE_UNAVAILABLE,
+            "",
+        );
+    }
+
+    let result = client
+        .get_item_orders("ash_prime_set".into(), None, true)
+        .await;
+    assert!(result.is_err());
+    assert_eq!(transport.requests().len(), 2);
+}
@@ -0,0 +1,164 @@
+use futures::{
+    future::{join_all, BoxFuture},
+    FutureExt,
+};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde_json::json;
+use std::{
+    net::{SocketAddr, TcpListener},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use wfbp_http::{test_util::MockTransport, Transport};
+use wfbp_wm::{
+    NoopMetrics, SingleFlight, WmClientConfig, WmErrorKind, WmRequestErrorExt,
+    WmRestClient,
+};
+
+const DELAY: Duration = Duration::from_millis(50);
+
+fn client(transport: Arc<MockTransport>) -> WmRestClient {
+    transport.delay(DELAY);
+    WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport,
+    )
+}
+
+#[tokio::test]
+async fn concurrent_lookups_share_one_request() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond_json(
+        Method::GET,
+        "/v1/items/soma_prime_set/orders",
+        json!({ "payload": { "orders": [] } }),
+    );
+    let client = client(transport.clone());
+
+    let lookups = (0..5)
+        .map(|_| client.get_item_orders("soma_prime_set".into(), None, false));
+    for result in join_all(lookups).await {
+        result.expect("error getting orders");
+    }
+    assert_eq!(transport.requests().len(), 1);
+
+    // Finished requests aren't reused
+    client
+        .get_item_orders("soma_prime_set".into(), None, false)
+        .await
+        .expect("error getting orders");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn different_lookups_are_not_shared() {
+    let transport = Arc::new(MockTransport::new());
+    for url_name in ["soma_prime_set", "ash_prime_set"] {
+        transport.respond_json(
+            Method::GET,
+            format!("/v1/items/{url_name}/orders"),
+            json!({ "payload": { "orders": [] } }),
+        );
+    }
+    let client = client(transport.clone());
+
+    let (soma, ash) = futures::join!(
+        client.get_item_orders("soma_prime_set".into(), None, false),
+        client.get_item_orders("ash_prime_set".into(), None, false),
+    );
+    soma.expect("error getting orders");
+    ash.expect("error getting orders");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn shared_errors_keep_their_kind() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(
+        Method::GET,
+        "/v1/items/missing/orders",
+        StatusCode::NOT_FOUND,
+        "{}",
+    );
+    let client = client(transport.clone());
+
+    let lookups =
+        (0..3).map(|_| client.get_item_orders("missing".into(), None, false));
+    for result in join_all(lookups).await {
+        let error = result.expect_err("lookup should fail");
+        assert!(error.is_not_found(), "unexpected error: {error}");
+    }
+    assert_eq!(transport.requests().len(), 1);
+}
+
+/// Sends every request to a server which never responds, so they time out.
+#[derive(Debug)]
+struct TimeoutTransport {
+    addr: SocketAddr,
+    sent: AtomicUsize,
+}
+
+impl Transport for TimeoutTransport {
+    fn send(
+        &self,
+        _request: RequestBuilder,
+    ) -> BoxFuture<'static, Result<Response, reqwest::Error>> {
+        self.sent.fetch_add(1, Ordering::SeqCst);
+        Client::new()
+            .get(format!("http://{}", self.addr))
+            .timeout(DELAY)
+            .send()
+            .boxed()
+    }
+}
+
+#[tokio::test]
+async fn shared_timeouts_keep_their_kind() {
+    // Connections are queued by the OS but never accepted
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let transport = Arc::new(TimeoutTransport {
+        addr: listener.local_addr().unwrap(),
+        sent: AtomicUsize::new(0),
+    });
+    let client = WmRestClient::new_with_transport(
+        Client::new(),
+        WmClientConfig::default(),
+        Arc::new(NoopMetrics),
+        transport.clone(),
+    );
+
+    let (first, second) = futures::join!(
+        client.get_item_orders("soma_prime_set".into(), None, false),
+        client.get_item_orders("soma_prime_set".into(), None, false),
+    );
+    for result in [first, second] {
+        let error = result.expect_err("lookup should time out");
+        assert_eq!(error.kind(), Some(WmErrorKind::Unavailable), "{error}");
+    }
+    assert_eq!(transport.sent.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn abandoned_requests_still_complete() {
+    tokio::time::pause();
+    let flights = SingleFlight::new();
+
+    let abandoned = tokio::time::timeout(
+        DELAY / 2,
+        flights.run("key", || async {
+            tokio::time::sleep(DELAY).await;
+            1
+        }),
+    )
+    .await;
+    assert!(abandoned.is_err());
+    assert_eq!(flights.len(), 1);
+
+    tokio::time::sleep(DELAY).await;
+    assert!(flights.is_empty());
+}
@@ -1,3 +1,5 @@
+mod debug;
 mod interactions;
 
+pub use debug::*;
 pub use interactions::*;
@@ -0,0 +1,37 @@
+use crate::models::{RecentInteractions, RecordedInteraction};
+use actix_web::{
+    dev::HttpServiceFactory,
+    http::StatusCode,
+    post,
+    web::{scope, Data, Json},
+};
+use std::collections::HashMap;
+use tracing::instrument;
+use wfbp_azure::functions::{FunctionsOutput, HttpOutput};
+
+pub fn debug_service() -> impl HttpServiceFactory + 'static {
+    scope("/debug").service(get_recent_interactions)
+}
+
+/// Lists the most recently received interactions. This function requires the
+/// admin key (see `func/debug/function.json`).
+#[post("")]
+#[instrument(skip(recent_interactions))]
+async fn get_recent_interactions(
+    recent_interactions: Data<RecentInteractions>,
+) -> Json<FunctionsOutput<(), HttpOutput<Vec<RecordedInteraction>>>> {
+    Json(FunctionsOutput {
+        outputs: (),
+        logs: vec![],
+        return_value: HttpOutput {
+            status_code: StatusCode::OK.as_u16(),
+            headers: {
+                let mut headers = HashMap::new();
+                headers
+                    .insert("content-type".into(), "application/json".into());
+                headers
+            },
+            body: recent_interactions.snapshot(),
+        },
+    })
+}
@@ -1,12 +1,18 @@
-use crate::models::{
-    CheckSignatureError, Config, InteractionError, InteractionInputData,
-    InteractionOutputData,
+use crate::{
+    middleware::MAX_BODY_SIZE,
+    models::{
+        CheckSignatureError, Config, InteractionError, InteractionInputData,
+        InteractionOutputData, RecentInteractions, RoutePath,
+        EPHEMERAL_COMMANDS,
+    },
 };
 use actix_web::{
     dev::HttpServiceFactory,
+    error::JsonPayloadError,
     http::StatusCode,
     post,
-    web::{scope, Data, Json},
+    web::{scope, Data, Json, JsonConfig},
+    Error, HttpRequest,
 };
 use ed25519_dalek::{Signature, Verifier};
 use std::collections::HashMap;
@@ -15,28 +21,80 @@ use wfbp_azure::functions::{
     FunctionsInput, FunctionsOutput, HttpOutput, RawHttpInput,
 };
 use wfbp_discord::models::{
-    Interaction, InteractionApplicationCommandCallbackData,
-    InteractionResponse, InteractionType,
+    ApplicationCommandInteractionData, Interaction,
+    InteractionApplicationCommandCallbackData, InteractionResponse,
+    InteractionResponseDataFlags, InteractionType,
 };
 
-pub const HEADER_SIGNATURE: &'static str = "x-signature-ed25519";
-pub const HEADER_TIMESTAMP: &'static str = "x-signature-timestamp";
+pub const HEADER_SIGNATURE: &str = "x-signature-ed25519";
+pub const HEADER_TIMESTAMP: &str = "x-signature-timestamp";
+pub const HEADER_CONTENT_TYPE: &str = "content-type";
 
-pub fn interactions_service() -> impl HttpServiceFactory + 'static {
-    scope("/interactions").service(handle_interaction)
+pub fn interactions_service(
+    path: &RoutePath,
+) -> impl HttpServiceFactory + 'static {
+    let json_config = JsonConfig::default()
+        .limit(MAX_BODY_SIZE)
+        .error_handler(json_error);
+    scope(path.as_str())
+        .app_data(json_config)
+        .service(handle_interaction)
+}
+
+/// Reports oversized bodies the same way as other interaction errors.
+fn json_error(error: JsonPayloadError, _req: &HttpRequest) -> Error {
+    match error {
+        JsonPayloadError::Overflow { limit }
+        | JsonPayloadError::OverflowKnownLength { limit, .. } => {
+            InteractionError::PayloadTooLarge { limit }.into()
+        }
+        error => error.into(),
+    }
+}
+
+/// Whether the invoked command should only be visible to the user.
+fn is_ephemeral_command(data: &ApplicationCommandInteractionData) -> bool {
+    let path = data.command_path();
+    EPHEMERAL_COMMANDS.contains(&path.as_slice())
+}
+
+/// Whether a `content-type` header value is JSON, which is the only content
+/// type Discord sends interactions with.
+fn is_json_content_type(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .map(str::trim)
+        .filter(|mime| mime.eq_ignore_ascii_case("application/json"))
+        .is_some()
 }
 
 #[post("")]
-#[instrument(skip(input, config))]
+#[instrument(skip(input, config, recent_interactions))]
 async fn handle_interaction(
     input: Json<FunctionsInput<InteractionInputData<RawHttpInput>>>,
     config: Data<Config>,
+    recent_interactions: Data<RecentInteractions>,
 ) -> Result<
     Json<
         FunctionsOutput<InteractionOutputData, HttpOutput<InteractionResponse>>,
     >,
     InteractionError,
 > {
+    // Validate content type
+    let is_json = input
+        .data
+        .request
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(HEADER_CONTENT_TYPE))
+        .and_then(|(_, values)| values.first())
+        .filter(|content_type| is_json_content_type(content_type))
+        .is_some();
+    if !is_json {
+        return Err(InteractionError::UnsupportedMediaType);
+    }
+
     // Validate signature
     if !config.ignore_signature {
         let timestamp = input
@@ -78,20 +136,28 @@ async fn handle_interaction(
     if interaction.application_id != config.app_id {
         return Err(InteractionError::UnauthorizedApplication);
     }
+    recent_interactions.record(&input.data.request.body);
 
     // Create HTTP response
-    // TODO: check if response should be ephemeral
     let response = match &interaction.kind {
         InteractionType::Ping => InteractionResponse::Pong,
-        InteractionType::ApplicationCommand { .. } => {
+        InteractionType::ApplicationCommand { data, .. } => {
+            let flags = if is_ephemeral_command(data) {
+                Some(InteractionResponseDataFlags::EPHEMERAL)
+            } else {
+                None
+            };
             InteractionResponse::DeferredChannelMessageWithSource {
                 data: InteractionApplicationCommandCallbackData {
+                    flags,
                     ..Default::default()
                 },
             }
         }
+        // Components edit the message they're attached to, so the processor
+        // updates it through the original response
         InteractionType::MessageComponent { .. } => {
-            InteractionResponse::DeferredChannelMessageWithSource {
+            InteractionResponse::DeferredUpdateMessage {
                 data: InteractionApplicationCommandCallbackData {
                     ..Default::default()
                 },
@@ -119,3 +185,118 @@ async fn handle_interaction(
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ConfigPublicKey;
+    use actix_web::{
+        test::{call_and_read_body_json, init_service, TestRequest},
+        App,
+    };
+    use ed25519_dalek::PublicKey;
+    use serde_json::json;
+    use wfbp_discord::models::Snowflake;
+
+    fn config() -> Config {
+        Config {
+            app_id: Snowflake::new(1),
+            client_id: Snowflake::new(1),
+            client_secret: String::new(),
+            ignore_signature: true,
+            discord_public_key: ConfigPublicKey::from(
+                PublicKey::from_bytes(&[0; 32]).unwrap(),
+            ),
+            port: 0,
+            debug_interactions: 1,
+            interactions_path: RoutePath::default(),
+        }
+    }
+
+    fn chat_input(
+        name: &str,
+        options: serde_json::Value,
+    ) -> ApplicationCommandInteractionData {
+        serde_json::from_value(json!({
+            "type": 1,
+            "id": "1",
+            "name": name,
+            "options": options,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn ephemeral_commands_are_matched_by_path() {
+        assert!(is_ephemeral_command(&chat_input("help", json!([]))));
+        assert!(is_ephemeral_command(&chat_input(
+            "pc",
+            json!([{ "type": 1, "name": "refresh-items" }]),
+        )));
+        assert!(!is_ephemeral_command(&chat_input(
+            "pc",
+            json!([{ "type": 1, "name": "items" }]),
+        )));
+        assert!(!is_ephemeral_command(&chat_input("pc", json!([]))));
+    }
+
+    #[test]
+    fn json_content_types_are_accepted() {
+        assert!(is_json_content_type("application/json"));
+        assert!(is_json_content_type("Application/JSON"));
+        assert!(is_json_content_type("application/json; charset=utf-8"));
+        assert!(!is_json_content_type("text/plain"));
+        assert!(!is_json_content_type("application/jsonx"));
+        assert!(!is_json_content_type(""));
+    }
+
+    fn functions_input(content_type: &str, body: &str) -> serde_json::Value {
+        json!({
+            "Data": {
+                "request": {
+                    "Url": "https://localhost/interactions",
+                    "Method": "POST",
+                    "Query": {},
+                    "Headers": { "Content-Type": [content_type] },
+                    "Params": {},
+                    "Body": body,
+                },
+            },
+        })
+    }
+
+    /// Sends an input to the interactions service, returning the status that
+    /// the Functions host would respond to Discord with.
+    async fn returned_status(input: serde_json::Value) -> serde_json::Value {
+        let path = RoutePath::default();
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(config()))
+                .app_data(Data::new(RecentInteractions::new(1)))
+                .service(interactions_service(&path)),
+        )
+        .await;
+
+        let request = TestRequest::post()
+            .uri(path.as_str())
+            .set_json(input)
+            .to_request();
+        let response: serde_json::Value =
+            call_and_read_body_json(&app, request).await;
+        response["ReturnValue"]["Status"].clone()
+    }
+
+    #[actix_web::test]
+    async fn non_json_interactions_are_unsupported() {
+        let status = returned_status(functions_input("text/plain", "{}")).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE.as_u16());
+    }
+
+    #[actix_web::test]
+    async fn oversized_interactions_are_rejected() {
+        let body = "x".repeat(MAX_BODY_SIZE);
+        let input = functions_input("application/json", &body);
+        let status = returned_status(input).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE.as_u16());
+    }
+}
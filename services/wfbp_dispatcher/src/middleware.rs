@@ -3,3 +3,27 @@ mod log_body;
 
 pub use ed25519::*;
 pub use log_body::*;
+
+use crate::models::CheckSignatureError;
+use actix_web::dev::Payload;
+use tracing::error;
+use wfbp_http::{read_body_limited, ReadBodyError};
+
+/// The largest request body that middleware will buffer.
+pub const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+async fn read_payload(
+    payload: Payload,
+) -> Result<Vec<u8>, CheckSignatureError> {
+    read_body_limited(payload, MAX_BODY_SIZE)
+        .await
+        .map_err(|error| match error {
+            ReadBodyError::TooLarge { limit } => {
+                CheckSignatureError::PayloadTooLarge { limit }
+            }
+            error => {
+                error!(%error, "error retrieving request payload");
+                CheckSignatureError::InternalServerError
+            }
+        })
+}
@@ -1,3 +1,4 @@
+use super::read_payload;
 use crate::models::CheckSignatureError;
 use actix_web::{
     dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform},
@@ -7,14 +8,12 @@ use actix_web::{
     Error as ActixError, HttpMessage,
 };
 use ed25519_dalek::{PublicKey, Signature, Verifier};
-use futures::TryStreamExt;
 use std::{
     future::{Future, Ready},
     pin::Pin,
     rc::Rc,
     task::{Context, Poll},
 };
-use tracing::error;
 
 #[derive(Clone, Debug)]
 pub struct CheckEd25519Signature {
@@ -129,17 +128,7 @@ where
             // Get required data
             let mut message = timestamp?;
             let signature = signature?;
-            let payload: Vec<_> = req
-                .take_payload()
-                .try_fold(Vec::new(), |mut acc, cur| async move {
-                    acc.extend(cur);
-                    Ok(acc)
-                })
-                .await
-                .map_err(|_| {
-                    error!("error retrieving request payload");
-                    CheckSignatureError::InternalServerError
-                })?;
+            let payload = read_payload(req.take_payload()).await?;
             let body = String::from_utf8(payload.clone()).map_err(|_| {
                 CheckSignatureError::BadRequest {
                     message: "invalid request payload".into(),
@@ -1,3 +1,4 @@
+use super::read_payload;
 use crate::models::CheckSignatureError;
 use actix_web::{
     dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform},
@@ -6,14 +7,13 @@ use actix_web::{
     web::Bytes,
     Error as ActixError, HttpMessage,
 };
-use futures::TryStreamExt;
 use std::{
     future::{Future, Ready},
     pin::Pin,
     rc::Rc,
     task::{Context, Poll},
 };
-use tracing::{error, info};
+use tracing::info;
 
 #[derive(Clone, Debug)]
 pub struct LogBody;
@@ -75,17 +75,7 @@ where
         // Execute
         Box::pin(async move {
             // Get required data
-            let payload: Vec<_> = req
-                .take_payload()
-                .try_fold(Vec::new(), |mut acc, cur| async move {
-                    acc.extend(cur);
-                    Ok(acc)
-                })
-                .await
-                .map_err(|_| {
-                    error!("error retrieving request payload");
-                    CheckSignatureError::InternalServerError
-                })?;
+            let payload = read_payload(req.take_payload()).await?;
             let body = String::from_utf8(payload.clone()).map_err(|_| {
                 CheckSignatureError::BadRequest {
                     message: "invalid request payload".into(),
@@ -2,8 +2,10 @@ mod commands;
 mod config;
 mod errors;
 mod interactions;
+mod recent_interactions;
 
 pub use commands::*;
 pub use config::*;
 pub use errors::*;
 pub use interactions::*;
+pub use recent_interactions::*;
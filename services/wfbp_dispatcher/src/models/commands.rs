@@ -5,3 +5,11 @@ use serde::{Deserialize, Serialize};
 pub enum AdminCommand {
     RegisterCommands,
 }
+
+/// Commands whose responses are only shown to the user who invoked them, as
+/// returned by [`command_path`]. Discord gives followups to a deferred response
+/// the visibility of the deferral, so these must be deferred ephemerally.
+///
+/// [`command_path`]: wfbp_discord::models::ApplicationCommandInteractionData::command_path
+pub const EPHEMERAL_COMMANDS: &[&[&str]] =
+    &[&["help"], &["pc", "refresh-items"]];
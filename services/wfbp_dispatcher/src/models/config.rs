@@ -1,7 +1,7 @@
-use derive_more::{Deref, DerefMut, From, Into};
+use derive_more::{Deref, DerefMut, Display, From, Into};
 use ed25519_dalek::PublicKey;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use std::borrow::Cow;
+use std::{borrow::Cow, convert::TryFrom, path::Path};
 use wfbp_discord::models::Snowflake;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -14,12 +14,97 @@ pub struct Config {
     pub discord_public_key: ConfigPublicKey,
     #[serde(rename = "functions_customhandler_port", default = "default_port")]
     pub port: u16,
+    #[serde(default = "default_debug_interactions")]
+    pub debug_interactions: usize,
+    /// The path interactions are received at. The Functions host only
+    /// forwards requests to `/{function name}`, so this must be named after
+    /// the interactions function's folder. Startup fails if it isn't.
+    #[serde(default)]
+    pub interactions_path: RoutePath,
 }
 
 fn default_port() -> u16 {
     3000
 }
 
+fn default_debug_interactions() -> usize {
+    50
+}
+
+/// A path that a service is mounted at. Paths must start with `/`.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[display(fmt = "{}", _0)]
+pub struct RoutePath(String);
+
+impl RoutePath {
+    /// The default path that interactions are received at.
+    pub const DEFAULT_INTERACTIONS: &'static str = "/interactions";
+
+    pub fn new(path: impl Into<String>) -> Result<Self, InvalidRoutePathError> {
+        let path = path.into();
+        if !path.starts_with('/') {
+            return Err(InvalidRoutePathError(path));
+        }
+
+        Ok(RoutePath(path))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Checks that a function in the function app at `functions_root` is
+    /// named after this path. The Functions host forwards each function's
+    /// requests to `/{function name}`, so no other path receives requests.
+    pub fn check_function(
+        &self,
+        functions_root: &Path,
+    ) -> Result<(), MissingFunctionError> {
+        let name = &self.0[1..];
+        let is_function = !name.is_empty()
+            && !name.contains('/')
+            && functions_root.join(name).join("function.json").is_file();
+        if !is_function {
+            return Err(MissingFunctionError(self.0.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for RoutePath {
+    fn default() -> Self {
+        RoutePath(Self::DEFAULT_INTERACTIONS.to_owned())
+    }
+}
+
+impl TryFrom<String> for RoutePath {
+    type Error = InvalidRoutePathError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        RoutePath::new(value)
+    }
+}
+
+impl From<RoutePath> for String {
+    fn from(path: RoutePath) -> Self {
+        path.0
+    }
+}
+
+#[derive(Clone, Debug, Display)]
+#[display(fmt = "route path must start with '/': '{}'", _0)]
+pub struct InvalidRoutePathError(String);
+
+impl std::error::Error for InvalidRoutePathError {}
+
+#[derive(Clone, Debug, Display)]
+#[display(fmt = "no function is named after route path '{}'", _0)]
+pub struct MissingFunctionError(String);
+
+impl std::error::Error for MissingFunctionError {}
+
 #[derive(Clone, Debug, From, Into, Deref, DerefMut)]
 pub struct ConfigPublicKey(PublicKey);
 
@@ -47,3 +132,32 @@ impl<'de> Deserialize<'de> for ConfigPublicKey {
         Ok(public_key.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn functions_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("func")
+    }
+
+    #[test]
+    fn function_paths_are_accepted() {
+        let root = functions_root();
+        assert!(RoutePath::default().check_function(&root).is_ok());
+        assert!(RoutePath::new("/debug")
+            .unwrap()
+            .check_function(&root)
+            .is_ok());
+    }
+
+    #[test]
+    fn other_paths_are_rejected() {
+        let root = functions_root();
+        for path in ["/", "/bin", "/api/interactions", "/interactions/"] {
+            let path = RoutePath::new(path).unwrap();
+            assert!(path.check_function(&root).is_err(), "{path}");
+        }
+    }
+}
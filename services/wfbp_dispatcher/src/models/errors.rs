@@ -23,6 +23,8 @@ pub enum CheckSignatureError {
     VerificationFailed,
     #[display(fmt = "request expired")]
     RequestExpired,
+    #[display(fmt = "request body is larger than {} bytes", limit)]
+    PayloadTooLarge { limit: usize },
 }
 
 impl ResponseError for CheckSignatureError {
@@ -39,6 +41,9 @@ impl ResponseError for CheckSignatureError {
             | CheckSignatureError::InvalidSignature(..)
             | CheckSignatureError::VerificationFailed
             | CheckSignatureError::RequestExpired => StatusCode::UNAUTHORIZED,
+            CheckSignatureError::PayloadTooLarge { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
         }
     }
 }
@@ -57,6 +62,11 @@ pub enum InteractionError {
     UnauthorizedApplication,
     #[display(fmt = "{}", _0)]
     InvalidBody(serde_json::Error),
+    #[display(fmt = "content type must be application/json")]
+    UnsupportedMediaType,
+    #[display(fmt = "request body is larger than {} bytes", limit)]
+    #[from(ignore)]
+    PayloadTooLarge { limit: usize },
 }
 
 impl ResponseError for InteractionError {
@@ -70,6 +80,12 @@ impl ResponseError for InteractionError {
             InteractionError::InvalidSignature(inner) => inner.status_code(),
             InteractionError::UnauthorizedApplication => StatusCode::FORBIDDEN,
             InteractionError::InvalidBody(_) => StatusCode::BAD_REQUEST,
+            InteractionError::UnsupportedMediaType => {
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            }
+            InteractionError::PayloadTooLarge { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
         }
     }
 
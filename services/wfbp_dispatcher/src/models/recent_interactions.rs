@@ -0,0 +1,84 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+use wfbp_discord::models::Timestamp;
+
+/// Keys whose values are replaced before an interaction is recorded.
+const REDACTED_KEYS: &[&str] = &["token"];
+const REDACTED_VALUE: &str = "[redacted]";
+
+/// A verified interaction that was received by the dispatcher.
+#[derive(Clone, Debug, Serialize)]
+pub struct RecordedInteraction {
+    pub received_at: Timestamp,
+    pub interaction: Value,
+}
+
+/// A bounded buffer of the most recently received interactions, used for
+/// debugging. Tokens are redacted before an interaction is stored.
+#[derive(Clone, Debug)]
+pub struct RecentInteractions {
+    capacity: usize,
+    entries: Arc<Mutex<VecDeque<RecordedInteraction>>>,
+}
+
+impl RecentInteractions {
+    /// Creates a new buffer holding at most `capacity` interactions. A
+    /// capacity of 0 disables recording.
+    pub fn new(capacity: usize) -> Self {
+        RecentInteractions {
+            capacity,
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    /// Records a raw interaction body, evicting the oldest entry if the buffer
+    /// is full. Bodies which are not valid JSON are ignored.
+    pub fn record(&self, body: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        // Parse and redact outside of the lock
+        let mut interaction: Value = match serde_json::from_str(body) {
+            Ok(interaction) => interaction,
+            Err(_) => return,
+        };
+        redact(&mut interaction);
+        let entry = RecordedInteraction {
+            received_at: Timestamp::now(),
+            interaction,
+        };
+
+        let mut entries = self.entries.lock().unwrap();
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Gets the recorded interactions, newest first.
+    pub fn snapshot(&self) -> Vec<RecordedInteraction> {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().rev().cloned().collect()
+    }
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_KEYS.contains(&key.as_str()) {
+                    *value = Value::String(REDACTED_VALUE.into());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
@@ -1,23 +1,39 @@
-use crate::{controllers::interactions_service, models::Config};
+use crate::{
+    controllers::{debug_service, interactions_service},
+    models::{Config, RecentInteractions},
+};
 use actix_web::{middleware::Logger, web::Data, App, HttpServer};
 use anyhow::Context;
-use std::net::Ipv4Addr;
+use std::{net::Ipv4Addr, path::Path};
 use tracing::instrument;
 
+/// The function app's folder, relative to the handler's working directory set
+/// in `host.json`.
+const FUNCTIONS_ROOT: &str = "..";
+
 #[instrument]
 pub async fn start() -> anyhow::Result<()> {
     // Read config from environment
     let config: Config = envy::from_env().context("error reading config")?;
+    config
+        .interactions_path
+        .check_function(Path::new(FUNCTIONS_ROOT))
+        .context("error checking interactions path")?;
 
     // Shared data
     let port = config.port;
+    let interactions_path = config.interactions_path.clone();
+    let recent_interactions =
+        Data::new(RecentInteractions::new(config.debug_interactions));
 
     // Start web server
     HttpServer::new(move || {
         let logger = Logger::default();
         App::new()
             .app_data(Data::new(config.clone()))
-            .service(interactions_service())
+            .app_data(recent_interactions.clone())
+            .service(interactions_service(&interactions_path))
+            .service(debug_service())
             .wrap(logger)
     })
     .bind((Ipv4Addr::UNSPECIFIED, port))?
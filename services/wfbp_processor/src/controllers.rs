@@ -1,5 +1,7 @@
 mod commands;
+mod debug;
 mod interactions;
 
 pub use commands::*;
+pub use debug::*;
 pub use interactions::*;
@@ -0,0 +1,44 @@
+use crate::models::RateLimitStatus;
+use actix_web::{
+    dev::HttpServiceFactory,
+    http::StatusCode,
+    post,
+    web::{scope, Data, Json},
+};
+use std::collections::HashMap;
+use tracing::instrument;
+use wfbp_azure::functions::{FunctionsOutput, HttpOutput};
+use wfbp_discord::DiscordRestClient;
+
+pub fn debug_service() -> impl HttpServiceFactory + 'static {
+    scope("/debug").service(get_rate_limits)
+}
+
+/// Lists the state of each Discord rate limit bucket. This function requires
+/// the admin key (see `func/debug/function.json`).
+#[post("")]
+#[instrument(skip(discord_client))]
+async fn get_rate_limits(
+    discord_client: Data<DiscordRestClient>,
+) -> Json<FunctionsOutput<(), HttpOutput<Vec<RateLimitStatus>>>> {
+    let rate_limits = discord_client
+        .rate_limit_snapshot()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Json(FunctionsOutput {
+        outputs: (),
+        logs: vec![],
+        return_value: HttpOutput {
+            status_code: StatusCode::OK.as_u16(),
+            headers: {
+                let mut headers = HashMap::new();
+                headers
+                    .insert("content-type".into(), "application/json".into());
+                headers
+            },
+            body: rate_limits,
+        },
+    })
+}
@@ -6,23 +6,14 @@ mod startup;
 
 #[actix_web::main]
 async fn main() -> anyhow::Result<()> {
-    use tracing::{error, Level};
-    use tracing_subscriber::FmtSubscriber;
+    use anyhow::Context;
+    use tracing::error;
+    use wfbp_azure::logging::LoggingConfig;
 
     // Setup tracing
-    if cfg!(debug_assertions) {
-        FmtSubscriber::builder()
-            .compact()
-            .with_max_level(Level::DEBUG)
-            .try_init()
-            .expect("error registering tracing subscriber");
-    } else {
-        FmtSubscriber::builder()
-            .json()
-            .with_max_level(Level::INFO)
-            .try_init()
-            .expect("error registering tracing subscriber");
-    };
+    let logging: LoggingConfig =
+        envy::from_env().context("error reading logging config")?;
+    logging.init()?;
 
     // Run application
     if let Err(error) = startup::start().await {
@@ -2,8 +2,10 @@ mod commands;
 mod config;
 mod errors;
 mod functions;
+mod rate_limits;
 
 pub use commands::*;
 pub use config::*;
 pub use errors::*;
 pub use functions::*;
+pub use rate_limits::*;
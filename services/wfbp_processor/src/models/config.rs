@@ -1,16 +1,123 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
-use wfbp_discord::{middleware::ClientSecret, models::Snowflake};
+use wfbp_discord::{
+    middleware::{ClientSecret, OAuthScope},
+    models::Snowflake,
+};
+use wfbp_http::middleware::RetryConfig;
+use wfbp_wm::{
+    middleware::{CircuitBreakerConfig, RateLimitConfig},
+    models::{Language, Platform},
+    ItemOrdersCacheConfig,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
     pub app_id: Snowflake,
     pub client_id: Snowflake,
     pub client_secret: ClientSecret,
+    /// The OAuth2 scopes to request when authenticating with Discord.
+    #[serde(default)]
+    pub discord_scope: OAuthScope,
+    /// The bot's token, used for the few routes which need bot
+    /// authentication.
+    #[serde(default)]
+    pub bot_token: Option<ClientSecret>,
+    /// The user who can run admin commands, like refreshing the item index.
+    #[serde(default)]
+    pub owner_id: Option<Snowflake>,
     #[serde(rename = "functions_customhandler_port", default = "default_port")]
     pub port: u16,
+    /// Whether to ask APIs to compress their responses.
+    #[serde(default = "default_http_compression")]
+    pub http_compression: bool,
+    #[serde(default)]
+    pub platinum_emoji_id: Option<Snowflake>,
+    #[serde(default)]
+    pub platinum_emoji_guilds: Vec<Snowflake>,
+    #[serde(default = "default_wm_failure_threshold")]
+    pub wm_failure_threshold: u32,
+    #[serde(default = "default_wm_cooldown_secs")]
+    pub wm_cooldown_secs: u64,
+    #[serde(default = "default_wm_orders_cache_size")]
+    pub wm_orders_cache_size: usize,
+    #[serde(default = "default_wm_orders_cache_ttl_secs")]
+    pub wm_orders_cache_ttl_secs: u64,
+    #[serde(default)]
+    pub wm_bypass_cache: bool,
+    #[serde(default = "default_wm_requests_per_second")]
+    pub wm_requests_per_second: f64,
+    #[serde(default = "default_wm_request_burst")]
+    pub wm_request_burst: u32,
+    /// How many times failed warframe.market requests are retried.
+    #[serde(default = "default_wm_max_retries")]
+    pub wm_max_retries: usize,
+    #[serde(default = "default_wm_backoff_base_millis")]
+    pub wm_backoff_base_millis: u64,
+    /// The platform to get prices for when a command doesn't specify one.
+    #[serde(default)]
+    pub wm_default_platform: Platform,
+    /// The language to request localized item details in.
+    #[serde(default)]
+    pub wm_default_language: Language,
+    #[serde(default = "default_command_cooldown_secs")]
+    pub command_cooldown_secs: u64,
+    /// Overwrite the registered commands on startup even if they are already
+    /// up to date.
+    #[serde(default)]
+    pub force_command_sync: bool,
+    /// Commands which tell users they're disabled instead of running.
+    #[serde(default)]
+    pub disabled_commands: Vec<String>,
+    /// Guilds the bot can be used in. If empty, every guild is allowed.
+    #[serde(default)]
+    pub allowed_guilds: Vec<Snowflake>,
+    /// The secret key used to hash user IDs before they're logged. Each
+    /// deployment should use its own key. Without one, users aren't logged.
+    #[serde(default)]
+    pub user_hash_key: Option<ClientSecret>,
 }
 
 fn default_port() -> u16 {
     3000
 }
+
+fn default_http_compression() -> bool {
+    true
+}
+
+fn default_wm_failure_threshold() -> u32 {
+    CircuitBreakerConfig::default().failure_threshold
+}
+
+fn default_wm_cooldown_secs() -> u64 {
+    CircuitBreakerConfig::default().cooldown.as_secs()
+}
+
+fn default_wm_orders_cache_size() -> usize {
+    ItemOrdersCacheConfig::default().capacity
+}
+
+fn default_wm_orders_cache_ttl_secs() -> u64 {
+    ItemOrdersCacheConfig::default().ttl.as_secs()
+}
+
+fn default_wm_requests_per_second() -> f64 {
+    RateLimitConfig::default().requests_per_second
+}
+
+fn default_wm_request_burst() -> u32 {
+    RateLimitConfig::default().burst
+}
+
+fn default_wm_max_retries() -> usize {
+    RetryConfig::default().max_retries
+}
+
+fn default_wm_backoff_base_millis() -> u64 {
+    RetryConfig::default().backoff_base_millis
+}
+
+fn default_command_cooldown_secs() -> u64 {
+    5
+}
@@ -0,0 +1,23 @@
+use serde::Serialize;
+use wfbp_discord::{RateLimitBucket, RemainingInfo};
+
+/// The state of a Discord rate limit bucket.
+#[derive(Clone, Debug, Serialize)]
+pub struct RateLimitStatus {
+    pub method: String,
+    pub route: &'static str,
+    pub major_parameters: [u64; 2],
+    #[serde(flatten)]
+    pub info: RemainingInfo,
+}
+
+impl From<(RateLimitBucket, RemainingInfo)> for RateLimitStatus {
+    fn from((bucket, info): (RateLimitBucket, RemainingInfo)) -> Self {
+        RateLimitStatus {
+            method: bucket.method.to_string(),
+            route: bucket.route,
+            major_parameters: bucket.major_parameters,
+            info,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    controllers::{commands_service, interactions_service},
+    controllers::{commands_service, debug_service, interactions_service},
     models::Config,
 };
 use actix_web::{middleware::Logger, web::Data, App, HttpServer};
@@ -7,11 +7,20 @@ use anyhow::Context;
 use reqwest::Client;
 use std::{net::Ipv4Addr, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
-use tracing::instrument;
-use wfbp_commands::CommandRegistry;
-use wfbp_discord::DiscordRestClient;
-use wfbp_logic::{commands::pc_command, services::WarframeItemService};
-use wfbp_wm::WmRestClient;
+use tracing::{instrument, warn};
+use wfbp_commands::{CommandRegistry, UserHasher};
+use wfbp_discord::{DiscordClientConfig, DiscordRestClient};
+use wfbp_http::middleware::RetryConfig;
+use wfbp_logic::{
+    commands::{help_command, pc_command, CommandContext},
+    services::{PlatinumEmojiService, WarframeItemService},
+};
+use wfbp_wm::{
+    middleware::{CircuitBreakerConfig, RateLimitConfig},
+    ItemOrdersCacheConfig, WmClientConfig, WmRestClient,
+};
+
+const ITEM_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 #[instrument]
 pub async fn start() -> anyhow::Result<()> {
@@ -24,30 +33,108 @@ pub async fn start() -> anyhow::Result<()> {
         .timeout(Duration::from_secs(30))
         .https_only(true)
         .user_agent(concat!("TEST_BOT/", env!("CARGO_PKG_VERSION")))
+        .gzip(config.http_compression)
+        .brotli(config.http_compression)
+        .deflate(config.http_compression)
         .build()
         .context("error creating reqwest client")?;
-    let discord_client = DiscordRestClient::new(
+    let discord_client = DiscordRestClient::new_with_config(
         raw_client.clone(),
+        DiscordRestClient::BASE_URL,
         config.client_id,
         Arc::new(std::mem::take(&mut config.client_secret)),
+        DiscordClientConfig {
+            scope: config.discord_scope.clone(),
+            bot_token: config.bot_token.take().map(Arc::new),
+        },
+    );
+    let item_orders_cache = if config.wm_bypass_cache {
+        None
+    } else {
+        Some(ItemOrdersCacheConfig {
+            capacity: config.wm_orders_cache_size,
+            ttl: Duration::from_secs(config.wm_orders_cache_ttl_secs),
+        })
+    };
+    let wm_client = WmRestClient::new_with_config(
+        raw_client.clone(),
+        WmClientConfig {
+            circuit_breaker: CircuitBreakerConfig {
+                failure_threshold: config.wm_failure_threshold,
+                cooldown: Duration::from_secs(config.wm_cooldown_secs),
+            },
+            item_orders_cache,
+            rate_limit: RateLimitConfig {
+                requests_per_second: config.wm_requests_per_second,
+                burst: config.wm_request_burst,
+            },
+            default_platform: config.wm_default_platform,
+            default_language: config.wm_default_language,
+            retry: RetryConfig {
+                max_retries: config.wm_max_retries,
+                backoff_base_millis: config.wm_backoff_base_millis,
+            },
+        },
     );
-    let wm_client = WmRestClient::new(raw_client.clone());
     let item_service = WarframeItemService::new(wm_client.clone())
         .await
         .context("error creating warframe item service")?;
+    tokio::spawn(refresh_items(item_service.clone(), wm_client.clone()));
+    let platinum_emoji = PlatinumEmojiService::new(
+        config.platinum_emoji_id,
+        config.platinum_emoji_guilds.iter().copied(),
+    );
 
     // Create command registry
     let lazy_command_registry = Arc::new(RwLock::new(None));
-    let command_registry = CommandRegistry::new(vec![pc_command(
-        discord_client.clone(),
-        wm_client.clone(),
-        item_service.clone(),
-        config.app_id,
-    )]);
+    let command_context = CommandContext {
+        discord_client: discord_client.clone(),
+        wm_client: wm_client.clone(),
+        item_service: item_service.clone(),
+        platinum_emoji,
+        app_id: config.app_id,
+        owner_id: config.owner_id,
+    };
+    let command_registry = CommandRegistry::new(vec![
+        pc_command(
+            command_context,
+            Duration::from_secs(config.command_cooldown_secs),
+        ),
+        help_command(discord_client.clone(), lazy_command_registry.clone()),
+    ]);
     let _ = lazy_command_registry
         .write()
         .await
         .insert(Arc::downgrade(&command_registry));
+    command_registry
+        .set_discord_client(discord_client.clone())
+        .await;
+    for name in config.disabled_commands.iter() {
+        if !command_registry.set_command_enabled(name, false).await {
+            warn!(%name, "can't disable unknown command");
+        }
+    }
+    command_registry
+        .set_allowed_guilds(config.allowed_guilds.iter().copied())
+        .await;
+    match config.user_hash_key.as_deref() {
+        Some(key) if !key.is_empty() => {
+            command_registry.set_user_hasher(UserHasher::new(key)).await;
+        }
+        _ => warn!("no user hash key is set, users won't be recorded in logs"),
+    }
+
+    // Sync commands with Discord
+    let result = command_registry
+        .sync_commands(
+            &discord_client,
+            config.app_id,
+            config.force_command_sync,
+        )
+        .await;
+    if let Err(error) = result {
+        warn!(?error, "error syncing application commands");
+    }
 
     let port = config.port;
 
@@ -63,6 +150,7 @@ pub async fn start() -> anyhow::Result<()> {
             .app_data(Data::from(command_registry.clone()))
             .service(interactions_service())
             .service(commands_service())
+            .service(debug_service())
             .wrap(logger)
     })
     .bind((Ipv4Addr::UNSPECIFIED, port))?
@@ -70,3 +158,17 @@ pub async fn start() -> anyhow::Result<()> {
     .await
     .context("error running web server")
 }
+
+async fn refresh_items(
+    item_service: WarframeItemService,
+    wm_client: WmRestClient,
+) {
+    let mut interval = tokio::time::interval(ITEM_REFRESH_INTERVAL);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if let Err(error) = item_service.refresh(&wm_client).await {
+            warn!(?error, "error refreshing items");
+        }
+    }
+}